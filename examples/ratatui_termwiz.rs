@@ -0,0 +1,54 @@
+use ratatui::backend::TermwizBackend;
+use ratatui::widgets::{Block, Borders};
+use ratatui::Terminal;
+use std::error::Error;
+use std::time::Duration;
+use termwiz::input::InputEvent;
+use termwiz::terminal::Terminal as _;
+use tui_textarea::{Input, Key, TextArea};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let backend = TermwizBackend::new()?;
+    let mut term = Terminal::new(backend)?;
+    term.hide_cursor()?;
+
+    let mut textarea = TextArea::default();
+    textarea.set_block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Termwiz Minimal Example"),
+    );
+
+    loop {
+        term.draw(|f| {
+            f.render_widget(textarea.widget(), f.size());
+        })?;
+
+        let input = term
+            .backend_mut()
+            .buffered_terminal_mut()
+            .terminal()
+            .poll_input(Some(Duration::from_millis(100)))?;
+        match input {
+            Some(InputEvent::Resized { cols, rows }) => {
+                term.backend_mut()
+                    .buffered_terminal_mut()
+                    .resize(cols, rows);
+            }
+            Some(input) => match input.into() {
+                Input { key: Key::Esc, .. } => break,
+                input => {
+                    textarea.input(input);
+                }
+            },
+            None => {}
+        }
+    }
+
+    term.show_cursor()?;
+    term.flush()?;
+    drop(term); // Leave the alternate screen before printing the result
+
+    println!("Lines: {:?}", textarea.lines());
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use std::env;
+use std::fs;
+use std::io;
+use std::io::BufRead;
+use tui::backend::CrosstermBackend;
+use tui::style::{Color, Modifier, Style};
+use tui::widgets::{Block, Borders};
+use tui::Terminal;
+use tui_textarea::{Input, Key, TextArea, Vim, VimMode};
+
+fn cursor_color(mode: VimMode) -> Color {
+    match mode {
+        VimMode::Insert => Color::LightBlue,
+        VimMode::Visual => Color::LightYellow,
+        _ => Color::Reset,
+    }
+}
+
+fn main() -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+
+    enable_raw_mode()?;
+    crossterm::execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut term = Terminal::new(backend)?;
+
+    let mut textarea = if let Some(path) = env::args().nth(1) {
+        let file = fs::File::open(path)?;
+        io::BufReader::new(file)
+            .lines()
+            .collect::<io::Result<_>>()?
+    } else {
+        TextArea::default()
+    };
+
+    let mut vim = Vim::new();
+    loop {
+        // Show current mode in title of the block
+        let mode = vim.mode();
+        let title = if mode == VimMode::Normal {
+            format!("{} MODE (type q to quit)", mode)
+        } else {
+            format!("{} MODE (type Esc to back to normal mode)", mode)
+        };
+        let block = Block::default().borders(Borders::ALL).title(title);
+        textarea.set_block(block);
+
+        // Change the cursor color looking at current mode
+        let style = Style::default()
+            .fg(cursor_color(mode))
+            .add_modifier(Modifier::REVERSED);
+        textarea.set_cursor_style(style);
+
+        term.draw(|f| f.render_widget(textarea.widget(), f.size()))?;
+
+        match crossterm::event::read()?.into() {
+            Input {
+                key: Key::Char('q'),
+                ctrl: false,
+                alt: false,
+                ..
+            } if mode == VimMode::Normal => break,
+            input => {
+                vim.input(&mut textarea, input);
+            }
+        }
+    }
+
+    disable_raw_mode()?;
+    crossterm::execute!(
+        term.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture
+    )?;
+    term.show_cursor()?;
+
+    println!("Lines: {:?}", textarea.lines());
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use std::ops::Range;
+
+const PAIRS: [(char, char); 3] = [('(', ')'), ('[', ']'), ('{', '}')];
+
+const QUOTES: [char; 2] = ['"', '\''];
+
+// Closing character inserted along with the opening character by auto-pairs
+pub fn auto_pair_close(c: char) -> Option<char> {
+    if QUOTES.contains(&c) {
+        return Some(c);
+    }
+    PAIRS
+        .iter()
+        .find(|(open, _)| *open == c)
+        .map(|(_, close)| *close)
+}
+
+pub fn is_auto_pair_close(c: char) -> bool {
+    QUOTES.contains(&c) || PAIRS.iter().any(|(_, close)| *close == c)
+}
+
+fn find_forward(
+    lines: &[String],
+    (row, col): (usize, usize),
+    open: char,
+    close: char,
+    end: usize,
+) -> Option<(usize, usize)> {
+    let mut depth = 0usize;
+    for (r, line) in lines[..end].iter().enumerate().skip(row) {
+        let start = if r == row { col + 1 } else { 0 };
+        for (c, ch) in line.chars().enumerate().skip(start) {
+            if ch == open {
+                depth += 1;
+            } else if ch == close {
+                if depth == 0 {
+                    return Some((r, c));
+                }
+                depth -= 1;
+            }
+        }
+    }
+    None
+}
+
+fn find_backward(
+    lines: &[String],
+    (row, col): (usize, usize),
+    open: char,
+    close: char,
+    start: usize,
+) -> Option<(usize, usize)> {
+    let mut depth = 0usize;
+    for r in (start..=row).rev() {
+        let line = &lines[r];
+        let count = line.chars().count();
+        let end = if r == row { col } else { count };
+        for (i, ch) in line.chars().rev().skip(count - end).enumerate() {
+            if ch == close {
+                depth += 1;
+            } else if ch == open {
+                if depth == 0 {
+                    return Some((r, end - 1 - i));
+                }
+                depth -= 1;
+            }
+        }
+    }
+    None
+}
+
+pub fn find_matching_bracket(lines: &[String], pos: (usize, usize)) -> Option<(usize, usize)> {
+    find_matching_bracket_within(lines, pos, 0..lines.len())
+}
+
+// Find the matching bracket only in the rows. Lines outside the rows are not scanned so that finding the bracket to
+// highlight on rendering does not scan the whole text when the bracket is not closed
+pub fn find_matching_bracket_within(
+    lines: &[String],
+    (row, col): (usize, usize),
+    rows: Range<usize>,
+) -> Option<(usize, usize)> {
+    let c = lines[row].chars().nth(col)?;
+    for (open, close) in PAIRS {
+        if c == open {
+            return find_forward(lines, (row, col), open, close, rows.end);
+        }
+        if c == close {
+            return find_backward(lines, (row, col), open, close, rows.start);
+        }
+    }
+    None
+}
@@ -1,4 +1,5 @@
-use crate::widget::Viewport;
+use crate::textarea::TextArea;
+use crate::util::{next_grapheme_col, prev_grapheme_col};
 use crate::word::{find_word_start_backward, find_word_start_forward};
 #[cfg(feature = "arbitrary")]
 use arbitrary::Arbitrary;
@@ -12,6 +13,7 @@ use std::cmp;
 #[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
 pub enum CursorMove {
     /// Move cursor forward by one character. When the cursor is at the end of line, it moves to the head of next line.
+    /// A grapheme cluster consisting of multiple characters such as an emoji sequence is skipped at once.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
@@ -24,7 +26,7 @@ pub enum CursorMove {
     /// ```
     Forward,
     /// Move cursor backward by one character. When the cursor is at the head of line, it moves to the end of previous
-    /// line.
+    /// line. A grapheme cluster consisting of multiple characters is skipped at once as [`CursorMove::Forward`] does.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
@@ -229,11 +231,12 @@ impl CursorMove {
     pub(crate) fn next_cursor(
         &self,
         (row, col): (usize, usize),
-        lines: &[String],
-        viewport: &Viewport,
+        textarea: &TextArea<'_>,
     ) -> Option<(usize, usize)> {
         use CursorMove::*;
 
+        let lines = textarea.lines();
+
         fn fit_col(col: usize, line: &str) -> usize {
             cmp::min(col, line.chars().count())
         }
@@ -242,12 +245,12 @@ impl CursorMove {
             Forward if col >= lines[row].chars().count() => {
                 (row + 1 < lines.len()).then(|| (row + 1, 0))
             }
-            Forward => Some((row, col + 1)),
+            Forward => Some((row, next_grapheme_col(&lines[row], col))),
             Back if col == 0 => {
                 let row = row.checked_sub(1)?;
                 Some((row, lines[row].chars().count()))
             }
-            Back => Some((row, col - 1)),
+            Back => Some((row, prev_grapheme_col(&lines[row], col))),
             Up => {
                 let row = row.checked_sub(1)?;
                 Some((row, fit_col(col, &lines[row])))
@@ -309,12 +312,28 @@ impl CursorMove {
                 Some((row, col))
             }
             InViewport => {
-                let (row_top, col_top, row_bottom, col_bottom) = viewport.position();
+                let (row_top, col_top, row_bottom, col_bottom) = textarea.viewport.position();
 
                 let row = row.clamp(row_top as usize, row_bottom as usize);
                 let row = cmp::min(row, lines.len() - 1);
-                let col = col.clamp(col_top as usize, col_bottom as usize);
                 let col = fit_col(col, &lines[row]);
+                if textarea.wrap() {
+                    return Some((row, col)); // Lines are never scrolled horizontally while wrapping
+                }
+
+                // Viewport columns are visual columns where tabs and wide characters occupy multiple columns
+                let screen_col = textarea.screen_col(row, col);
+                let clamped = screen_col.clamp(col_top as usize, col_bottom as usize);
+                if clamped == screen_col {
+                    return Some((row, col));
+                }
+                // Wide character may be partially hidden at the edges of the viewport
+                let mut col = textarea.col_at_screen_col(row, clamped);
+                if textarea.screen_col(row, col) < col_top as usize {
+                    col = fit_col(col + 1, &lines[row]);
+                } else if col > 0 && textarea.screen_col(row, col + 1) > col_bottom as usize + 1 {
+                    col -= 1;
+                }
 
                 Some((row, col))
             }
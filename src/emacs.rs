@@ -0,0 +1,104 @@
+use crate::input::{Input, Key};
+use crate::textarea::TextArea;
+
+/// Handle the input with Emacs-like key mappings on top of the default key mappings of [`TextArea::input`]. This
+/// function returns if the input modified text contents or not in the textarea. The following mappings are added or
+/// overridden. Other inputs are handled by [`TextArea::input`].
+///
+/// | Mappings                | Description                                         |
+/// |-------------------------|-----------------------------------------------------|
+/// | `Ctrl+Space`, `Ctrl+@`  | Set mark. Moving the cursor extends the region      |
+/// | `Ctrl+G`                | Cancel the region                                   |
+/// | `Ctrl+W`                | Kill the region, or delete one word before cursor   |
+/// | `Alt+W`                 | Copy the region                                     |
+/// | `Ctrl+/`, `Ctrl+_`      | Undo                                                |
+/// | `Ctrl+S`                | Search forward (only when `search` feature enabled) |
+/// | `Ctrl+R`                | Search back (only when `search` feature enabled)    |
+///
+/// The region is the text selection of [`TextArea`]. Killed or copied text is saved to the yank buffer and it can be
+/// pasted with `Ctrl+Y`. Text search requires a pattern set by `TextArea::set_search_pattern`.
+/// ```
+/// use tui_textarea::{emacs_input, TextArea, Input, Key};
+///
+/// let mut textarea = TextArea::from(["hello world"]);
+///
+/// let ctrl = |c| Input { key: Key::Char(c), ctrl: true, alt: false, shift: false };
+///
+/// // Kill "hello " and yank it at the end
+/// emacs_input(&mut textarea, ctrl(' '));
+/// emacs_input(&mut textarea, Input { key: Key::Char('f'), ctrl: false, alt: true, shift: false });
+/// assert!(emacs_input(&mut textarea, ctrl('w')));
+/// assert_eq!(textarea.lines(), ["world"]);
+/// emacs_input(&mut textarea, ctrl('e'));
+/// emacs_input(&mut textarea, ctrl('y'));
+/// assert_eq!(textarea.lines(), ["worldhello "]);
+/// ```
+pub fn emacs_input(textarea: &mut TextArea<'_>, input: impl Into<Input>) -> bool {
+    let input = input.into();
+    let version = textarea.version();
+    let read_only = textarea.is_read_only();
+    match input {
+        Input {
+            key: Key::Char(' ' | '@'),
+            ctrl: true,
+            alt: false,
+            ..
+        } => textarea.start_selection(),
+        Input {
+            key: Key::Char('g'),
+            ctrl: true,
+            alt: false,
+            ..
+        } => textarea.cancel_selection(),
+        Input {
+            key: Key::Char('w'),
+            ctrl: true,
+            alt: false,
+            ..
+        } if textarea.selection_range().is_some() => {
+            if read_only {
+                textarea.copy();
+            } else {
+                textarea.cut();
+            }
+        }
+        Input {
+            key: Key::Char('w'),
+            ctrl: false,
+            alt: true,
+            ..
+        } => {
+            textarea.copy();
+        }
+        Input {
+            key: Key::Char('/' | '_'),
+            ctrl: true,
+            alt: false,
+            ..
+        } if !read_only => {
+            textarea.undo();
+        }
+        #[cfg(feature = "search")]
+        Input {
+            key: Key::Char('s'),
+            ctrl: true,
+            alt: false,
+            ..
+        } => {
+            textarea.search_forward(false);
+        }
+        #[cfg(feature = "search")]
+        Input {
+            key: Key::Char('r'),
+            ctrl: true,
+            alt: false,
+            ..
+        } => {
+            textarea.search_back(false);
+        }
+        input => {
+            textarea.input(input);
+        }
+    }
+    textarea.version() != version
+}
@@ -1,51 +1,69 @@
-use crate::tui::style::Style;
+use crate::tui::style::{Modifier, Style};
 use crate::tui::text::{Line, Span};
-use crate::util::{num_digits, spaces};
+use crate::util::{char_width, num_digits, spaces};
 use std::borrow::Cow;
-use std::cmp::Ordering;
+use std::cmp::{self, Ordering};
+use std::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
 
 enum Boundary {
     Cursor(Style),
+    Extra(Style),
+    Bracket(Style),
+    Select(Style),
+    #[cfg(feature = "search")]
+    SearchCurrent(Style),
     #[cfg(feature = "search")]
     Search(Style),
-    End,
 }
 
 impl Boundary {
     fn cmp(&self, other: &Boundary) -> Ordering {
         fn rank(b: &Boundary) -> u8 {
             match b {
-                Boundary::Cursor(_) => 2,
+                Boundary::Cursor(_) => 6,
+                Boundary::Extra(_) => 5,
+                Boundary::Bracket(_) => 4,
+                Boundary::Select(_) => 3,
+                #[cfg(feature = "search")]
+                Boundary::SearchCurrent(_) => 2,
                 #[cfg(feature = "search")]
                 Boundary::Search(_) => 1,
-                Boundary::End => 0,
             }
         }
         rank(self).cmp(&rank(other))
     }
 
-    fn style(&self) -> Option<Style> {
+    // Style of the range layered on the style below it. Extra highlights are patched on top of it and others replace it
+    fn layer(&self, below: Style) -> Style {
         match self {
-            Boundary::Cursor(s) => Some(*s),
+            Boundary::Cursor(s) => *s,
+            Boundary::Extra(s) => below.patch(*s),
+            Boundary::Bracket(s) => *s,
+            Boundary::Select(s) => *s,
             #[cfg(feature = "search")]
-            Boundary::Search(s) => Some(*s),
-            Boundary::End => None,
+            Boundary::SearchCurrent(s) => *s,
+            #[cfg(feature = "search")]
+            Boundary::Search(s) => *s,
         }
     }
 }
 
-fn replace_tabs(s: &str, tab_len: u8) -> Cow<'_, str> {
-    let tab = spaces(tab_len);
+// Expand tabs to the next tab stops. `col` is the visual column where the string starts and it is updated to the
+// column where the string ends
+fn replace_tabs<'a>(s: &'a str, tab_len: u8, col: &mut usize) -> Cow<'a, str> {
     let mut buf = String::new();
     for (i, c) in s.char_indices() {
+        let w = char_width(c, *col, tab_len);
+        *col += w;
         if buf.is_empty() {
             if c == '\t' {
-                buf.reserve(s.len());
+                buf.reserve(s.len() + w);
                 buf.push_str(&s[..i]);
-                buf.push_str(tab);
+                buf.push_str(spaces(w as u8));
             }
         } else if c == '\t' {
-            buf.push_str(tab);
+            buf.push_str(spaces(w as u8));
         } else {
             buf.push(c);
         }
@@ -57,14 +75,95 @@ fn replace_tabs(s: &str, tab_len: u8) -> Cow<'_, str> {
     }
 }
 
+// How characters in a line are rendered as glyphs
+struct Glyphs {
+    tab_len: u8,
+    visible: bool,
+    trailing: usize, // Byte offset where trailing whitespaces start
+    col: usize,      // Visual column of the next text
+    mask: Option<char>,
+}
+
+impl Glyphs {
+    // Push the text at the byte offset in the line. When whitespaces are visible, tabs are rendered as an arrow padded
+    // to the next tab stop and trailing spaces are rendered as middle dots in dim style. Their widths are the same as
+    // usual so the cursor position is not affected. When the mask character is set, each character is rendered as it
+    fn push_text<'a>(
+        &mut self,
+        spans: &mut Vec<Span<'a>>,
+        text: &'a str,
+        offset: usize,
+        style: Style,
+    ) {
+        if let Some(mask) = self.mask {
+            let masked: String = text.chars().map(|_| mask).collect();
+            spans.push(Span::styled(masked, style));
+            return;
+        }
+
+        if !self.visible {
+            let text = replace_tabs(text, self.tab_len, &mut self.col);
+            spans.push(Span::styled(text, style));
+            return;
+        }
+
+        let dim = style.add_modifier(Modifier::DIM);
+        let mut start = 0;
+        for (i, c) in text.char_indices() {
+            let w = char_width(c, self.col, self.tab_len);
+            self.col += w;
+            let glyph = match c {
+                '\t' if w > 0 => format!("\u{2192}{}", spaces(w as u8 - 1)),
+                '\t' => String::new(),
+                ' ' if offset + i >= self.trailing => "\u{b7}".to_string(),
+                _ => continue,
+            };
+            if start < i {
+                spans.push(Span::styled(&text[start..i], style));
+            }
+            if !glyph.is_empty() {
+                spans.push(Span::styled(glyph, dim));
+            }
+            start = i + c.len_utf8();
+        }
+        if start < text.len() {
+            spans.push(Span::styled(&text[start..], style));
+        }
+    }
+}
+
+fn push_segment<'a>(
+    spans: &mut Vec<Span<'a>>,
+    line: &'a str,
+    (start, end): (usize, usize),
+    style: Style,
+    syntax: &[(usize, usize, Style)],
+    glyphs: &mut Glyphs,
+) {
+    if syntax.is_empty() {
+        glyphs.push_text(spans, &line[start..end], start, style);
+        return;
+    }
+
+    // Syntax styles are the base layer. Styles of cursor, search, ... are patched on top of them
+    for &(s, e, syntax_style) in syntax {
+        let s = s.max(start);
+        let e = e.min(end);
+        if s < e {
+            glyphs.push_text(spans, &line[s..e], s, syntax_style.patch(style));
+        }
+    }
+}
+
 pub struct LineHighlighter<'a> {
     line: &'a str,
     spans: Vec<Span<'a>>,
-    boundaries: Vec<(Boundary, usize)>, // TODO: Consider smallvec
+    boundaries: Vec<(Boundary, usize, usize)>, // (kind, start, end) in byte offsets. TODO: Consider smallvec
+    syntax: Vec<(usize, usize, Style)>,
     style_begin: Style,
     cursor_at_end: bool,
     cursor_style: Style,
-    tab_len: u8,
+    glyphs: Glyphs,
 }
 
 impl<'a> LineHighlighter<'a> {
@@ -73,40 +172,170 @@ impl<'a> LineHighlighter<'a> {
             line,
             spans: vec![],
             boundaries: vec![],
+            syntax: vec![],
             style_begin: Style::default(),
             cursor_at_end: false,
             cursor_style,
-            tab_len,
+            glyphs: Glyphs {
+                tab_len,
+                visible: false,
+                trailing: line.len(),
+                col: 0,
+                mask: None,
+            },
         }
     }
 
+    // Sign column is two cells of the sign and a space. Lines without sign are padded with spaces
+    pub fn sign(&mut self, sign: Option<(char, Style)>) {
+        let span = match sign {
+            Some((c, style)) => Span::styled(format!("{} ", c), style),
+            None => Span::raw(spaces(2)),
+        };
+        self.spans.push(span);
+    }
+
     pub fn line_number(&mut self, row: usize, lnum_len: u8, style: Style) {
         let pad = spaces(lnum_len - num_digits(row + 1) + 1);
         self.spans
             .push(Span::styled(format!("{}{} ", pad, row + 1), style));
     }
 
-    pub fn push_spans(&mut self, spans: impl IntoIterator<Item = Span<'a>>) {
-        self.spans.extend(spans);
+    // Show the distance from the cursor line. The cursor line shows its absolute line number aligned to left
+    pub fn relative_line_number(
+        &mut self,
+        row: usize,
+        cursor_row: usize,
+        lnum_len: u8,
+        style: Style,
+    ) {
+        let (num, left) = if row == cursor_row {
+            (row + 1, true)
+        } else {
+            (cmp::max(row, cursor_row) - cmp::min(row, cursor_row), false)
+        };
+        let pad = spaces(lnum_len - num_digits(num));
+        let text = if left {
+            format!(" {}{} ", num, pad)
+        } else {
+            format!(" {}{} ", pad, num)
+        };
+        self.spans.push(Span::styled(text, style));
     }
 
     pub fn cursor_line(&mut self, cursor_col: usize, style: Style) {
         if let Some((start, c)) = self.line.char_indices().nth(cursor_col) {
+            // Cursor covers the whole grapheme cluster such as an emoji sequence or a character with combining marks
+            let len = self.line[start..]
+                .graphemes(true)
+                .next()
+                .map_or(c.len_utf8(), |g| g.len());
             self.boundaries
-                .push((Boundary::Cursor(self.cursor_style), start));
-            self.boundaries.push((Boundary::End, start + c.len_utf8()));
+                .push((Boundary::Cursor(self.cursor_style), start, start + len));
         } else {
             self.cursor_at_end = true;
         }
         self.style_begin = style;
     }
 
+    pub fn bracket(&mut self, col: usize, style: Style) {
+        if let Some((start, c)) = self.line.char_indices().nth(col) {
+            self.boundaries
+                .push((Boundary::Bracket(style), start, start + c.len_utf8()));
+        }
+    }
+
+    pub fn selection(
+        &mut self,
+        row: usize,
+        (start_row, start_col): (usize, usize),
+        (end_row, end_col): (usize, usize),
+        style: Style,
+    ) {
+        if row < start_row || end_row < row {
+            return;
+        }
+        let byte_idx = |col| {
+            self.line
+                .char_indices()
+                .nth(col)
+                .map(|(i, _)| i)
+                .unwrap_or(self.line.len())
+        };
+        let start = if row == start_row {
+            byte_idx(start_col)
+        } else {
+            0
+        };
+        let end = if row == end_row {
+            byte_idx(end_col)
+        } else {
+            self.line.len()
+        };
+        if start < end {
+            self.boundaries.push((Boundary::Select(style), start, end));
+        }
+    }
+
+    // Highlight the character-wise column ranges. Where ranges overlap, their styles are patched in order
+    pub fn extra(&mut self, ranges: &[(Range<usize>, Style)]) {
+        let mut cols: Vec<_> = ranges.iter().flat_map(|(r, _)| [r.start, r.end]).collect();
+        cols.sort_unstable();
+        cols.dedup();
+
+        let byte_idx = |col| {
+            self.line
+                .char_indices()
+                .nth(col)
+                .map(|(i, _)| i)
+                .unwrap_or(self.line.len())
+        };
+
+        // Split the ranges into segments which don't overlap so that each segment has the layered style
+        for w in cols.windows(2) {
+            let (s, e) = (w[0], w[1]);
+            let style = ranges
+                .iter()
+                .filter(|(r, _)| r.start <= s && e <= r.end)
+                .fold(None, |acc: Option<Style>, &(_, style)| {
+                    Some(acc.map_or(style, |a| a.patch(style)))
+                });
+            let (start, end) = (byte_idx(s), byte_idx(e));
+            if let Some(style) = style.filter(|_| start < end) {
+                self.boundaries.push((Boundary::Extra(style), start, end));
+            }
+        }
+    }
+
+    pub fn show_whitespace(&mut self) {
+        let glyphs = &mut self.glyphs;
+        glyphs.visible = true;
+        glyphs.trailing = self.line.trim_end_matches(|c| c == ' ' || c == '\t').len();
+    }
+
+    pub fn mask(&mut self, mask: char) {
+        self.glyphs.mask = Some(mask);
+    }
+
+    pub fn syntax(&mut self, ranges: impl IntoIterator<Item = (usize, usize, Style)>) {
+        self.syntax.extend(ranges);
+    }
+
+    // The match containing the byte offset of `current` is highlighted with its style instead
     #[cfg(feature = "search")]
-    pub fn search(&mut self, matches: impl Iterator<Item = (usize, usize)>, style: Style) {
+    pub fn search(
+        &mut self,
+        matches: impl Iterator<Item = (usize, usize)>,
+        style: Style,
+        current: Option<(usize, Style)>,
+    ) {
         for (start, end) in matches {
             if start != end {
-                self.boundaries.push((Boundary::Search(style), start));
-                self.boundaries.push((Boundary::End, end));
+                let boundary = match current {
+                    Some((i, s)) if start <= i && i < end => Boundary::SearchCurrent(s),
+                    _ => Boundary::Search(style),
+                };
+                self.boundaries.push((boundary, start, end));
             }
         }
     }
@@ -116,55 +345,55 @@ impl<'a> LineHighlighter<'a> {
             line,
             mut spans,
             mut boundaries,
-            tab_len,
+            syntax,
+            mut glyphs,
             style_begin,
             cursor_style,
             cursor_at_end,
         } = self;
 
         if boundaries.is_empty() {
-            spans.push(Span::styled(replace_tabs(line, tab_len), style_begin));
+            push_segment(
+                &mut spans,
+                line,
+                (0, line.len()),
+                style_begin,
+                &syntax,
+                &mut glyphs,
+            );
             if cursor_at_end {
                 spans.push(Span::styled(" ", cursor_style));
             }
             return Line::from(spans);
         }
 
-        boundaries.sort_unstable_by(|(l, i), (r, j)| match i.cmp(j) {
+        // Ranges which start later are layered on top. Ranges starting at the same offset are layered by their kinds
+        boundaries.sort_by(|(l, i, _), (r, j, _)| match i.cmp(j) {
             Ordering::Equal => l.cmp(r),
             o => o,
         });
 
-        let mut boundaries = boundaries.into_iter();
-        let mut style = style_begin;
-        let mut start = 0;
-        let mut stack = vec![];
-
-        loop {
-            if let Some((next_boundary, end)) = boundaries.next() {
-                if start < end {
-                    spans.push(Span::styled(
-                        replace_tabs(&line[start..end], tab_len),
-                        style,
-                    ));
-                }
-
-                style = if let Some(s) = next_boundary.style() {
-                    stack.push(style);
-                    s
-                } else {
-                    stack.pop().unwrap_or(style_begin)
-                };
-                start = end;
-            } else {
-                if start != line.len() {
-                    spans.push(Span::styled(replace_tabs(&line[start..], tab_len), style));
-                }
-                if cursor_at_end {
-                    spans.push(Span::styled(" ", cursor_style));
-                }
-                return Line::from(spans);
-            }
+        // Split the line at every edge of the ranges so that ranges partially overlapping each other are rendered with
+        // the layered style in the overlap
+        let mut edges: Vec<_> = boundaries
+            .iter()
+            .flat_map(|&(_, start, end)| [start, end])
+            .chain([0, line.len()])
+            .collect();
+        edges.sort_unstable();
+        edges.dedup();
+
+        for w in edges.windows(2) {
+            let (start, end) = (w[0], w[1]);
+            let style = boundaries
+                .iter()
+                .filter(|(_, s, e)| *s <= start && end <= *e)
+                .fold(style_begin, |style, (b, _, _)| b.layer(style));
+            push_segment(&mut spans, line, (start, end), style, &syntax, &mut glyphs);
+        }
+        if cursor_at_end {
+            spans.push(Span::styled(" ", cursor_style));
         }
+        Line::from(spans)
     }
 }
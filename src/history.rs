@@ -1,4 +1,9 @@
+use std::cmp;
 use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+// Consecutive character insertions within this duration are grouped into one undo unit
+const COALESCE_TIMEOUT: Duration = Duration::from_secs(1);
 
 #[derive(Clone)]
 pub enum EditKind {
@@ -8,6 +13,13 @@ pub enum EditKind {
     DeleteNewline(usize),
     Insert(String, usize),
     Remove(String, usize),
+    // Multiple lines inserted at (row, byte offset). Unlike other kinds, it has its position in itself
+    InsertChunk(Vec<String>, usize, usize),
+    RemoveChunk(Vec<String>, usize, usize),
+    // Lines replaced with other lines. Each element is (row, old line, new line)
+    ReplaceLines(Vec<(usize, String, String)>),
+    // Consecutive lines from the row replaced with the different number of lines. (row, old lines, new lines)
+    SpliceLines(usize, Vec<String>, Vec<String>),
 }
 
 impl EditKind {
@@ -38,6 +50,65 @@ impl EditKind {
                 let end = *i + s.len();
                 lines[row].replace_range(*i..end, "");
             }
+            EditKind::InsertChunk(chunk, row, i) => {
+                debug_assert!(
+                    chunk.len() > 1,
+                    "chunk must contain multiple lines: {:?}",
+                    chunk
+                );
+                let (row, i) = (*row, *i);
+                let line = &mut lines[row];
+                let mut last = chunk[chunk.len() - 1].clone();
+                last.push_str(&line[i..]);
+                line.truncate(i);
+                line.push_str(&chunk[0]);
+                let middle = chunk[1..chunk.len() - 1].iter().cloned();
+                lines.splice(row + 1..row + 1, middle.chain(std::iter::once(last)));
+            }
+            EditKind::RemoveChunk(chunk, row, i) => {
+                debug_assert!(
+                    chunk.len() > 1,
+                    "chunk must contain multiple lines: {:?}",
+                    chunk
+                );
+                let (row, i) = (*row, *i);
+                let last_row = row + chunk.len() - 1;
+                let last_len = chunk[chunk.len() - 1].len();
+                let rest = lines[last_row][last_len..].to_string();
+                let line = &mut lines[row];
+                line.truncate(i);
+                line.push_str(&rest);
+                lines.drain(row + 1..=last_row);
+            }
+            EditKind::ReplaceLines(replaced) => {
+                for (row, _, new) in replaced {
+                    lines[*row] = new.clone();
+                }
+            }
+            EditKind::SpliceLines(row, old, new) => {
+                lines.splice(*row..*row + old.len(), new.iter().cloned());
+            }
+        }
+    }
+
+    // Rows changed by applying this edit at the row as (row, removed, inserted). The removed rows from the row are
+    // replaced with the inserted rows and the rows after them are shifted. Lines modified in place are not counted
+    fn changed_rows(&self, row: usize) -> (usize, usize, usize) {
+        match self {
+            EditKind::InsertNewline(0) => (row, 0, 1),
+            EditKind::InsertNewline(_) => (row + 1, 0, 1),
+            EditKind::DeleteNewline(_) if row == 0 => (row, 0, 0),
+            EditKind::DeleteNewline(0) => (row - 1, 1, 0),
+            EditKind::DeleteNewline(_) => (row, 1, 0),
+            EditKind::InsertChunk(chunk, row, 0) => (*row, 0, chunk.len() - 1),
+            EditKind::InsertChunk(chunk, row, _) => (*row + 1, 0, chunk.len() - 1),
+            EditKind::RemoveChunk(chunk, row, 0) => (*row, chunk.len() - 1, 0),
+            EditKind::RemoveChunk(chunk, row, _) => (*row + 1, chunk.len() - 1, 0),
+            EditKind::SpliceLines(row, old, new) => {
+                let kept = cmp::min(old.len(), new.len());
+                (*row + kept, old.len() - kept, new.len() - kept)
+            }
+            _ => (row, 0, 0),
         }
     }
 
@@ -50,6 +121,15 @@ impl EditKind {
             DeleteNewline(i) => InsertNewline(i),
             Insert(s, i) => Remove(s, i),
             Remove(s, i) => Insert(s, i),
+            InsertChunk(c, r, i) => RemoveChunk(c, r, i),
+            RemoveChunk(c, r, i) => InsertChunk(c, r, i),
+            ReplaceLines(replaced) => ReplaceLines(
+                replaced
+                    .into_iter()
+                    .map(|(row, old, new)| (row, new, old))
+                    .collect(),
+            ),
+            SpliceLines(row, old, new) => SpliceLines(row, new, old),
         }
     }
 }
@@ -59,6 +139,7 @@ pub struct Edit {
     kind: EditKind,
     cursor_before: (usize, usize),
     cursor_after: (usize, usize),
+    typed: bool, // Whether this edit consists only of characters typed one by one
 }
 
 impl Edit {
@@ -67,10 +148,12 @@ impl Edit {
         cursor_before: (usize, usize),
         cursor_after: (usize, usize),
     ) -> Self {
+        let typed = matches!(kind, EditKind::InsertChar(..));
         Self {
             kind,
             cursor_before,
             cursor_after,
+            typed,
         }
     }
 
@@ -84,6 +167,16 @@ impl Edit {
         self.kind.invert().apply(row, lines); // Undo is redo of inverted edit
     }
 
+    // Rows changed by redoing this edit. See `EditKind::changed_rows`
+    pub fn rows_on_redo(&self) -> (usize, usize, usize) {
+        self.kind.changed_rows(self.cursor_before.0)
+    }
+
+    // Rows changed by undoing this edit. See `EditKind::changed_rows`
+    pub fn rows_on_undo(&self) -> (usize, usize, usize) {
+        self.kind.invert().changed_rows(self.cursor_after.0)
+    }
+
     pub fn cursor_before(&self) -> (usize, usize) {
         self.cursor_before
     }
@@ -91,6 +184,44 @@ impl Edit {
     pub fn cursor_after(&self) -> (usize, usize) {
         self.cursor_after
     }
+
+    // Merge the next edit into this edit when both are typed characters of consecutive text in the same line. Other
+    // insertions such as pasting text break the group so that they can be undone separately
+    fn coalesce(&mut self, next: &Edit) -> bool {
+        if !self.typed || self.cursor_after != next.cursor_before {
+            return false;
+        }
+        let end = match &self.kind {
+            EditKind::InsertChar(c, i) => *i + c.len_utf8(),
+            EditKind::Insert(s, i) => *i + s.len(),
+            _ => return false,
+        };
+        let inserted = match &next.kind {
+            EditKind::InsertChar(c, i) if *i == end => *c,
+            _ => return false,
+        };
+
+        if let EditKind::InsertChar(c, i) = self.kind {
+            self.kind = EditKind::Insert(c.to_string(), i);
+        }
+        if let EditKind::Insert(s, _) = &mut self.kind {
+            s.push(inserted);
+        }
+        self.cursor_after = next.cursor_after;
+        true
+    }
+
+    // The first row modified by this edit. Rows after it may also be modified or shifted
+    pub fn first_row(&self) -> usize {
+        let row = cmp::min(self.cursor_before.0, self.cursor_after.0);
+        match &self.kind {
+            EditKind::ReplaceLines(replaced) => {
+                replaced.iter().map(|(r, _, _)| *r).fold(row, cmp::min)
+            }
+            EditKind::SpliceLines(r, _, _) => cmp::min(row, *r),
+            _ => row,
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -98,6 +229,7 @@ pub struct History {
     index: usize,
     max_items: usize,
     edits: VecDeque<Edit>,
+    last_pushed: Option<Instant>,
 }
 
 impl History {
@@ -106,6 +238,7 @@ impl History {
             index: 0,
             max_items,
             edits: VecDeque::new(),
+            last_pushed: None,
         }
     }
 
@@ -114,6 +247,19 @@ impl History {
             return;
         }
 
+        let now = Instant::now();
+        let within_timeout = self
+            .last_pushed
+            .map_or(false, |t| now.duration_since(t) < COALESCE_TIMEOUT);
+        self.last_pushed = Some(now);
+        if within_timeout && self.index == self.edits.len() {
+            if let Some(last) = self.edits.back_mut() {
+                if last.coalesce(&edit) {
+                    return;
+                }
+            }
+        }
+
         if self.edits.len() == self.max_items {
             self.edits.pop_front();
             self.index = self.index.saturating_sub(1);
@@ -127,21 +273,23 @@ impl History {
         self.edits.push_back(edit);
     }
 
-    pub fn redo(&mut self, lines: &mut Vec<String>) -> Option<(usize, usize)> {
+    pub fn redo(&mut self, lines: &mut Vec<String>) -> Option<&Edit> {
         if self.index == self.edits.len() {
             return None;
         }
         let edit = &self.edits[self.index];
         edit.redo(lines);
         self.index += 1;
-        Some(edit.cursor_after())
+        self.last_pushed = None;
+        Some(edit)
     }
 
-    pub fn undo(&mut self, lines: &mut Vec<String>) -> Option<(usize, usize)> {
+    pub fn undo(&mut self, lines: &mut Vec<String>) -> Option<&Edit> {
         self.index = self.index.checked_sub(1)?;
+        self.last_pushed = None; // Don't merge the next edit into the undone one
         let edit = &self.edits[self.index];
         edit.undo(lines);
-        Some(edit.cursor_before())
+        Some(edit)
     }
 
     pub fn max_items(&self) -> usize {
@@ -1,18 +1,27 @@
 #[cfg(any(feature = "crossterm", feature = "ratatui-crossterm"))]
 use crate::crossterm::event::{
     Event as CrosstermEvent, KeyCode, KeyEvent, KeyEventKind, KeyModifiers,
-    MouseEvent as CrosstermMouseEvent, MouseEventKind as CrosstermMouseEventKind,
+    MouseButton as CrosstermMouseButton, MouseEvent as CrosstermMouseEvent,
+    MouseEventKind as CrosstermMouseEventKind,
 };
 #[cfg(feature = "arbitrary")]
 use arbitrary::Arbitrary;
+#[cfg(feature = "ratatui-termwiz")]
+use std::sync::atomic::{AtomicBool, Ordering};
 #[cfg(any(feature = "termion", feature = "ratatui-termion"))]
 use termion::event::{Event as TermionEvent, Key as TermionKey, MouseEvent as TermionMouseEvent};
+#[cfg(feature = "ratatui-termwiz")]
+use termwiz::input::{
+    InputEvent as TermwizInputEvent, KeyCode as TermwizKeyCode, KeyEvent as TermwizKeyEvent,
+    Modifiers as TermwizModifiers, MouseButtons as TermwizMouseButtons,
+    MouseEvent as TermwizMouseEvent,
+};
 
 /// Backend-agnostic key input kind.
 ///
 /// This type is marked as `#[non_exhaustive]` since more keys may be supported in the future.
 #[non_exhaustive]
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
 pub enum Key {
     /// Normal letter key input.
@@ -36,13 +45,21 @@ pub enum Key {
     MouseScrollDown,
     /// Virtual key to scroll up by mouse
     MouseScrollUp,
+    /// Virtual key to click by mouse. The values are 0-based (column, row) position on terminal.
+    MouseClick(u16, u16),
+    /// Virtual key to drag by mouse while pressing the button. The values are 0-based (column, row) position on
+    /// terminal.
+    MouseDrag(u16, u16),
+    /// Virtual key to release the mouse button. The values are 0-based (column, row) position on terminal.
+    MouseRelease(u16, u16),
     /// An invalid key input (this key is always ignored by [`TextArea`](crate::TextArea)).
     Null,
 }
 
 /// Backend-agnostic key input type.
 ///
-/// When `crossterm` and/or `termion` features are enabled, converting their key input types into this `Input` type is defined.
+/// When `crossterm`, `termion` and/or `ratatui-termwiz` features are enabled, converting their key input types into
+/// this `Input` type is defined.
 /// ```no_run
 /// use tui_textarea::{TextArea, Input, Key};
 /// use crossterm::event::{Event, read};
@@ -71,9 +88,17 @@ pub enum Key {
 ///     key: Key::Char('a'),
 ///     ctrl: true,
 ///     alt: false,
+///     shift: false,
+/// });
+///
+/// // Input Shift+Right. Other fields can be omitted with `Default::default()`
+/// textarea.input(Input {
+///     key: Key::Right,
+///     shift: true,
+///     ..Default::default()
 /// });
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
 pub struct Input {
     /// Typed key.
@@ -82,6 +107,9 @@ pub struct Input {
     pub ctrl: bool,
     /// Alt modifier key. `true` means Alt key was pressed.
     pub alt: bool,
+    /// Shift modifier key. `true` means Shift key was pressed. Note that it may be `false` on typing an upper-case
+    /// character depending on the backend. termion reports the Shift modifier key only for Shift+Tab.
+    pub shift: bool,
 }
 
 impl Default for Input {
@@ -91,6 +119,7 @@ impl Default for Input {
             key: Key::Null,
             ctrl: false,
             alt: false,
+            shift: false,
         }
     }
 }
@@ -113,6 +142,7 @@ impl From<KeyEvent> for Input {
     fn from(key: KeyEvent) -> Self {
         let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
         let alt = key.modifiers.contains(KeyModifiers::ALT);
+        let mut shift = key.modifiers.contains(KeyModifiers::SHIFT);
         let key = match key.code {
             KeyCode::Char(c) => Key::Char(c),
             KeyCode::Backspace => Key::Backspace,
@@ -122,6 +152,11 @@ impl From<KeyEvent> for Input {
             KeyCode::Up => Key::Up,
             KeyCode::Down => Key::Down,
             KeyCode::Tab => Key::Tab,
+            // crossterm reports Shift+Tab as `BackTab`. Normalize it in the same way as termion
+            KeyCode::BackTab => {
+                shift = true;
+                Key::Tab
+            }
             KeyCode::Delete => Key::Delete,
             KeyCode::Home => Key::Home,
             KeyCode::End => Key::End,
@@ -131,7 +166,12 @@ impl From<KeyEvent> for Input {
             KeyCode::F(x) => Key::F(x),
             _ => Key::Null,
         };
-        Self { key, ctrl, alt }
+        Self {
+            key,
+            ctrl,
+            alt,
+            shift,
+        }
     }
 }
 
@@ -142,11 +182,26 @@ impl From<CrosstermMouseEvent> for Input {
         let key = match mouse.kind {
             CrosstermMouseEventKind::ScrollDown => Key::MouseScrollDown,
             CrosstermMouseEventKind::ScrollUp => Key::MouseScrollUp,
+            CrosstermMouseEventKind::Down(CrosstermMouseButton::Left) => {
+                Key::MouseClick(mouse.column, mouse.row)
+            }
+            CrosstermMouseEventKind::Drag(CrosstermMouseButton::Left) => {
+                Key::MouseDrag(mouse.column, mouse.row)
+            }
+            CrosstermMouseEventKind::Up(CrosstermMouseButton::Left) => {
+                Key::MouseRelease(mouse.column, mouse.row)
+            }
             _ => return Self::default(),
         };
         let ctrl = mouse.modifiers.contains(KeyModifiers::CONTROL);
         let alt = mouse.modifiers.contains(KeyModifiers::ALT);
-        Self { key, ctrl, alt }
+        let shift = mouse.modifiers.contains(KeyModifiers::SHIFT);
+        Self {
+            key,
+            ctrl,
+            alt,
+            shift,
+        }
     }
 }
 
@@ -170,8 +225,11 @@ impl From<TermionKey> for Input {
 
         let mut ctrl = false;
         let mut alt = false;
+        let mut shift = false;
         let key = match key {
             Char('\n' | '\r') => Key::Enter,
+            // termion reports Tab key as a tab character
+            Char('\t') => Key::Tab,
             Char(c) => Key::Char(c),
             Ctrl(c) => {
                 ctrl = true;
@@ -190,14 +248,22 @@ impl From<TermionKey> for Input {
             End => Key::End,
             PageUp => Key::PageUp,
             PageDown => Key::PageDown,
-            BackTab => Key::Tab,
+            BackTab => {
+                shift = true;
+                Key::Tab
+            }
             Delete => Key::Delete,
             Esc => Key::Esc,
             F(x) => Key::F(x),
             _ => Key::Null,
         };
 
-        Input { key, ctrl, alt }
+        Input {
+            key,
+            ctrl,
+            alt,
+            shift,
+        }
     }
 }
 
@@ -209,12 +275,110 @@ impl From<TermionMouseEvent> for Input {
         let key = match mouse {
             TermionMouseEvent::Press(MouseButton::WheelUp, ..) => Key::MouseScrollUp,
             TermionMouseEvent::Press(MouseButton::WheelDown, ..) => Key::MouseScrollDown,
+            // termion reports 1-based positions
+            TermionMouseEvent::Press(MouseButton::Left, x, y) => {
+                Key::MouseClick(x.saturating_sub(1), y.saturating_sub(1))
+            }
+            TermionMouseEvent::Hold(x, y) => {
+                Key::MouseDrag(x.saturating_sub(1), y.saturating_sub(1))
+            }
+            TermionMouseEvent::Release(x, y) => {
+                Key::MouseRelease(x.saturating_sub(1), y.saturating_sub(1))
+            }
             _ => return Self::default(),
         };
         Self {
             key,
             ctrl: false,
             alt: false,
+            shift: false,
+        }
+    }
+}
+
+#[cfg(feature = "ratatui-termwiz")]
+impl From<TermwizInputEvent> for Input {
+    /// Convert [`termwiz::input::InputEvent`] to [`Input`].
+    fn from(input: TermwizInputEvent) -> Self {
+        match input {
+            TermwizInputEvent::Key(key) => Self::from(key),
+            TermwizInputEvent::Mouse(mouse) => Self::from(mouse),
+            _ => Self::default(),
+        }
+    }
+}
+
+#[cfg(feature = "ratatui-termwiz")]
+impl From<TermwizKeyEvent> for Input {
+    /// Convert [`termwiz::input::KeyEvent`] to [`Input`].
+    fn from(key: TermwizKeyEvent) -> Self {
+        let TermwizKeyEvent { key, modifiers } = key;
+        let ctrl = modifiers.contains(TermwizModifiers::CTRL);
+        let alt = modifiers.contains(TermwizModifiers::ALT);
+        let shift = modifiers.contains(TermwizModifiers::SHIFT);
+        let key = match key {
+            TermwizKeyCode::Char(c) => Key::Char(c),
+            TermwizKeyCode::Backspace => Key::Backspace,
+            TermwizKeyCode::Enter => Key::Enter,
+            TermwizKeyCode::LeftArrow => Key::Left,
+            TermwizKeyCode::RightArrow => Key::Right,
+            TermwizKeyCode::UpArrow => Key::Up,
+            TermwizKeyCode::DownArrow => Key::Down,
+            TermwizKeyCode::Tab => Key::Tab,
+            TermwizKeyCode::Delete => Key::Delete,
+            TermwizKeyCode::Home => Key::Home,
+            TermwizKeyCode::End => Key::End,
+            TermwizKeyCode::PageUp => Key::PageUp,
+            TermwizKeyCode::PageDown => Key::PageDown,
+            TermwizKeyCode::Escape => Key::Esc,
+            TermwizKeyCode::Function(x) => Key::F(x),
+            _ => Key::Null,
+        };
+        Self {
+            key,
+            ctrl,
+            alt,
+            shift,
+        }
+    }
+}
+
+// termwiz reports only the buttons held on each mouse event. Whether the left button was held on the previous event
+// is remembered to tell pressing the button from dragging, and to report releasing it
+#[cfg(feature = "ratatui-termwiz")]
+static TERMWIZ_LEFT_PRESSED: AtomicBool = AtomicBool::new(false);
+
+#[cfg(feature = "ratatui-termwiz")]
+impl From<TermwizMouseEvent> for Input {
+    /// Convert [`termwiz::input::MouseEvent`] to [`Input`]. Since termwiz doesn't tell the kind of mouse event, the
+    /// first event with the left button held is converted to [`Key::MouseClick`], the following events with the
+    /// button held are converted to [`Key::MouseDrag`] and the event after the button is released is converted to
+    /// [`Key::MouseRelease`].
+    fn from(mouse: TermwizMouseEvent) -> Self {
+        let buttons = mouse.mouse_buttons;
+        let key = if buttons.contains(TermwizMouseButtons::VERT_WHEEL) {
+            if buttons.contains(TermwizMouseButtons::WHEEL_POSITIVE) {
+                Key::MouseScrollUp
+            } else {
+                Key::MouseScrollDown
+            }
+        } else {
+            let left = buttons.contains(TermwizMouseButtons::LEFT);
+            match (TERMWIZ_LEFT_PRESSED.swap(left, Ordering::Relaxed), left) {
+                (false, true) => Key::MouseClick(mouse.x, mouse.y),
+                (true, true) => Key::MouseDrag(mouse.x, mouse.y),
+                (true, false) => Key::MouseRelease(mouse.x, mouse.y),
+                (false, false) => return Self::default(),
+            }
+        };
+        let ctrl = mouse.modifiers.contains(TermwizModifiers::CTRL);
+        let alt = mouse.modifiers.contains(TermwizModifiers::ALT);
+        let shift = mouse.modifiers.contains(TermwizModifiers::SHIFT);
+        Self {
+            key,
+            ctrl,
+            alt,
+            shift,
         }
     }
 }
@@ -0,0 +1,267 @@
+use crate::cursor::CursorMove;
+use crate::input::{Input, Key};
+use crate::scroll::Scrolling;
+use std::collections::HashMap;
+
+/// Editor operation which a key input is mapped to by [`KeyMap`]. Each action calls the [`crate::TextArea`] method of
+/// the same name. Actions which modify the text are ignored while the textarea is read-only.
+///
+/// This type is marked as `#[non_exhaustive]` since more actions may be supported in the future.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug)]
+pub enum Action {
+    /// Insert a newline. See [`crate::TextArea::insert_newline`].
+    InsertNewline,
+    /// Insert a tab. See [`crate::TextArea::insert_tab`].
+    InsertTab,
+    /// Indent the current line or the selected lines. See [`crate::TextArea::indent_lines`].
+    IndentLines,
+    /// Dedent the current line or the selected lines. See [`crate::TextArea::dedent_lines`].
+    DedentLines,
+    /// Delete one character before the cursor. See [`crate::TextArea::delete_char`].
+    DeleteChar,
+    /// Delete one character next to the cursor. See [`crate::TextArea::delete_next_char`].
+    DeleteNextChar,
+    /// Delete one word before the cursor. See [`crate::TextArea::delete_word`].
+    DeleteWord,
+    /// Delete one word next to the cursor. See [`crate::TextArea::delete_next_word`].
+    DeleteNextWord,
+    /// Delete from the cursor until the end of line. See [`crate::TextArea::delete_line_by_end`].
+    DeleteLineByEnd,
+    /// Delete from the cursor until the head of line. See [`crate::TextArea::delete_line_by_head`].
+    DeleteLineByHead,
+    /// Delete the current line. See [`crate::TextArea::delete_line`].
+    DeleteLine,
+    /// Swap the characters around the cursor. See [`crate::TextArea::transpose_chars`].
+    TransposeChars,
+    /// Duplicate the current line or the selected lines. See [`crate::TextArea::duplicate_line`].
+    DuplicateLine,
+    /// Join the current line and the next line. See [`crate::TextArea::join_next_line`].
+    JoinNextLine,
+    /// Move the current line or the selected lines up. See [`crate::TextArea::move_line_up`].
+    MoveLineUp,
+    /// Move the current line or the selected lines down. See [`crate::TextArea::move_line_down`].
+    MoveLineDown,
+    /// Undo the last change. See [`crate::TextArea::undo`].
+    Undo,
+    /// Redo the last undone change. See [`crate::TextArea::redo`].
+    Redo,
+    /// Paste the yanked text. See [`crate::TextArea::paste`].
+    Paste,
+    /// Copy the selected text. See [`crate::TextArea::copy`].
+    Copy,
+    /// Cut the selected text. See [`crate::TextArea::cut`].
+    Cut,
+    /// Select the whole text. See [`crate::TextArea::select_all`].
+    SelectAll,
+    /// Move the cursor. See [`crate::TextArea::move_cursor`].
+    MoveCursor(CursorMove),
+    /// Move the cursor to the head of the first line. See [`crate::TextArea::move_cursor_top`].
+    MoveCursorTop,
+    /// Move the cursor to the end of the last line. See [`crate::TextArea::move_cursor_bottom`].
+    MoveCursorBottom,
+    /// Move the cursor to the head of line. `true` toggles the head and the first non-blank character. See
+    /// [`crate::TextArea::move_cursor_line_start`].
+    MoveCursorLineStart(bool),
+    /// Move the cursor to the end of line. See [`crate::TextArea::move_cursor_line_end`].
+    MoveCursorLineEnd,
+    /// Scroll the viewport. See [`crate::TextArea::scroll`].
+    Scroll(Scrolling),
+}
+
+/// Table of key bindings from inputs to [`Action`]s. Inputs are dispatched through the table by
+/// [`crate::TextArea::input_with`]. [`KeyMap::default`] reproduces the default key mappings of
+/// [`crate::TextArea::input`], and [`KeyMap::new`] creates an empty table.
+///
+/// When no action is bound to an input with Shift key, the action bound to the input without Shift key is used.
+/// Like [`crate::TextArea::input`], moving the cursor with Shift key extends the text selection.
+/// ```
+/// use tui_textarea::{Action, Input, Key, KeyMap};
+///
+/// let mut map = KeyMap::default();
+///
+/// let ctrl_d = Input { key: Key::Char('d'), ctrl: true, alt: false, shift: false };
+/// map.bind(ctrl_d.clone(), Action::DeleteLine);
+/// assert!(matches!(map.get(&ctrl_d), Some(Action::DeleteLine)));
+///
+/// map.unbind(&ctrl_d);
+/// assert!(map.get(&ctrl_d).is_none());
+/// ```
+#[derive(Clone, Debug)]
+pub struct KeyMap {
+    bindings: HashMap<Input, Action>,
+}
+
+impl KeyMap {
+    /// Create an empty key map. Typing characters without Ctrl and Alt keys and mouse inputs are still handled by
+    /// [`crate::TextArea::input_with`] when no action is bound to them.
+    pub fn new() -> Self {
+        Self {
+            bindings: HashMap::new(),
+        }
+    }
+
+    /// Bind the action to the input. Only `key`, `ctrl`, `alt` and `shift` fields of the input are compared. The
+    /// action previously bound to the input is returned.
+    pub fn bind(&mut self, input: Input, action: Action) -> Option<Action> {
+        self.bindings.insert(input, action)
+    }
+
+    /// Remove the binding of the input. The removed action is returned.
+    pub fn unbind(&mut self, input: &Input) -> Option<Action> {
+        self.bindings.remove(input)
+    }
+
+    /// Get the action bound to the input.
+    pub fn get(&self, input: &Input) -> Option<Action> {
+        if let Some(action) = self.bindings.get(input) {
+            return Some(*action);
+        }
+        if !input.shift {
+            return None;
+        }
+        let input = Input {
+            shift: false,
+            ..input.clone()
+        };
+        self.bindings.get(&input).copied()
+    }
+
+    fn bind_all(&mut self, keys: &[(Key, bool, bool)], action: Action) {
+        for &(key, ctrl, alt) in keys {
+            let input = Input {
+                key,
+                ctrl,
+                alt,
+                shift: false,
+            };
+            self.bind(input, action);
+        }
+    }
+}
+
+impl Default for KeyMap {
+    /// Key map which reproduces the default key mappings of [`crate::TextArea::input`].
+    fn default() -> Self {
+        // (key, ctrl, alt)
+        let key = |k| (k, false, false);
+        let ctrl = |c| (Key::Char(c), true, false);
+        let alt = |k| (k, false, true);
+        let ctrl_alt = |k| (k, true, true);
+        let any = |k| {
+            [
+                (k, false, false),
+                (k, true, false),
+                (k, false, true),
+                (k, true, true),
+            ]
+        };
+
+        let mut m = Self::new();
+        m.bind_all(&any(Key::Enter), Action::InsertNewline);
+        m.bind_all(
+            &[ctrl('m'), key(Key::Char('\n')), key(Key::Char('\r'))],
+            Action::InsertNewline,
+        );
+        m.bind_all(&[key(Key::Tab)], Action::InsertTab);
+        m.bind(
+            Input {
+                key: Key::Tab,
+                shift: true,
+                ..Default::default()
+            },
+            Action::DedentLines,
+        );
+        m.bind_all(&[ctrl('h'), key(Key::Backspace)], Action::DeleteChar);
+        m.bind_all(&[ctrl('d'), key(Key::Delete)], Action::DeleteNextChar);
+        m.bind_all(&[ctrl('t')], Action::TransposeChars);
+        m.bind_all(&[ctrl('k')], Action::DeleteLineByEnd);
+        m.bind_all(&[ctrl('j')], Action::DeleteLineByHead);
+        m.bind_all(
+            &[ctrl('w'), alt(Key::Char('h')), alt(Key::Backspace)],
+            Action::DeleteWord,
+        );
+        m.bind_all(
+            &[alt(Key::Delete), alt(Key::Char('d'))],
+            Action::DeleteNextWord,
+        );
+        m.bind_all(
+            &[ctrl('n'), key(Key::Down)],
+            Action::MoveCursor(CursorMove::Down),
+        );
+        m.bind_all(
+            &[ctrl('p'), key(Key::Up)],
+            Action::MoveCursor(CursorMove::Up),
+        );
+        m.bind_all(&[alt(Key::Up)], Action::MoveLineUp);
+        m.bind_all(&[alt(Key::Down)], Action::MoveLineDown);
+        m.bind_all(
+            &[ctrl('f'), key(Key::Right)],
+            Action::MoveCursor(CursorMove::Forward),
+        );
+        m.bind_all(
+            &[ctrl('b'), key(Key::Left)],
+            Action::MoveCursor(CursorMove::Back),
+        );
+        m.bind_all(&any(Key::Home), Action::MoveCursorLineStart(true));
+        m.bind_all(&[(Key::Home, true, false)], Action::MoveCursorTop);
+        m.bind_all(&any(Key::End), Action::MoveCursorLineEnd);
+        m.bind_all(&[(Key::End, true, false)], Action::MoveCursorBottom);
+        m.bind_all(
+            &[ctrl('a'), ctrl_alt(Key::Left), ctrl_alt(Key::Char('b'))],
+            Action::MoveCursorLineStart(false),
+        );
+        m.bind_all(
+            &[ctrl('e'), ctrl_alt(Key::Right), ctrl_alt(Key::Char('f'))],
+            Action::MoveCursorLineEnd,
+        );
+        m.bind_all(
+            &[
+                alt(Key::Char('<')),
+                ctrl_alt(Key::Up),
+                ctrl_alt(Key::Char('p')),
+            ],
+            Action::MoveCursor(CursorMove::Top),
+        );
+        m.bind_all(
+            &[
+                alt(Key::Char('>')),
+                ctrl_alt(Key::Down),
+                ctrl_alt(Key::Char('n')),
+            ],
+            Action::MoveCursor(CursorMove::Bottom),
+        );
+        m.bind_all(
+            &[alt(Key::Char('f')), (Key::Right, true, false)],
+            Action::MoveCursor(CursorMove::WordForward),
+        );
+        m.bind_all(
+            &[alt(Key::Char('b')), (Key::Left, true, false)],
+            Action::MoveCursor(CursorMove::WordBack),
+        );
+        m.bind_all(
+            &[
+                alt(Key::Char(']')),
+                alt(Key::Char('n')),
+                (Key::Down, true, false),
+            ],
+            Action::MoveCursor(CursorMove::ParagraphForward),
+        );
+        m.bind_all(
+            &[
+                alt(Key::Char('[')),
+                alt(Key::Char('p')),
+                (Key::Up, true, false),
+            ],
+            Action::MoveCursor(CursorMove::ParagraphBack),
+        );
+        m.bind_all(&[ctrl('u')], Action::Undo);
+        m.bind_all(&[ctrl('r')], Action::Redo);
+        m.bind_all(&[ctrl('y')], Action::Paste);
+        m.bind_all(&any(Key::PageDown), Action::Scroll(Scrolling::PageDown));
+        m.bind_all(&[ctrl('v')], Action::Scroll(Scrolling::PageDown));
+        m.bind_all(&any(Key::PageUp), Action::Scroll(Scrolling::PageUp));
+        m.bind_all(&[alt(Key::Char('v'))], Action::Scroll(Scrolling::PageUp));
+        m
+    }
+}
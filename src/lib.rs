@@ -6,31 +6,46 @@
 
 #[cfg(all(
     any(feature = "crossterm", feature = "termion"),
-    any(feature = "ratatui-crossterm", feature = "ratatui-termion"),
+    any(
+        feature = "ratatui-crossterm",
+        feature = "ratatui-termion",
+        feature = "ratatui-termwiz",
+    ),
 ))]
 compile_error!("tui-rs support and ratatui support are exclussive. only one of them can be enabled at the same time. see https://github.com/rhysd/tui-textarea");
 
+mod bracket;
 mod cursor;
+mod emacs;
 mod highlight;
 mod history;
 mod input;
+mod keymap;
+mod line_ending;
 mod scroll;
 #[cfg(feature = "search")]
 mod search;
+mod selection;
+mod text_edit;
 mod textarea;
 mod util;
+#[cfg(feature = "vim")]
+mod vim;
 mod widget;
 mod word;
+mod wrap;
 
 #[cfg(any(
     feature = "ratatui-crossterm",
     feature = "ratatui-termion",
+    feature = "ratatui-termwiz",
     feature = "ratatui-your-backend",
 ))]
 use ratatui as tui;
 #[cfg(not(any(
     feature = "ratatui-crossterm",
     feature = "ratatui-termion",
+    feature = "ratatui-termwiz",
     feature = "ratatui-your-backend",
 )))]
 #[allow(clippy::single_component_path_imports)]
@@ -43,6 +58,18 @@ use crossterm;
 use crossterm_026 as crossterm;
 
 pub use cursor::CursorMove;
+pub use emacs::emacs_input;
 pub use input::{Input, Key};
+pub use keymap::{Action, KeyMap};
+pub use line_ending::LineEnding;
 pub use scroll::Scrolling;
+#[cfg(feature = "search")]
+pub use search::SearchOptions;
+pub use selection::SelectionMode;
+pub use text_edit::{TextEdit, TextEditError};
 pub use textarea::TextArea;
+#[cfg(feature = "vim")]
+#[cfg_attr(docsrs, doc(cfg(feature = "vim")))]
+pub use vim::{Vim, VimMode};
+pub use widget::{ColorDepth, SyntaxRenderer};
+pub use wrap::WrapMode;
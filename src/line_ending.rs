@@ -0,0 +1,48 @@
+/// Line ending used when the text in the textarea is output by [`crate::TextArea::write_to`] and
+/// [`crate::TextArea::content`]. Lines in the textarea never contain line endings.
+///
+/// The default value is the line ending of the platform: [`LineEnding::CrLf`] on Windows and [`LineEnding::Lf`] on
+/// other platforms.
+/// ```
+/// use tui_textarea::LineEnding;
+///
+/// assert_eq!(LineEnding::Lf.as_str(), "\n");
+/// assert_eq!(LineEnding::CrLf.as_str(), "\r\n");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineEnding {
+    /// `\n`
+    Lf,
+    /// `\r\n`
+    CrLf,
+}
+
+impl LineEnding {
+    /// Get the line ending as a string.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Lf => "\n",
+            Self::CrLf => "\r\n",
+        }
+    }
+
+    // Detect the line ending from the first newline in the text
+    pub(crate) fn detect(s: &str) -> Option<Self> {
+        let i = s.find('\n')?;
+        if s[..i].ends_with('\r') {
+            Some(Self::CrLf)
+        } else {
+            Some(Self::Lf)
+        }
+    }
+}
+
+impl Default for LineEnding {
+    fn default() -> Self {
+        if cfg!(windows) {
+            Self::CrLf
+        } else {
+            Self::Lf
+        }
+    }
+}
@@ -1,10 +1,12 @@
 use crate::widget::Viewport;
+use std::cmp;
 
 /// Specify how to scroll the textarea.
 ///
 /// This type is marked as `#[non_exhaustive]` since more variations may be supported in the future. Note that the cursor will
 /// not move until it goes out the viewport. See also: [`TextArea::scroll`]
 #[non_exhaustive]
+#[derive(Clone, Copy, Debug)]
 pub enum Scrolling {
     /// Scroll the textarea by rows (vertically) and columns (horizontally). Passing positive scroll amounts to `rows` and `cols`
     /// scolls it to down and right. Negative integers means the opposite directions. `(i16, i16)` pair can be converted into
@@ -150,24 +152,17 @@ pub enum Scrolling {
 
 impl Scrolling {
     pub(crate) fn scroll(self, viewport: &mut Viewport) {
+        // Before the first render, the height of the viewport is unknown. Page scrolls move by one line in the case
+        let page = || {
+            let (_, _, _, height) = viewport.rect();
+            cmp::max(cmp::min(height, i16::MAX as u16) as i16, 1)
+        };
         let (rows, cols) = match self {
             Self::Delta { rows, cols } => (rows, cols),
-            Self::PageDown => {
-                let (_, _, _, height) = viewport.rect();
-                (height as i16, 0)
-            }
-            Self::PageUp => {
-                let (_, _, _, height) = viewport.rect();
-                (-(height as i16), 0)
-            }
-            Self::HalfPageDown => {
-                let (_, _, _, height) = viewport.rect();
-                ((height as i16) / 2, 0)
-            }
-            Self::HalfPageUp => {
-                let (_, _, _, height) = viewport.rect();
-                (-(height as i16) / 2, 0)
-            }
+            Self::PageDown => (page(), 0),
+            Self::PageUp => (-page(), 0),
+            Self::HalfPageDown => (cmp::max(page() / 2, 1), 0),
+            Self::HalfPageUp => (-cmp::max(page() / 2, 1), 0),
         };
         viewport.scroll(rows, cols);
     }
@@ -207,4 +202,146 @@ mod tests {
         textarea.scroll((1, 0));
         assert_eq!(textarea.cursor(), (3, 0));
     }
+
+    #[test]
+    fn page_before_first_render() {
+        use crate::TextArea;
+
+        // The viewport height is unknown before the first render. Scroll by one line instead of doing nothing
+        let mut textarea: TextArea = (0..20).into_iter().map(|i| i.to_string()).collect();
+        textarea.scroll_page_down();
+        assert_eq!(textarea.cursor(), (1, 0));
+        textarea.scroll_half_page_down();
+        assert_eq!(textarea.cursor(), (2, 0));
+        textarea.scroll_half_page_up();
+        assert_eq!(textarea.cursor(), (1, 0));
+        textarea.scroll_page_up();
+        assert_eq!(textarea.cursor(), (0, 0));
+    }
+
+    #[test]
+    fn clamp_past_end() {
+        use crate::tui::buffer::Buffer;
+        use crate::tui::layout::Rect;
+        use crate::tui::widgets::Widget;
+        use crate::TextArea;
+
+        let mut textarea: TextArea = (0..20).into_iter().map(|i| i.to_string()).collect();
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 24,
+            height: 8,
+        };
+        let mut b = Buffer::empty(r.clone());
+        textarea.widget().render(r, &mut b);
+
+        // The top row stops at the last screenful
+        textarea.scroll((100, 0));
+        assert_eq!(textarea.scrollbar_state(), (12, 8, 20));
+        assert_eq!(textarea.cursor(), (19, 0));
+        textarea.scroll(Scrolling::PageDown);
+        assert_eq!(textarea.scrollbar_state(), (12, 8, 20));
+        textarea.widget().render(r, &mut b);
+        assert_eq!(textarea.scrollbar_state(), (12, 8, 20));
+
+        // Scrolling down near the end moves the cursor to the last line
+        textarea.scroll((-12, 0));
+        assert_eq!(textarea.cursor(), (7, 0));
+        textarea.scroll(Scrolling::PageDown);
+        textarea.scroll(Scrolling::PageDown);
+        assert_eq!(textarea.scrollbar_state(), (12, 8, 20));
+        assert_eq!(textarea.cursor(), (16, 0));
+        textarea.scroll(Scrolling::PageDown);
+        assert_eq!(textarea.cursor(), (19, 0));
+
+        // Lines shorter than the viewport are not scrolled horizontally
+        textarea.scroll((0, 10));
+        assert_eq!(textarea.horizontal_scroll(), 0);
+
+        // The end of the longest line stops at the right edge
+        let mut textarea = TextArea::from(["a".repeat(30)]);
+        textarea.widget().render(r, &mut b);
+        textarea.scroll((0, 100));
+        assert_eq!(textarea.horizontal_scroll(), 7);
+        assert_eq!(textarea.cursor(), (0, 30));
+    }
+
+    #[test]
+    fn clamp_past_end_with_wrap() {
+        use crate::tui::buffer::Buffer;
+        use crate::tui::layout::Rect;
+        use crate::tui::widgets::Widget;
+        use crate::TextArea;
+
+        // The last line is wrapped into 3 rows
+        let mut textarea: TextArea = (0..5)
+            .map(|i| i.to_string())
+            .chain(["a".repeat(12)])
+            .collect();
+        textarea.set_wrap(true);
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 4,
+            height: 4,
+        };
+        let mut b = Buffer::empty(r.clone());
+        textarea.widget().render(r, &mut b);
+
+        // The top row stops where the wrapped rows of the following lines fill the viewport
+        textarea.scroll((100, 0));
+        assert_eq!(textarea.scrollbar_state().0, 4);
+        assert_eq!(textarea.cursor(), (5, 0));
+        let mut b = Buffer::empty(r.clone());
+        textarea.widget().render(r, &mut b);
+        assert_eq!(textarea.scrollbar_state().0, 4);
+        assert_eq!(textarea.viewport_visible_rows(), 4..6);
+        assert_eq!(b.get(0, 0).symbol, "4");
+        assert_eq!(b.get(0, 3).symbol, "a");
+    }
+
+    #[test]
+    fn scroll_cursor() {
+        use crate::tui::buffer::Buffer;
+        use crate::tui::layout::Rect;
+        use crate::tui::widgets::Widget;
+        use crate::{CursorMove, TextArea};
+
+        // Before the first render, the line is put at the top
+        let mut textarea: TextArea = (0..20).into_iter().map(|i| i.to_string()).collect();
+        textarea.move_cursor(CursorMove::Jump(10, 0));
+        textarea.scroll_cursor_center();
+        assert_eq!(textarea.scrollbar_state().0, 10);
+        textarea.scroll_cursor_bottom();
+        assert_eq!(textarea.scrollbar_state().0, 10);
+
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 24,
+            height: 8,
+        };
+        let mut b = Buffer::empty(r.clone());
+        textarea.widget().render(r, &mut b);
+
+        for (row, center, top, bottom) in [(10, 6, 10, 3), (2, 0, 2, 0), (19, 15, 19, 12)] {
+            textarea.move_cursor(CursorMove::Jump(row, 0));
+            textarea.scroll_cursor_center();
+            assert_eq!(textarea.scrollbar_state().0, center, "{}", row);
+            textarea.widget().render(r, &mut b);
+            assert_eq!(textarea.scrollbar_state().0, center, "{}", row);
+
+            textarea.scroll_cursor_top();
+            assert_eq!(textarea.scrollbar_state().0, top, "{}", row);
+            textarea.widget().render(r, &mut b);
+            assert_eq!(textarea.scrollbar_state().0, top, "{}", row);
+
+            textarea.scroll_cursor_bottom();
+            assert_eq!(textarea.scrollbar_state().0, bottom, "{}", row);
+            textarea.widget().render(r, &mut b);
+            assert_eq!(textarea.scrollbar_state().0, bottom, "{}", row);
+            assert_eq!(textarea.cursor(), (row as usize, 0));
+        }
+    }
 }
@@ -1,10 +1,70 @@
 use crate::tui::style::{Color, Style};
-use regex::Regex;
+use crate::word::is_word_char;
+use regex::{Regex, RegexBuilder};
+use std::iter;
+use std::sync::{Mutex, PoisonError};
+
+/// Options of text search set by [`crate::TextArea::set_search_options`]. Create the options with [`Default`] and set
+/// each option with its setter method.
+/// ```
+/// use tui_textarea::SearchOptions;
+///
+/// let options = SearchOptions::default().case_insensitive(true);
+/// assert!(options.case_insensitive);
+/// assert!(!options.whole_word);
+/// ```
+///
+/// This type is marked as `#[non_exhaustive]` since more options may be supported in the future.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SearchOptions {
+    /// Match the pattern ignoring case. This is applied to the pattern set by [`crate::TextArea::set_search_pattern`].
+    pub case_insensitive: bool,
+    /// Match the pattern only when it is not surrounded by word characters. Word characters are the same as word
+    /// movements like [`crate::CursorMove::WordForward`].
+    pub whole_word: bool,
+}
+
+impl SearchOptions {
+    /// Set whether to match the pattern ignoring case. See [`SearchOptions::case_insensitive`] field.
+    pub fn case_insensitive(mut self, enabled: bool) -> Self {
+        self.case_insensitive = enabled;
+        self
+    }
+
+    /// Set whether to match the pattern only as a whole word. See [`SearchOptions::whole_word`] field.
+    pub fn whole_word(mut self, enabled: bool) -> Self {
+        self.whole_word = enabled;
+        self
+    }
+}
+
+// Non-empty matches of the pattern in the whole text as (row, start, end). They are computed lazily on the first
+// query and cached until the pattern or the text is changed. `Mutex` is used since it is updated while the textarea is
+// immutably borrowed, as `SyntaxCache` does.
+#[derive(Default)]
+struct MatchCache(Mutex<Option<Vec<(usize, usize, usize)>>>);
+
+impl Clone for MatchCache {
+    fn clone(&self) -> Self {
+        let matches = self
+            .0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        MatchCache(Mutex::new(matches))
+    }
+}
 
 #[derive(Clone)]
 pub struct Search {
     pub pat: Option<Regex>,
     pub style: Style,
+    pub current_style: Style,
+    pub wrap: bool,
+    options: SearchOptions,
+    from_query: bool, // The pattern was compiled from a string with the options
+    cache: MatchCache,
 }
 
 impl Default for Search {
@@ -12,6 +72,11 @@ impl Default for Search {
         Self {
             pat: None,
             style: Style::default().bg(Color::Blue),
+            current_style: Style::default().bg(Color::Cyan),
+            wrap: true,
+            options: SearchOptions::default(),
+            from_query: false,
+            cache: MatchCache::default(),
         }
     }
 }
@@ -21,55 +86,210 @@ impl Search {
         &'a self,
         line: &'a str,
     ) -> Option<impl Iterator<Item = (usize, usize)> + 'a> {
-        let pat = self.pat.as_ref()?;
-        let matches = pat.find_iter(line).map(|m| (m.start(), m.end()));
-        Some(matches)
+        self.pat.as_ref()?;
+        Some(self.find_from(line, 0))
+    }
+
+    // Matches in the line which start at or after the byte offset as (start, end). Matches don't overlap as
+    // `Regex::find_iter`. When the whole word option is enabled, matches surrounded by word characters are skipped
+    fn find_from<'a>(
+        &'a self,
+        line: &'a str,
+        mut i: usize,
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let pat = self.pat.as_ref();
+        let whole_word = self.options.whole_word;
+        iter::from_fn(move || loop {
+            if i > line.len() {
+                return None;
+            }
+            let m = pat?.find_at(line, i)?;
+            let (start, end) = (m.start(), m.end());
+            let found = !whole_word || is_whole_word(line, start, end);
+            i = if found && start < end {
+                end
+            } else {
+                // Search the next match from the next character
+                start + line[start..].chars().next().map_or(1, char::len_utf8)
+            };
+            if found {
+                return Some((start, end));
+            }
+        })
+    }
+
+    fn build(&self, query: &str) -> Result<Regex, regex::Error> {
+        RegexBuilder::new(query)
+            .case_insensitive(self.options.case_insensitive)
+            .build()
     }
 
     pub fn set_pattern(&mut self, query: &str) -> Result<(), regex::Error> {
         match &self.pat {
-            Some(r) if r.as_str() == query => {}
+            Some(r) if r.as_str() == query && self.from_query => {}
             _ if query.is_empty() => self.pat = None,
-            _ => self.pat = Some(Regex::new(query)?),
+            _ => self.pat = Some(self.build(query)?),
         }
+        self.from_query = true;
+        self.invalidate();
         Ok(())
     }
 
+    pub fn set_regex(&mut self, re: Regex) {
+        self.pat = if re.as_str().is_empty() {
+            None
+        } else {
+            Some(re)
+        };
+        self.from_query = false;
+        self.invalidate();
+    }
+
+    pub fn options(&self) -> SearchOptions {
+        self.options
+    }
+
+    pub fn set_options(&mut self, options: SearchOptions) {
+        let case_changed = self.options.case_insensitive != options.case_insensitive;
+        self.options = options;
+        if case_changed && self.from_query {
+            if let Some(pat) = &self.pat {
+                // The pattern was already compiled successfully
+                if let Ok(re) = self.build(pat.as_str()) {
+                    self.pat = Some(re);
+                }
+            }
+        }
+        self.invalidate();
+    }
+
+    pub fn invalidate(&mut self) {
+        *self
+            .cache
+            .0
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner) = None;
+    }
+
+    // Call the function with all non-empty matches in the text
+    fn with_all_matches<T>(
+        &self,
+        lines: &[String],
+        f: impl FnOnce(&[(usize, usize, usize)]) -> T,
+    ) -> T {
+        let mut cache = self.cache.0.lock().unwrap_or_else(PoisonError::into_inner);
+        let matches = cache.get_or_insert_with(|| {
+            let mut matches = vec![];
+            for (row, line) in lines.iter().enumerate() {
+                let found = self.find_from(line, 0).filter(|&(s, e)| s < e);
+                matches.extend(found.map(|(s, e)| (row, s, e)));
+            }
+            matches
+        });
+        f(matches)
+    }
+
+    pub fn match_count(&self, lines: &[String]) -> usize {
+        self.with_all_matches(lines, |matches| matches.len())
+    }
+
+    // Index of the match containing the byte offset in the row
+    pub fn match_index(&self, lines: &[String], row: usize, i: usize) -> Option<usize> {
+        self.with_all_matches(lines, |matches| {
+            let idx = matches.partition_point(|&(r, _, e)| (r, e) <= (row, i));
+            match matches.get(idx) {
+                Some(&(r, s, _)) if r == row && s <= i => Some(idx),
+                _ => None,
+            }
+        })
+    }
+
+    // The first non-empty match at or after the byte offset in the row as (row, start, end). Empty matches are skipped
+    // since replacing them would insert the replacement endlessly. Lines before the position are searched when the
+    // search wraps around
+    pub fn next_match(
+        &self,
+        lines: &[String],
+        row: usize,
+        i: usize,
+    ) -> Option<(usize, usize, usize)> {
+        self.pat.as_ref()?;
+        let find_non_empty = |line: &str, i: usize| self.find_from(line, i).find(|&(s, e)| s < e);
+        if let Some((s, e)) = find_non_empty(&lines[row], i) {
+            return Some((row, s, e));
+        }
+        for (r, line) in lines.iter().enumerate().skip(row + 1) {
+            if let Some((s, e)) = find_non_empty(line, 0) {
+                return Some((r, s, e));
+            }
+        }
+        if !self.wrap {
+            return None;
+        }
+        for (r, line) in lines.iter().enumerate().take(row + 1) {
+            if let Some((s, e)) = find_non_empty(line, 0) {
+                if r < row || s < i {
+                    return Some((r, s, e));
+                }
+            }
+        }
+        None
+    }
+
+    // Replace all non-empty matches in the line. Returns the replaced line and the number of the replaced matches
+    pub fn replace_line(&self, line: &str, replacement: &str) -> Option<(String, usize)> {
+        self.pat.as_ref()?;
+        let mut replaced = String::new();
+        let mut last = 0;
+        let mut count = 0;
+        for (s, e) in self.find_from(line, 0).filter(|&(s, e)| s < e) {
+            replaced.push_str(&line[last..s]);
+            replaced.push_str(replacement);
+            last = e;
+            count += 1;
+        }
+        if count == 0 {
+            return None;
+        }
+        replaced.push_str(&line[last..]);
+        Some((replaced, count))
+    }
+
     pub fn forward(
         &mut self,
         lines: &[String],
         cursor: (usize, usize),
         match_cursor: bool,
     ) -> Option<(usize, usize)> {
-        let pat = if let Some(pat) = &self.pat {
-            pat
-        } else {
-            return None;
-        };
+        self.pat.as_ref()?;
         let (row, col) = cursor;
         let current_line = &lines[row];
 
         // Search current line after cursor
         let start_col = if match_cursor { col } else { col + 1 };
         if let Some((i, _)) = current_line.char_indices().nth(start_col) {
-            if let Some(m) = pat.find_at(current_line, i) {
-                let col = start_col + current_line[i..m.start()].chars().count();
+            if let Some((s, _)) = self.find_from(current_line, i).next() {
+                let col = start_col + current_line[i..s].chars().count();
                 return Some((row, col));
             }
         }
 
         // Search lines after cursor
         for (i, line) in lines[row + 1..].iter().enumerate() {
-            if let Some(m) = pat.find(line) {
-                let col = line[..m.start()].chars().count();
+            if let Some((s, _)) = self.find_from(line, 0).next() {
+                let col = line[..s].chars().count();
                 return Some((row + 1 + i, col));
             }
         }
 
+        if !self.wrap {
+            return None;
+        }
+
         // Search lines before cursor (wrap)
         for (i, line) in lines[..row].iter().enumerate() {
-            if let Some(m) = pat.find(line) {
-                let col = line[..m.start()].chars().count();
+            if let Some((s, _)) = self.find_from(line, 0).next() {
+                let col = line[..s].chars().count();
                 return Some((i, col));
             }
         }
@@ -80,8 +300,7 @@ impl Search {
             .nth(col)
             .map(|(i, _)| i)
             .unwrap_or(current_line.len());
-        if let Some(m) = pat.find(current_line) {
-            let i = m.start();
+        if let Some((i, _)) = self.find_from(current_line, 0).next() {
             if i <= col_idx {
                 let col = current_line[..i].chars().count();
                 return Some((row, col));
@@ -97,11 +316,7 @@ impl Search {
         cursor: (usize, usize),
         match_cursor: bool,
     ) -> Option<(usize, usize)> {
-        let pat = if let Some(pat) = &self.pat {
-            pat
-        } else {
-            return None;
-        };
+        self.pat.as_ref()?;
         let (row, col) = cursor;
         let current_line = &lines[row];
 
@@ -109,12 +324,12 @@ impl Search {
         if col > 0 || match_cursor {
             let start_col = if match_cursor { col } else { col - 1 };
             if let Some((i, _)) = current_line.char_indices().nth(start_col) {
-                if let Some(m) = pat
-                    .find_iter(current_line)
-                    .take_while(|m| m.start() <= i)
+                if let Some((s, _)) = self
+                    .find_from(current_line, 0)
+                    .take_while(|&(s, _)| s <= i)
                     .last()
                 {
-                    let col = current_line[..m.start()].chars().count();
+                    let col = current_line[..s].chars().count();
                     return Some((row, col));
                 }
             }
@@ -122,28 +337,32 @@ impl Search {
 
         // Search lines before cursor
         for (i, line) in lines[..row].iter().enumerate().rev() {
-            if let Some(m) = pat.find_iter(line).last() {
-                let col = line[..m.start()].chars().count();
+            if let Some((s, _)) = self.find_from(line, 0).last() {
+                let col = line[..s].chars().count();
                 return Some((i, col));
             }
         }
 
+        if !self.wrap {
+            return None;
+        }
+
         // Search lines after cursor (wrap)
         for (i, line) in lines[row + 1..].iter().enumerate().rev() {
-            if let Some(m) = pat.find_iter(line).last() {
-                let col = line[..m.start()].chars().count();
+            if let Some((s, _)) = self.find_from(line, 0).last() {
+                let col = line[..s].chars().count();
                 return Some((row + 1 + i, col));
             }
         }
 
         // Search current line after cursor
         if let Some((i, _)) = current_line.char_indices().nth(col) {
-            if let Some(m) = pat
-                .find_iter(current_line)
-                .skip_while(|m| m.start() < i)
+            if let Some((s, _)) = self
+                .find_from(current_line, 0)
+                .skip_while(|&(s, _)| s < i)
                 .last()
             {
-                let col = col + current_line[i..m.start()].chars().count();
+                let col = col + current_line[i..s].chars().count();
                 return Some((row, col));
             }
         }
@@ -151,3 +370,10 @@ impl Search {
         None
     }
 }
+
+// Check the match is not surrounded by word characters
+fn is_whole_word(line: &str, start: usize, end: usize) -> bool {
+    let before = line[..start].chars().next_back();
+    let after = line[end..].chars().next();
+    !before.map_or(false, is_word_char) && !after.map_or(false, is_word_char)
+}
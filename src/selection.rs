@@ -0,0 +1,19 @@
+/// How text between the selection start and the cursor is selected. This is set by
+/// [`crate::TextArea::set_selection_mode`].
+///
+/// This type is marked as `#[non_exhaustive]` since more modes may be supported in the future.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SelectionMode {
+    /// Select the characters from the selection start to the cursor as a continuous range of text. This is the default.
+    Normal,
+    /// Select the rectangle whose corners are the selection start and the cursor. Each line in the rows of the
+    /// rectangle is selected between the columns of the rectangle.
+    Block,
+}
+
+impl Default for SelectionMode {
+    fn default() -> Self {
+        Self::Normal
+    }
+}
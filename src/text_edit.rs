@@ -0,0 +1,52 @@
+use std::error::Error;
+use std::fmt;
+
+/// Replacement of the text in the range with the new text, applied by [`crate::TextArea::apply_edits`]. The range is a
+/// pair of the start position and the end position where each position is `(row, col)`. The end position is
+/// exclusive. Like cursor positions, columns are character-wise. An empty range inserts the new text and an empty new
+/// text deletes the range.
+/// ```
+/// use tui_textarea::TextEdit;
+///
+/// // Replace "foo" at the head of the second line with "bar"
+/// let edit = TextEdit::new((1, 0), (1, 3), "bar");
+/// assert_eq!(edit.range, ((1, 0), (1, 3)));
+/// assert_eq!(edit.new_text, "bar");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct TextEdit {
+    /// Start and end positions of the text to be replaced.
+    pub range: ((usize, usize), (usize, usize)),
+    /// Text to replace the range with. It may contain newlines.
+    pub new_text: String,
+}
+
+impl TextEdit {
+    /// Create a new edit which replaces the text from `start` to `end` with `new_text`.
+    pub fn new(start: (usize, usize), end: (usize, usize), new_text: impl Into<String>) -> Self {
+        Self {
+            range: (start, end),
+            new_text: new_text.into(),
+        }
+    }
+}
+
+/// Error returned from [`crate::TextArea::apply_edits`] when the edits cannot be applied. No edit is applied on error.
+///
+/// This type is marked as `#[non_exhaustive]` since more errors may be reported in the future.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TextEditError {
+    /// Ranges of two edits overlap. The elements are the indices of the edits in the given slice.
+    Overlap(usize, usize),
+}
+
+impl fmt::Display for TextEditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Overlap(i, j) => write!(f, "ranges of edits at index {} and {} overlap", i, j),
+        }
+    }
+}
+
+impl Error for TextEditError {}
@@ -1,24 +1,73 @@
-use ansi_to_tui::{IntoLine, IntoText};
-// use ratatui::text::Text;
+use ansi_to_tui::IntoText;
 use syntect::easy::HighlightLines;
-use syntect::highlighting::{Style as SyntectStyle, ThemeSet};
-use syntect::parsing::SyntaxSet;
+use syntect::highlighting::Style as SyntectStyle;
 use syntect::util::as_24_bit_terminal_escaped;
 
+use crate::bracket::{
+    auto_pair_close, find_matching_bracket, find_matching_bracket_within, is_auto_pair_close,
+};
 use crate::cursor::CursorMove;
 use crate::highlight::LineHighlighter;
 use crate::history::{Edit, EditKind, History};
 use crate::input::{Input, Key};
+use crate::keymap::{Action, KeyMap};
+use crate::line_ending::LineEnding;
 use crate::scroll::Scrolling;
 #[cfg(feature = "search")]
-use crate::search::Search;
-use crate::tui::layout::Alignment;
-use crate::tui::style::{Modifier, Style};
-use crate::tui::text::{Line, Text};
+use crate::search::{Search, SearchOptions};
+use crate::selection::SelectionMode;
+use crate::text_edit::{TextEdit, TextEditError};
+use crate::tui::layout::{Alignment, Rect};
+use crate::tui::style::{Color, Modifier, Style};
+use crate::tui::text::{Line, Span, Text};
 use crate::tui::widgets::{Block, Widget};
-use crate::util::spaces;
-use crate::widget::{Renderer, SyntaxRenderer, Viewport};
-use crate::word::{find_word_end_forward, find_word_start_backward};
+use crate::util::{
+    char_col, display_col, num_digits, prev_grapheme_col, shift_rows, spaces, tab_width,
+};
+use crate::widget::{
+    Renderer, SyntaxCache, SyntaxRenderer, Viewport, DEFAULT_SYNTAX_SET, DEFAULT_THEME_SET,
+};
+use crate::word::{find_word_end_forward, find_word_range, find_word_start_backward, is_word_char};
+use crate::wrap::{WrapMode, Wrapper};
+use std::cmp;
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use std::ops::Range;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthChar;
+
+// Lazily computed maximum display width of lines. `usize::MAX` means the width is not computed yet. It is atomic to
+// compute the width through a shared reference
+struct MaxWidthCache(AtomicUsize);
+
+impl Default for MaxWidthCache {
+    fn default() -> Self {
+        Self(AtomicUsize::new(usize::MAX))
+    }
+}
+
+impl Clone for MaxWidthCache {
+    fn clone(&self) -> Self {
+        Self(AtomicUsize::new(self.0.load(Ordering::Relaxed)))
+    }
+}
+
+impl MaxWidthCache {
+    fn get_or_compute(&self, compute: impl FnOnce() -> usize) -> usize {
+        let width = self.0.load(Ordering::Relaxed);
+        if width != usize::MAX {
+            return width;
+        }
+        let width = compute();
+        self.0.store(width, Ordering::Relaxed);
+        width
+    }
+
+    fn invalidate(&mut self) {
+        *self.0.get_mut() = usize::MAX;
+    }
+}
 
 /// A type to manage state of textarea.
 ///
@@ -31,7 +80,7 @@ use crate::word::{find_word_end_forward, find_word_start_backward};
 /// let mut textarea = TextArea::default();
 ///
 /// // Input 'a'
-/// let input = Input { key: Key::Char('a'), ctrl: false, alt: false };
+/// let input = Input { key: Key::Char('a'), ctrl: false, alt: false, shift: false };
 /// textarea.input(input);
 ///
 /// // Get widget to render.
@@ -47,17 +96,54 @@ pub struct TextArea<'a> {
     block: Option<Block<'a>>,
     style: Style,
     cursor: (usize, usize), // 0-base
+    selection_start: Option<(usize, usize)>,
+    shift_selection: bool, // The selection was started by moving the cursor with Shift key
+    selection_mode: SelectionMode,
     tab_len: u8,
     hard_tab_indent: bool,
+    auto_indent: bool,
+    auto_pairs: bool,
     history: History,
     cursor_line_style: Style,
     line_number_style: Option<Style>,
+    cursor_line_number_style: Option<Style>,
+    match_bracket_style: Option<Style>,
+    selection_style: Style,
     pub(crate) viewport: Viewport,
+    pub(crate) syntax_cache: SyntaxCache,
     cursor_style: Style,
     yank: String,
     #[cfg(feature = "search")]
     search: Search,
     alignment: Alignment,
+    wrap: bool,
+    wrap_mode: WrapMode,
+    show_whitespace: bool,
+    placeholder: String,
+    placeholder_style: Style,
+    mask: Option<char>,
+    line_number_relative: bool,
+    read_only: bool,
+    max_chars: Option<usize>,
+    max_lines: Option<usize>,
+    line_ending: LineEnding,
+    trailing_newline: bool,
+    scroll_lines_per_wheel: u16,
+    ruler_column: Option<u16>,
+    ruler_style: Style,
+    line_signs: HashMap<usize, (char, Style)>,
+    highlights: HashMap<usize, Vec<(Range<usize>, Style)>>,
+    max_width: MaxWidthCache,
+    version: u64,
+    saved_version: u64,
+    // Column which the cursor tries to keep on moving vertically, and the cursor position after the move. The column is
+    // forgotten when the cursor moves from the position by other ways
+    desired_col: Option<(usize, (usize, usize))>,
+    // Cursor position when the viewport was scrolled by mouse wheel. The viewport doesn't follow the cursor until the
+    // cursor moves from the position
+    pub(crate) wheel_scrolled_cursor: Option<(usize, usize)>,
+    // Whether the mouse button was pressed inside the textarea and has not been released yet
+    mouse_pressed: bool,
 }
 
 /// Convert any iterator whose elements can be converted into [`String`] into [`TextArea`]. Each [`String`] element is
@@ -139,14 +225,16 @@ impl<'a> TextArea<'a> {
             lines.push(String::new());
         }
 
-        let ss = SyntaxSet::load_defaults_newlines();
-        let ts = ThemeSet::load_defaults();
-        let syntax = ss.find_syntax_by_extension("rs").unwrap();
+        let ss = &*DEFAULT_SYNTAX_SET;
+        let ts = &*DEFAULT_THEME_SET;
+        let syntax = ss
+            .find_syntax_by_extension("rs")
+            .unwrap_or_else(|| ss.find_syntax_plain_text());
         let mut h = HighlightLines::new(syntax, &ts.themes["base16-eighties.dark"]);
 
         let mut escaped = Vec::new();
         for line in &lines {
-            let ranges: Vec<(SyntectStyle, &str)> = h.highlight_line(line, &ss).unwrap();
+            let ranges: Vec<(SyntectStyle, &str)> = h.highlight_line(line, ss).unwrap();
             escaped.push(as_24_bit_terminal_escaped(&ranges[..], true));
         }
 
@@ -156,30 +244,94 @@ impl<'a> TextArea<'a> {
             block: None,
             style: Style::default(),
             cursor: (0, 0),
+            selection_start: None,
+            shift_selection: false,
+            selection_mode: SelectionMode::default(),
             tab_len: 4,
             hard_tab_indent: false,
+            auto_indent: false,
+            auto_pairs: false,
             history: History::new(50),
             cursor_line_style: Style::default().add_modifier(Modifier::UNDERLINED),
             line_number_style: None,
+            cursor_line_number_style: None,
+            match_bracket_style: None,
+            selection_style: Style::default().bg(Color::LightBlue),
             viewport: Viewport::default(),
+            syntax_cache: SyntaxCache::default(),
             cursor_style: Style::default().add_modifier(Modifier::REVERSED | Modifier::SLOW_BLINK),
             yank: String::new(),
             #[cfg(feature = "search")]
             search: Search::default(),
             alignment: Alignment::Left,
+            wrap: false,
+            wrap_mode: WrapMode::default(),
+            show_whitespace: false,
+            placeholder: String::new(),
+            placeholder_style: Style::default().fg(Color::DarkGray),
+            mask: None,
+            line_number_relative: false,
+            read_only: false,
+            max_chars: None,
+            max_lines: None,
+            line_ending: LineEnding::default(),
+            trailing_newline: true,
+            scroll_lines_per_wheel: 3,
+            ruler_column: None,
+            ruler_style: Style::default().bg(Color::DarkGray),
+            line_signs: HashMap::new(),
+            highlights: HashMap::new(),
+            max_width: MaxWidthCache::default(),
+            version: 0,
+            saved_version: 0,
+            desired_col: None,
+            wheel_scrolled_cursor: None,
+            mouse_pressed: false,
         }
     }
 
+    /// Get the text highlighted as Rust code when this textarea was created. The text is not updated on edits.
+    #[deprecated(
+        note = "the text is not updated on edits. Use `TextArea::lines` or `SyntaxRenderer` instead"
+    )]
     #[inline]
     pub fn text(&self) -> &Text<'a> {
         &self.text
     }
 
+    /// Create [`TextArea`] instance with text read from the reader. Both `\n` and `\r\n` line endings are handled and
+    /// the line ending of the first line is remembered as [`TextArea::line_ending`]. When the text has no newline, the
+    /// default line ending is used. A newline at the end of the text does not make an empty line. Whether the text ends
+    /// with a newline is remembered as [`TextArea::trailing_newline`]. Empty input makes one empty line. An error is
+    /// returned when reading failed or the text is not valid UTF-8.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::from_reader("hello\r\nworld\r\n".as_bytes()).unwrap();
+    /// assert_eq!(textarea.lines(), ["hello", "world"]);
+    ///
+    /// let textarea = TextArea::from_reader("".as_bytes()).unwrap();
+    /// assert_eq!(textarea.lines(), [""]);
+    /// ```
+    pub fn from_reader<R: Read>(mut r: R) -> io::Result<Self> {
+        let mut s = String::new();
+        r.read_to_string(&mut s)?;
+        let mut textarea = Self::new(s.lines().map(String::from).collect());
+        if let Some(ending) = LineEnding::detect(&s) {
+            textarea.line_ending = ending;
+        }
+        textarea.trailing_newline = s.ends_with('\n');
+        Ok(textarea)
+    }
+
     /// Handle a key input with default key mappings. For default key mappings, see the table in
     /// [the module document](./index.html).
     /// `crossterm` and `termion` features enable conversion from their own key event types into [`Input`] so this
     /// method can take the event values directly.
-    /// This method returns if the input modified text contents or not in the textarea.
+    /// This method returns if the input modified text contents or not in the textarea. Inputs which modify the text are
+    /// ignored while the textarea is read-only. See [`TextArea::set_read_only`].
+    /// Cursor moves with Shift key (`shift` field of [`Input`]) extend the text selection. Cursor moves without Shift
+    /// key cancel the selection started by Shift key. Shift key is ignored in other key mappings.
     /// ```ignore
     /// use tui_textarea::{TextArea, Key, Input};
     ///
@@ -200,106 +352,130 @@ impl<'a> TextArea<'a> {
     /// }
     ///
     /// // Handle backend-agnostic key input
-    /// let input = Input { key: Key::Char('a'), ctrl: false, alt: false };
+    /// let input = Input { key: Key::Char('a'), ctrl: false, alt: false, shift: false };
     /// let modified = textarea.input(input);
     /// assert!(modified);
     /// ```
     pub fn input(&mut self, input: impl Into<Input>) -> bool {
         let input = input.into();
+        self.select_by_shift(&input);
         let modified = match input {
             Input {
                 key: Key::Char('m'),
                 ctrl: true,
                 alt: false,
+                ..
             }
             | Input {
                 key: Key::Char('\n' | '\r'),
                 ctrl: false,
                 alt: false,
+                ..
             }
             | Input {
                 key: Key::Enter, ..
-            } => {
-                self.insert_newline();
-                true
-            }
+            } if !self.read_only && self.within_line_limit(1) => self.input_newline(),
             Input {
                 key: Key::Char(c),
                 ctrl: false,
                 alt: false,
-            } => {
-                self.insert_char(c);
-                true
-            }
+                ..
+            } if !self.read_only && self.within_limits(1, 0) => self.input_char(c),
             Input {
                 key: Key::Tab,
                 ctrl: false,
                 alt: false,
-            } => self.insert_tab(),
+                shift: true,
+            } if !self.read_only => self.dedent_lines(),
+            Input {
+                key: Key::Tab,
+                ctrl: false,
+                alt: false,
+                ..
+            } if !self.read_only && self.within_limits(self.tab().len(), 0) => self.insert_tab(),
             Input {
                 key: Key::Char('h'),
                 ctrl: true,
                 alt: false,
+                ..
             }
             | Input {
                 key: Key::Backspace,
                 ctrl: false,
                 alt: false,
-            } => self.delete_char(),
+                ..
+            } if !self.read_only => self.delete_char_or_pair(),
             Input {
                 key: Key::Char('d'),
                 ctrl: true,
                 alt: false,
+                ..
             }
             | Input {
                 key: Key::Delete,
                 ctrl: false,
                 alt: false,
-            } => self.delete_next_char(),
+                ..
+            } if !self.read_only => self.delete_next_char(),
+            Input {
+                key: Key::Char('t'),
+                ctrl: true,
+                alt: false,
+                ..
+            } if !self.read_only => self.transpose_chars(),
             Input {
                 key: Key::Char('k'),
                 ctrl: true,
                 alt: false,
-            } => self.delete_line_by_end(),
+                ..
+            } if !self.read_only => self.delete_line_by_end(),
             Input {
                 key: Key::Char('j'),
                 ctrl: true,
                 alt: false,
-            } => self.delete_line_by_head(),
+                ..
+            } if !self.read_only => self.delete_line_by_head(),
             Input {
                 key: Key::Char('w'),
                 ctrl: true,
                 alt: false,
+                ..
             }
             | Input {
                 key: Key::Char('h'),
                 ctrl: false,
                 alt: true,
+                ..
             }
             | Input {
                 key: Key::Backspace,
                 ctrl: false,
                 alt: true,
-            } => self.delete_word(),
+                ..
+            } if !self.read_only => self.delete_word(),
             Input {
                 key: Key::Delete,
                 ctrl: false,
                 alt: true,
+                ..
             }
             | Input {
                 key: Key::Char('d'),
                 ctrl: false,
                 alt: true,
-            } => self.delete_next_word(),
+                ..
+            } if !self.read_only => self.delete_next_word(),
             Input {
                 key: Key::Char('n'),
                 ctrl: true,
                 alt: false,
+                ..
             }
             | Input {
                 key: Key::Down,
                 ctrl: false,
                 alt: false,
+                ..
             } => {
                 self.move_cursor(CursorMove::Down);
                 false
@@ -308,24 +484,40 @@ impl<'a> TextArea<'a> {
                 key: Key::Char('p'),
                 ctrl: true,
                 alt: false,
+                ..
             }
             | Input {
                 key: Key::Up,
                 ctrl: false,
                 alt: false,
+                ..
             } => {
                 self.move_cursor(CursorMove::Up);
                 false
             }
+            Input {
+                key: Key::Up,
+                ctrl: false,
+                alt: true,
+                ..
+            } if !self.read_only => self.move_line_up(),
+            Input {
+                key: Key::Down,
+                ctrl: false,
+                alt: true,
+                ..
+            } if !self.read_only => self.move_line_down(),
             Input {
                 key: Key::Char('f'),
                 ctrl: true,
                 alt: false,
+                ..
             }
             | Input {
                 key: Key::Right,
                 ctrl: false,
                 alt: false,
+                ..
             } => {
                 self.move_cursor(CursorMove::Forward);
                 false
@@ -334,52 +526,81 @@ impl<'a> TextArea<'a> {
                 key: Key::Char('b'),
                 ctrl: true,
                 alt: false,
+                ..
             }
             | Input {
                 key: Key::Left,
                 ctrl: false,
                 alt: false,
+                ..
             } => {
                 self.move_cursor(CursorMove::Back);
                 false
             }
+            Input {
+                key: Key::Home,
+                ctrl: true,
+                alt: false,
+                ..
+            } => {
+                self.move_cursor_top();
+                false
+            }
+            Input {
+                key: Key::End,
+                ctrl: true,
+                alt: false,
+                ..
+            } => {
+                self.move_cursor_bottom();
+                false
+            }
             Input {
                 key: Key::Char('a'),
                 ctrl: true,
                 alt: false,
+                ..
             }
-            | Input { key: Key::Home, .. }
             | Input {
                 key: Key::Left | Key::Char('b'),
                 ctrl: true,
                 alt: true,
+                ..
             } => {
-                self.move_cursor(CursorMove::Head);
+                self.move_cursor_line_start(false);
+                false
+            }
+            Input { key: Key::Home, .. } => {
+                self.move_cursor_line_start(true);
                 false
             }
             Input {
                 key: Key::Char('e'),
                 ctrl: true,
                 alt: false,
+                ..
             }
             | Input { key: Key::End, .. }
             | Input {
                 key: Key::Right | Key::Char('f'),
                 ctrl: true,
                 alt: true,
+                ..
             } => {
-                self.move_cursor(CursorMove::End);
+                self.move_cursor_line_end();
                 false
             }
             Input {
                 key: Key::Char('<'),
                 ctrl: false,
                 alt: true,
+                ..
             }
             | Input {
                 key: Key::Up | Key::Char('p'),
                 ctrl: true,
                 alt: true,
+                ..
             } => {
                 self.move_cursor(CursorMove::Top);
                 false
@@ -388,11 +609,13 @@ impl<'a> TextArea<'a> {
                 key: Key::Char('>'),
                 ctrl: false,
                 alt: true,
+                ..
             }
             | Input {
                 key: Key::Down | Key::Char('n'),
                 ctrl: true,
                 alt: true,
+                ..
             } => {
                 self.move_cursor(CursorMove::Bottom);
                 false
@@ -401,11 +624,13 @@ impl<'a> TextArea<'a> {
                 key: Key::Char('f'),
                 ctrl: false,
                 alt: true,
+                ..
             }
             | Input {
                 key: Key::Right,
                 ctrl: true,
                 alt: false,
+                ..
             } => {
                 self.move_cursor(CursorMove::WordForward);
                 false
@@ -414,11 +639,13 @@ impl<'a> TextArea<'a> {
                 key: Key::Char('b'),
                 ctrl: false,
                 alt: true,
+                ..
             }
             | Input {
                 key: Key::Left,
                 ctrl: true,
                 alt: false,
+                ..
             } => {
                 self.move_cursor(CursorMove::WordBack);
                 false
@@ -427,16 +654,19 @@ impl<'a> TextArea<'a> {
                 key: Key::Char(']'),
                 ctrl: false,
                 alt: true,
+                ..
             }
             | Input {
                 key: Key::Char('n'),
                 ctrl: false,
                 alt: true,
+                ..
             }
             | Input {
                 key: Key::Down,
                 ctrl: true,
                 alt: false,
+                ..
             } => {
                 self.move_cursor(CursorMove::ParagraphForward);
                 false
@@ -445,16 +675,19 @@ impl<'a> TextArea<'a> {
                 key: Key::Char('['),
                 ctrl: false,
                 alt: true,
+                ..
             }
             | Input {
                 key: Key::Char('p'),
                 ctrl: false,
                 alt: true,
+                ..
             }
             | Input {
                 key: Key::Up,
                 ctrl: true,
                 alt: false,
+                ..
             } => {
                 self.move_cursor(CursorMove::ParagraphBack);
                 false
@@ -463,21 +696,25 @@ impl<'a> TextArea<'a> {
                 key: Key::Char('u'),
                 ctrl: true,
                 alt: false,
-            } => self.undo(),
+                ..
+            } if !self.read_only => self.undo(),
             Input {
                 key: Key::Char('r'),
                 ctrl: true,
                 alt: false,
-            } => self.redo(),
+                ..
+            } if !self.read_only => self.redo(),
             Input {
                 key: Key::Char('y'),
                 ctrl: true,
                 alt: false,
-            } => self.paste(),
+                ..
+            } if !self.read_only => self.paste(),
             Input {
                 key: Key::Char('v'),
                 ctrl: true,
                 alt: false,
+                ..
             }
             | Input {
                 key: Key::PageDown, ..
@@ -489,6 +726,7 @@ impl<'a> TextArea<'a> {
                 key: Key::Char('v'),
                 ctrl: false,
                 alt: true,
+                ..
             }
             | Input {
                 key: Key::PageUp, ..
@@ -500,14 +738,43 @@ impl<'a> TextArea<'a> {
                 key: Key::MouseScrollDown,
                 ..
             } => {
-                self.scroll((1, 0));
+                self.scroll_by_wheel(true);
                 false
             }
             Input {
                 key: Key::MouseScrollUp,
                 ..
             } => {
-                self.scroll((-1, 0));
+                self.scroll_by_wheel(false);
+                false
+            }
+            Input {
+                key: Key::MouseClick(x, y),
+                shift,
+                ..
+            } => {
+                self.click_at(x, y, shift);
+                false
+            }
+            Input {
+                key: Key::MouseDrag(x, y),
+                ..
+            } => {
+                if self.mouse_pressed || self.viewport_contains(x, y) {
+                    self.drag_to(x, y);
+                }
+                false
+            }
+            Input {
+                key: Key::MouseRelease(x, y),
+                ..
+            } => {
+                if (self.mouse_pressed || self.viewport_contains(x, y))
+                    && self.selection_start.is_some()
+                {
+                    self.drag_to(x, y);
+                }
+                self.mouse_pressed = false;
                 false
             }
             _ => false,
@@ -535,6 +802,194 @@ impl<'a> TextArea<'a> {
         modified
     }
 
+    /// Handle a key input with the key bindings in the [`KeyMap`] instead of the default key mappings. Typing
+    /// characters without Ctrl and Alt keys and mouse inputs are handled as [`TextArea::input`] does when no action is
+    /// bound to them. Other inputs which are not bound are ignored. This method returns if the input modified text
+    /// contents or not in the textarea.
+    /// ```
+    /// use tui_textarea::{TextArea, Action, Input, Key, KeyMap};
+    ///
+    /// let mut textarea = TextArea::from(["hello", "world"]);
+    ///
+    /// let mut map = KeyMap::default();
+    /// let ctrl_d = Input { key: Key::Char('d'), ctrl: true, alt: false, shift: false };
+    /// map.bind(ctrl_d.clone(), Action::DeleteLine);
+    ///
+    /// assert!(textarea.input_with(ctrl_d, &map));
+    /// assert_eq!(textarea.lines(), ["world"]);
+    /// ```
+    pub fn input_with(&mut self, input: impl Into<Input>, map: &KeyMap) -> bool {
+        let input = input.into();
+        if let Some(action) = map.get(&input) {
+            self.select_by_shift(&input);
+            return self.perform(action);
+        }
+        match input.key {
+            Key::Char(_) if !input.ctrl && !input.alt => self.input(input),
+            Key::MouseScrollDown
+            | Key::MouseScrollUp
+            | Key::MouseClick(..)
+            | Key::MouseDrag(..)
+            | Key::MouseRelease(..) => self.input(input),
+            _ => false,
+        }
+    }
+
+    // Perform the action bound by a key map. This method returns if the action modified text contents or not
+    fn perform(&mut self, action: Action) -> bool {
+        let editable = !self.read_only;
+        match action {
+            Action::InsertNewline if editable && self.within_line_limit(1) => self.input_newline(),
+            Action::InsertTab if editable && self.within_limits(self.tab().len(), 0) => {
+                self.insert_tab()
+            }
+            Action::IndentLines if editable => self.indent_lines(),
+            Action::DedentLines if editable => self.dedent_lines(),
+            Action::DeleteChar if editable => self.delete_char_or_pair(),
+            Action::DeleteNextChar if editable => self.delete_next_char(),
+            Action::DeleteWord if editable => self.delete_word(),
+            Action::DeleteNextWord if editable => self.delete_next_word(),
+            Action::DeleteLineByEnd if editable => self.delete_line_by_end(),
+            Action::DeleteLineByHead if editable => self.delete_line_by_head(),
+            Action::DeleteLine if editable => self.delete_line(),
+            Action::TransposeChars if editable => self.transpose_chars(),
+            Action::DuplicateLine if editable && self.within_limits(0, 1) => self.duplicate_line(),
+            Action::JoinNextLine if editable => self.join_next_line(),
+            Action::MoveLineUp if editable => self.move_line_up(),
+            Action::MoveLineDown if editable => self.move_line_down(),
+            Action::Undo if editable => self.undo(),
+            Action::Redo if editable => self.redo(),
+            Action::Paste if editable => self.paste(),
+            Action::Copy => {
+                self.copy();
+                false
+            }
+            Action::Cut if editable => self.cut().is_some(),
+            Action::SelectAll => {
+                self.select_all();
+                false
+            }
+            Action::MoveCursor(m) => {
+                self.move_cursor(m);
+                false
+            }
+            Action::MoveCursorTop => {
+                self.move_cursor_top();
+                false
+            }
+            Action::MoveCursorBottom => {
+                self.move_cursor_bottom();
+                false
+            }
+            Action::MoveCursorLineStart(smart) => {
+                self.move_cursor_line_start(smart);
+                false
+            }
+            Action::MoveCursorLineEnd => {
+                self.move_cursor_line_end();
+                false
+            }
+            Action::Scroll(s) => {
+                self.scroll(s);
+                false
+            }
+            _ => false,
+        }
+    }
+
+    // Moving the cursor with Shift key extends the selection. Moving it without Shift key cancels the selection started
+    // with Shift key
+    fn select_by_shift(&mut self, input: &Input) {
+        let moves_cursor = matches!(
+            input.key,
+            Key::Left
+                | Key::Right
+                | Key::Up
+                | Key::Down
+                | Key::Home
+                | Key::End
+                | Key::PageUp
+                | Key::PageDown
+                | Key::MouseClick(..)
+        );
+        // Alt+Up and Alt+Down move lines with keeping the selection
+        let moves_lines = input.alt && !input.ctrl && matches!(input.key, Key::Up | Key::Down);
+        if !moves_cursor || moves_lines {
+            return;
+        }
+        if input.shift {
+            if self.selection_start.is_none() {
+                self.start_selection();
+                self.shift_selection = true;
+            }
+        } else if self.shift_selection {
+            self.shift_selection = false;
+            self.cancel_selection();
+        }
+    }
+
+    // Scroll the viewport by mouse wheel without moving the cursor. The viewport cannot scroll past the last line. The
+    // cursor may go out of the viewport and it is not rendered until it moves.
+    fn scroll_by_wheel(&mut self, down: bool) {
+        let (top, _, _, _) = self.viewport.rect();
+        let top = top as usize;
+        let max_top = self.max_scroll_top().0 as usize;
+        let amount = cmp::min(self.scroll_lines_per_wheel, i16::MAX as u16) as usize;
+        let next_top = if down {
+            cmp::max(cmp::min(top + amount, max_top), top)
+        } else {
+            top.saturating_sub(amount)
+        };
+        let delta = next_top as i64 - top as i64;
+        self.viewport.scroll(delta as i16, 0);
+        self.wheel_scrolled_cursor = Some(self.cursor);
+    }
+
+    // Move the cursor to the clicked position. Clicks outside the textarea are ignored, and so are drags and the
+    // release following them
+    fn click_at(&mut self, x: u16, y: u16, shift: bool) {
+        self.mouse_pressed = self.viewport_contains(x, y);
+        if !self.mouse_pressed {
+            return;
+        }
+        if !shift {
+            self.cancel_selection(); // Pressing the button starts a new selection by dragging
+        }
+        self.cursor = self.position_at(x, y);
+    }
+
+    // Whether the (x, y) position on terminal is inside the textarea rendered last time
+    fn viewport_contains(&self, x: u16, y: u16) -> bool {
+        let (_, _, width, height) = self.viewport.rect();
+        let (origin_x, origin_y) = self.viewport.origin();
+        (origin_x..origin_x.saturating_add(width)).contains(&x)
+            && (origin_y..origin_y.saturating_add(height)).contains(&y)
+    }
+
+    // Extend the selection to the position where the mouse is dragged. The selection starts at the position where the
+    // mouse button was pressed. Like the selection started with Shift key, moving the cursor cancels it. When the mouse
+    // is dragged above or below the viewport, the cursor moves by one line beyond the viewport so that the textarea
+    // scrolls.
+    fn drag_to(&mut self, x: u16, y: u16) {
+        if self.selection_start.is_none() {
+            self.start_selection();
+            self.shift_selection = true;
+        }
+
+        let (_, origin_y) = self.viewport.origin();
+        let (_, _, _, height) = self.viewport.rect();
+        let bottom_y = origin_y.saturating_add(height);
+        if height == 0 || (origin_y..bottom_y).contains(&y) {
+            self.cursor = self.position_at(x, y);
+        } else if y < origin_y {
+            let (row, col) = self.position_at(x, origin_y);
+            self.move_cursor_to(row.saturating_sub(1), col);
+        } else {
+            let (row, col) = self.position_at(x, bottom_y - 1);
+            self.move_cursor_to(row + 1, col);
+        }
+    }
+
     /// Handle a key input without default key mappings. This method handles only
     ///
     /// - Single character input without modifier keys
@@ -553,40 +1008,65 @@ impl<'a> TextArea<'a> {
                 key: Key::Char(c),
                 ctrl: false,
                 alt: false,
-            } => {
-                self.insert_char(c);
-                true
-            }
+                ..
+            } if !self.read_only && self.within_limits(1, 0) => self.input_char(c),
             Input {
                 key: Key::Tab,
                 ctrl: false,
                 alt: false,
-            } => self.insert_tab(),
+                ..
+            } if !self.read_only && self.within_limits(self.tab().len(), 0) => self.insert_tab(),
             Input {
                 key: Key::Backspace,
                 ..
-            } => self.delete_char(),
+            } if !self.read_only => self.delete_char_or_pair(),
             Input {
                 key: Key::Delete, ..
-            } => self.delete_next_char(),
+            } if !self.read_only => self.delete_next_char(),
             Input {
                 key: Key::Enter, ..
-            } => {
-                self.insert_newline();
-                true
-            }
+            } if !self.read_only && self.within_line_limit(1) => self.input_newline(),
             Input {
                 key: Key::MouseScrollDown,
                 ..
             } => {
-                self.scroll((1, 0));
+                self.scroll_by_wheel(true);
                 false
             }
             Input {
                 key: Key::MouseScrollUp,
                 ..
             } => {
-                self.scroll((-1, 0));
+                self.scroll_by_wheel(false);
+                false
+            }
+            Input {
+                key: Key::MouseClick(x, y),
+                shift,
+                ..
+            } => {
+                self.click_at(x, y, shift);
+                false
+            }
+            Input {
+                key: Key::MouseDrag(x, y),
+                ..
+            } => {
+                if self.mouse_pressed || self.viewport_contains(x, y) {
+                    self.drag_to(x, y);
+                }
+                false
+            }
+            Input {
+                key: Key::MouseRelease(x, y),
+                ..
+            } => {
+                if (self.mouse_pressed || self.viewport_contains(x, y))
+                    && self.selection_start.is_some()
+                {
+                    self.drag_to(x, y);
+                }
+                self.mouse_pressed = false;
                 false
             }
             _ => false,
@@ -595,9 +1075,29 @@ impl<'a> TextArea<'a> {
 
     fn push_history(&mut self, kind: EditKind, cursor_before: (usize, usize)) {
         let edit = Edit::new(kind, cursor_before, self.cursor);
+        self.selection_start = None;
+        self.desired_col = None;
+        self.wheel_scrolled_cursor = None;
+        self.invalidate_caches(edit.first_row());
+        self.shift_rows(edit.rows_on_redo());
         self.history.push(edit);
     }
 
+    // Move signs and highlights along with their lines when lines were inserted or removed
+    fn shift_rows(&mut self, rows: (usize, usize, usize)) {
+        shift_rows(&mut self.line_signs, rows);
+        shift_rows(&mut self.highlights, rows);
+    }
+
+    // Invalidate caches computed from the text when lines after the row were modified
+    fn invalidate_caches(&mut self, row: usize) {
+        self.version = self.version.wrapping_add(1);
+        self.syntax_cache.invalidate(row);
+        self.max_width.invalidate();
+        #[cfg(feature = "search")]
+        self.search.invalidate();
+    }
+
     /// Insert a single character at current cursor position.
     /// ```
     /// use tui_textarea::TextArea;
@@ -620,18 +1120,149 @@ impl<'a> TextArea<'a> {
         self.push_history(EditKind::InsertChar(c, i), (row, col));
     }
 
-    /// Insert a string at current cursor position. Currently the string must not contain any newlines. This method
-    /// returns if some text was inserted or not in the textarea.
+    // Insert a character typed by a user. When auto-pairs is enabled, a closing character is inserted along with an
+    // opening character and a closing character next to the cursor is typed over
+    fn input_char(&mut self, c: char) -> bool {
+        if !self.auto_pairs {
+            self.insert_char(c);
+            return true;
+        }
+
+        if let (Some(close), Some((start, end))) = (auto_pair_close(c), self.selection_range()) {
+            if start != end
+                && self.selection_mode != SelectionMode::Block
+                && self.within_limits(2, 0)
+            {
+                return self.wrap_selection(c, close, start, end);
+            }
+        }
+
+        let (row, col) = self.cursor;
+        let line = &self.lines[row];
+        let mut chars = line.chars().skip(col.saturating_sub(1));
+        let prev = if col > 0 { chars.next() } else { None };
+        let next = chars.next();
+
+        if next == Some(c) && is_auto_pair_close(c) {
+            self.move_cursor(CursorMove::Forward);
+            return false;
+        }
+
+        let close = match auto_pair_close(c) {
+            Some(close) if self.within_limits(2, 0) => close,
+            _ => {
+                self.insert_char(c);
+                return true;
+            }
+        };
+        // Pair only when the cursor is not followed by a word. A quote after a word is likely an apostrophe
+        let before_word = next.map_or(false, |n| !n.is_whitespace() && !is_auto_pair_close(n));
+        let after_word = c == close && prev.map_or(false, |p| p.is_alphanumeric() || p == c);
+        if before_word || after_word {
+            self.insert_char(c);
+            return true;
+        }
+
+        let i = line.char_indices().nth(col).map_or(line.len(), |(i, _)| i);
+        let pair: String = [c, close].iter().collect();
+        self.lines[row].insert_str(i, &pair);
+        self.cursor.1 += 1;
+        self.push_history(EditKind::Insert(pair, i), (row, col));
+        true
+    }
+
+    // Surround the selected text with the pair as one undo unit. The selection stays on the original text
+    fn wrap_selection(
+        &mut self,
+        open: char,
+        close: char,
+        (start_row, start_col): (usize, usize),
+        (end_row, end_col): (usize, usize),
+    ) -> bool {
+        fn insert_at(line: &mut String, col: usize, c: char) {
+            let i = line.char_indices().nth(col).map_or(line.len(), |(i, _)| i);
+            line.insert(i, c);
+        }
+
+        let mut first = self.lines[start_row].clone();
+        let edits = if start_row == end_row {
+            insert_at(&mut first, end_col, close);
+            insert_at(&mut first, start_col, open);
+            vec![(start_row, first, start_col, 1)]
+        } else {
+            let mut last = self.lines[end_row].clone();
+            insert_at(&mut first, start_col, open);
+            insert_at(&mut last, end_col, close);
+            vec![
+                (start_row, first, start_col, 1),
+                (end_row, last, end_col, 0),
+            ]
+        };
+        self.replace_lines(edits)
+    }
+
+    // Delete one character before the cursor. When auto-pairs is enabled and the cursor is between an empty pair, both
+    // characters of the pair are deleted
+    fn delete_char_or_pair(&mut self) -> bool {
+        let (row, col) = self.cursor;
+        if !self.auto_pairs || col == 0 {
+            return self.delete_char();
+        }
+
+        let line = &self.lines[row];
+        let mut chars = line.char_indices().skip(col - 1);
+        let (i, open, close) = match (chars.next(), chars.next()) {
+            (Some((i, open)), Some((_, close))) if auto_pair_close(open) == Some(close) => {
+                (i, open, close)
+            }
+            _ => return self.delete_char(),
+        };
+        let end = i + open.len_utf8() + close.len_utf8();
+        let removed = line[i..end].to_string();
+        self.lines[row].replace_range(i..end, "");
+        self.cursor.1 -= 1;
+        self.push_history(EditKind::Remove(removed, i), (row, col));
+        true
+    }
+
+    /// Insert a string at current cursor position. When the string contains newlines, the text after each newline is
+    /// inserted as new lines. `\r\n` is also accepted as a newline. The cursor moves to the end of the inserted text.
+    /// Inserting multiple lines is undone at once. When the maximum number of characters or lines is set, the string
+    /// is truncated so that it fits in the limits as [`TextArea::paste`] does. This method returns if some text was
+    /// inserted or not in the textarea.
     /// ```
-    /// use tui_textarea::TextArea;
+    /// use tui_textarea::{TextArea, CursorMove};
     ///
     /// let mut textarea = TextArea::default();
     ///
     /// textarea.insert_str("hello");
     /// assert_eq!(textarea.lines(), ["hello"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Jump(0, 2));
+    /// textarea.insert_str("y\nworld\nhe");
+    /// assert_eq!(textarea.lines(), ["hey", "world", "hello"]);
+    /// assert_eq!(textarea.cursor(), (2, 2));
     /// ```
     pub fn insert_str<S: Into<String>>(&mut self, s: S) -> bool {
         let s = s.into();
+        let mut chunk: Vec<String> = s.split('\n').map(|l| l.to_string()).collect();
+        let last = chunk.len() - 1;
+        for line in &mut chunk[..last] {
+            if line.ends_with('\r') {
+                line.pop();
+            }
+        }
+        self.fit_in_limits(&mut chunk);
+
+        if chunk.len() == 1 {
+            return self.insert_piece(chunk.pop().unwrap());
+        }
+        self.insert_chunk(chunk);
+        true
+    }
+
+    // Insert a string which does not contain any newline at cursor
+    fn insert_piece(&mut self, s: String) -> bool {
         if s.is_empty() {
             return false;
         }
@@ -639,9 +1270,9 @@ impl<'a> TextArea<'a> {
         let (row, col) = self.cursor;
         let line = &mut self.lines[row];
         debug_assert!(
-            !line.contains('\n'),
-            "string given to insert_str must not contain newline: {:?}",
-            line,
+            !s.contains('\n'),
+            "string given to insert_piece must not contain newline: {:?}",
+            s,
         );
 
         let i = line
@@ -692,31 +1323,70 @@ impl<'a> TextArea<'a> {
         }
     }
 
-    /// Insert a tab at current cursor position. Note that this method does nothing when the tab length is 0. This
-    /// method returns if a tab string was inserted or not in the textarea.
-    /// textarea.
-    /// ```
-    /// use tui_textarea::TextArea;
-    ///
-    /// let mut textarea = TextArea::from(["hi"]);
-    ///
+    // Insert multiple lines at cursor. The cursor moves to the end of the inserted text
+    fn insert_chunk(&mut self, chunk: Vec<String>) {
+        debug_assert!(
+            chunk.len() > 1,
+            "chunk must contain multiple lines: {:?}",
+            chunk
+        );
+
+        let (row, col) = self.cursor;
+        let line = &mut self.lines[row];
+        let i = line
+            .char_indices()
+            .nth(col)
+            .map(|(i, _)| i)
+            .unwrap_or(line.len());
+
+        let last_row = row + chunk.len() - 1;
+        let last_col = chunk[chunk.len() - 1].chars().count();
+        let mut last = chunk[chunk.len() - 1].clone();
+        last.push_str(&line[i..]);
+        line.truncate(i);
+        line.push_str(&chunk[0]);
+        let middle = chunk[1..chunk.len() - 1].iter().cloned();
+        self.lines
+            .splice(row + 1..row + 1, middle.chain(std::iter::once(last)));
+
+        self.cursor = (last_row, last_col);
+        self.push_history(EditKind::InsertChunk(chunk, row, i), (row, col));
+    }
+
+    /// Insert a tab at current cursor position. When hard tab is disabled, spaces are inserted until the next tab stop
+    /// from the display column of the cursor, where tabs and wide characters occupy multiple columns. When hard tab is
+    /// enabled by [`TextArea::set_hard_tab_indent`], a tab character is inserted. This method returns if a tab string
+    /// was inserted or not in the textarea.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["hi", "日本"]);
+    ///
+    /// textarea.insert_tab();
+    /// assert_eq!(textarea.lines(), ["    hi", "日本"]);
+    ///
+    /// // "日" occupies 2 columns. 2 spaces are inserted to reach the next tab stop
+    /// textarea.move_cursor(CursorMove::Jump(1, 1));
     /// textarea.insert_tab();
-    /// assert_eq!(textarea.lines(), ["    hi"]);
+    /// assert_eq!(textarea.lines(), ["    hi", "日  本"]);
     /// ```
     pub fn insert_tab(&mut self) -> bool {
-        if self.tab_len == 0 {
-            return false;
-        }
-        let tab = if self.hard_tab_indent {
+        self.insert_str(self.tab())
+    }
+
+    // String inserted by a tab at the cursor position
+    fn tab(&self) -> &'static str {
+        if self.hard_tab_indent {
             "\t"
         } else {
-            let len = self.tab_len - (self.cursor.1 % self.tab_len as usize) as u8;
-            spaces(len)
-        };
-        self.insert_str(tab)
+            let (row, col) = self.cursor;
+            let col = display_col(&self.lines[row], col, self.tab_len);
+            spaces(tab_width(col, self.tab_len) as u8)
+        }
     }
 
-    /// Insert a newline at current cursor position.
+    /// Insert a newline at current cursor position. When auto-indent is enabled by [`TextArea::set_auto_indent`], the
+    /// leading whitespaces of the current line before the cursor are inserted after the newline.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
@@ -727,19 +1397,120 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.lines(), ["h", "i"]);
     /// ```
     pub fn insert_newline(&mut self) {
+        self.split_line(false);
+    }
+
+    // Insert a newline typed by a user. The auto-indent is inserted only when it fits in the character limit
+    fn input_newline(&mut self) -> bool {
+        self.split_line(true);
+        true
+    }
+
+    fn split_line(&mut self, limit_indent: bool) {
         let (row, col) = self.cursor;
-        let line = &mut self.lines[row];
+        let line = &self.lines[row];
         let idx = line
             .char_indices()
             .nth(col)
             .map(|(i, _)| i)
             .unwrap_or(line.len());
-        let next_line = line[idx..].to_string();
+        let indent_len = if self.auto_indent {
+            line[..idx]
+                .find(|c: char| !c.is_whitespace())
+                .unwrap_or(idx)
+        } else {
+            0
+        };
+        let indent = line[..indent_len].to_string();
+        let indent_chars = indent.chars().count();
+
+        if indent.is_empty() || (limit_indent && !self.within_limits(indent_chars, 0)) {
+            let line = &mut self.lines[row];
+            let next_line = line[idx..].to_string();
+            line.truncate(idx);
+
+            self.lines.insert(row + 1, next_line);
+            self.cursor = (row + 1, 0);
+            self.push_history(EditKind::InsertNewline(idx), (row, col));
+            return;
+        }
+
+        // Insert the newline and the indent as one undo unit
+        let line = &mut self.lines[row];
+        let next_line = format!("{}{}", indent, &line[idx..]);
         line.truncate(idx);
 
         self.lines.insert(row + 1, next_line);
-        self.cursor = (row + 1, 0);
-        self.push_history(EditKind::InsertNewline(idx), (row, col));
+        self.cursor = (row + 1, indent_chars);
+        let chunk = vec![String::new(), indent];
+        self.push_history(EditKind::InsertChunk(chunk, row, idx), (row, col));
+    }
+
+    /// Open a new line below the current line and move the cursor to it without splitting the current line, like `o` of
+    /// Vim. When auto-indent is enabled by [`TextArea::set_auto_indent`], the new line is indented with the leading
+    /// whitespaces of the current line. Opening the line is undone at once.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["  foo", "bar"]);
+    /// textarea.set_auto_indent(true);
+    ///
+    /// textarea.insert_newline_below();
+    /// assert_eq!(textarea.lines(), ["  foo", "  ", "bar"]);
+    /// assert_eq!(textarea.cursor(), (1, 2));
+    /// ```
+    pub fn insert_newline_below(&mut self) {
+        let row = self.cursor.0;
+        let (indent, indent_chars) = self.auto_indent_of(row);
+        let idx = self.lines[row].len();
+        self.lines.insert(row + 1, indent.clone());
+        let before = self.cursor;
+        self.cursor = (row + 1, indent_chars);
+        self.push_history(
+            EditKind::InsertChunk(vec![String::new(), indent], row, idx),
+            before,
+        );
+    }
+
+    /// Open a new line above the current line and move the cursor to it without splitting the current line, like `O` of
+    /// Vim. When auto-indent is enabled by [`TextArea::set_auto_indent`], the new line is indented with the leading
+    /// whitespaces of the current line. Opening the line is undone at once.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["  foo"]);
+    /// textarea.set_auto_indent(true);
+    ///
+    /// textarea.insert_newline_above();
+    /// assert_eq!(textarea.lines(), ["  ", "  foo"]);
+    /// assert_eq!(textarea.cursor(), (0, 2));
+    /// ```
+    pub fn insert_newline_above(&mut self) {
+        let row = self.cursor.0;
+        let (indent, indent_chars) = self.auto_indent_of(row);
+        self.lines.insert(row, indent.clone());
+        let before = self.cursor;
+        self.cursor = (row, indent_chars);
+        self.push_history(
+            EditKind::InsertChunk(vec![indent, String::new()], row, 0),
+            before,
+        );
+    }
+
+    // Indent of a new line opened next to the line with auto-indent, and its length in characters. The indent is empty
+    // when auto-indent is disabled or it exceeds the limit of characters
+    fn auto_indent_of(&self, row: usize) -> (String, usize) {
+        if !self.auto_indent {
+            return (String::new(), 0);
+        }
+        let line = &self.lines[row];
+        let indent: String = line.chars().take_while(|c| c.is_whitespace()).collect();
+        let indent_chars = indent.chars().count();
+        if self.within_limits(indent_chars, 0) {
+            (indent, indent_chars)
+        } else {
+            (String::new(), 0)
+        }
     }
 
     /// Delete a newline from **head** of current cursor line. This method returns if a newline was deleted or not in
@@ -770,7 +1541,8 @@ impl<'a> TextArea<'a> {
     }
 
     /// Delete one character before cursor. When the cursor is at head of line, the newline before the cursor will be
-    /// removed. This method returns if some text was deleted or not in the textarea.
+    /// removed. A grapheme cluster consisting of multiple characters such as an emoji sequence is deleted at once. This
+    /// method returns if some text was deleted or not in the textarea.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
@@ -787,14 +1559,23 @@ impl<'a> TextArea<'a> {
         }
 
         let line = &mut self.lines[row];
-        if let Some((i, c)) = line.char_indices().nth(col - 1) {
+        let start = prev_grapheme_col(line, col);
+        let (i, c) = match line.char_indices().nth(start) {
+            Some(found) => found,
+            None => return false,
+        };
+        self.cursor.1 = start;
+        if start + 1 == col {
             line.remove(i);
-            self.cursor.1 -= 1;
             self.push_history(EditKind::DeleteChar(c, i), (row, col));
-            true
         } else {
-            false
+            // Delete all characters in the grapheme cluster before the cursor
+            let end = line.char_indices().nth(col).map_or(line.len(), |(j, _)| j);
+            let removed = line[i..end].to_string();
+            line.replace_range(i..end, "");
+            self.push_history(EditKind::Remove(removed, i), (row, col));
         }
+        true
     }
 
     /// Delete one character next to cursor. When the cursor is at end of line, the newline next to the cursor will be
@@ -817,6 +1598,41 @@ impl<'a> TextArea<'a> {
         self.delete_char()
     }
 
+    /// Swap the character before the cursor and the character at the cursor, then move the cursor forward as `Ctrl+T`
+    /// of Emacs does. When the cursor is at end of line, the last two characters of the line are swapped and the cursor
+    /// doesn't move. Nothing happens when the cursor is at head of line or the line has less than two characters. This
+    /// method returns if the characters were swapped or not in the textarea.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["abc"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Forward);
+    /// textarea.transpose_chars();
+    /// assert_eq!(textarea.lines(), ["bac"]);
+    /// assert_eq!(textarea.cursor(), (0, 2));
+    ///
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.transpose_chars();
+    /// assert_eq!(textarea.lines(), ["bca"]);
+    /// assert_eq!(textarea.cursor(), (0, 3));
+    /// ```
+    pub fn transpose_chars(&mut self) -> bool {
+        let (row, col) = self.cursor;
+        let mut chars: Vec<char> = self.lines[row].chars().collect();
+        if col == 0 || chars.len() < 2 {
+            return false;
+        }
+
+        let at = cmp::min(col, chars.len() - 1);
+        chars.swap(at - 1, at);
+        let new: String = chars.into_iter().collect();
+        let old = std::mem::replace(&mut self.lines[row], new.clone());
+        self.cursor.1 = at + 1;
+        self.push_history(EditKind::ReplaceLines(vec![(row, old, new)]), (row, col));
+        true
+    }
+
     /// Delete string from cursor to end of the line. When the cursor is at end of line, the newline next to the cursor
     /// is removed. This method returns if some text was deleted or not in the textarea.
     /// ```
@@ -859,9 +1675,51 @@ impl<'a> TextArea<'a> {
         self.delete_newline()
     }
 
+    /// Delete the current line including its newline. On the last line, the newline before it is deleted instead. The
+    /// cursor moves to the head of the line which took the place of the deleted line. The deleted line is yanked with a
+    /// newline at the end. When the text is only one empty line, nothing happens. This method returns if some text was
+    /// deleted or not in the textarea.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["foo", "bar", "baz"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Down);
+    /// textarea.delete_line();
+    /// assert_eq!(textarea.lines(), ["foo", "baz"]);
+    /// assert_eq!(textarea.cursor(), (1, 0));
+    /// assert_eq!(textarea.yank_text(), "bar\n");
+    ///
+    /// textarea.delete_line();
+    /// assert_eq!(textarea.lines(), ["foo"]);
+    /// assert_eq!(textarea.cursor(), (0, 0));
+    /// ```
+    pub fn delete_line(&mut self) -> bool {
+        let row = self.cursor.0;
+        let last = self.lines.len() - 1;
+        let len = self.lines[row].chars().count();
+        if last == 0 && len == 0 {
+            return false;
+        }
+
+        let yank = format!("{}\n", self.lines[row]);
+        if row < last {
+            self.delete_range((row, 0), (row + 1, 0));
+        } else if row > 0 {
+            let prev = self.lines[row - 1].chars().count();
+            self.delete_range((row - 1, prev), (row, len));
+            self.cursor.1 = 0;
+        } else {
+            self.delete_range((row, 0), (row, len));
+        }
+        self.yank = yank;
+        true
+    }
+
     /// Delete a word before cursor. Word boundary appears at spaces, punctuations, and others. For example `fn foo(a)`
-    /// consists of words `fn`, `foo`, `(`, `a`, `)`. When the cursor is at head of line, the newline before the cursor
-    /// will be removed.
+    /// consists of words `fn`, `foo`, `(`, `a`, `)`. Like Ctrl+W in readline, whitespaces between the cursor and the
+    /// word are deleted together with the word. When the cursor is at head of line, the newline before the cursor will
+    /// be removed. The deleted text is yanked and the deletion is undone at once.
     ///
     /// This method returns if some text was deleted or not in the textarea.
     ///
@@ -923,8 +1781,10 @@ impl<'a> TextArea<'a> {
     }
 
     /// Paste a string previously deleted by [`TextArea::delete_line_by_head`], [`TextArea::delete_line_by_end`],
-    /// [`TextArea::delete_word`], [`TextArea::delete_next_word`]. This method returns if some text was inserted or not
-    /// in the textarea.
+    /// [`TextArea::delete_word`], [`TextArea::delete_next_word`]. When the yanked text contains newlines, multiple
+    /// lines are inserted. The cursor moves to the end of the pasted text. When the maximum number of characters or
+    /// lines is set, the pasted text is truncated so that it fits in the limits. See [`TextArea::set_max_chars`] and
+    /// [`TextArea::set_max_lines`]. This method returns if some text was inserted or not in the textarea.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
@@ -936,464 +1796,2718 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.lines(), [" bbb cccaaa"]);
     /// ```
     pub fn paste(&mut self) -> bool {
-        self.insert_str(self.yank.to_string())
+        self.insert_str(self.yank.clone())
     }
 
-    /// Move the cursor to the position specified by the [`CursorMove`] parameter. For each kind of cursor moves, see
-    /// the document of [`CursorMove`].
+    /// Copy the selected text. The copied text is returned and also set to the yank buffer so that it can be inserted
+    /// by [`TextArea::paste`]. Lines in the copied text are joined with `\n`. The selection is cancelled after copying.
+    /// This method returns `None` when no selection is active.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
-    /// let mut textarea = TextArea::from(["abc", "def"]);
+    /// let mut textarea = TextArea::from(["hello", "world"]);
+    /// assert_eq!(textarea.copy(), None);
     ///
     /// textarea.move_cursor(CursorMove::Forward);
-    /// assert_eq!(textarea.cursor(), (0, 1));
+    /// textarea.start_selection();
     /// textarea.move_cursor(CursorMove::Down);
-    /// assert_eq!(textarea.cursor(), (1, 1));
+    /// assert_eq!(textarea.copy().as_deref(), Some("ello\nw"));
+    /// assert_eq!(textarea.yank_text(), "ello\nw");
+    /// assert_eq!(textarea.lines(), ["hello", "world"]);
     /// ```
-    pub fn move_cursor(&mut self, m: CursorMove) {
-        if let Some(cursor) = m.next_cursor(self.cursor, &self.lines, &self.viewport) {
-            // log::debug!("move cursor: {:?} -> {:?}", self.cursor, cursor);
-            self.cursor = cursor;
-        }
+    pub fn copy(&mut self) -> Option<String> {
+        let (start, end) = self.selection_range()?;
+        self.selection_start = None;
+        self.yank = if self.selection_mode == SelectionMode::Block {
+            self.block_chunk(start, end).join("\n")
+        } else {
+            self.selected_chunk(start, end).join("\n")
+        };
+        Some(self.yank.clone())
     }
 
-    /// Undo the last modification. This method returns if the undo modified text contents or not in the textarea.
+    /// Cut the selected text. The cut text is deleted from the textarea, returned, and set to the yank buffer as
+    /// [`TextArea::copy`] does. The cursor moves to the start of the selection and the selection is cancelled. Cutting
+    /// is undone at once by [`TextArea::undo`]. This method returns `None` when no selection is active.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
-    /// let mut textarea = TextArea::from(["abc def"]);
+    /// let mut textarea = TextArea::from(["hello", "world"]);
+    /// assert_eq!(textarea.cut(), None);
+    ///
+    /// textarea.move_cursor(CursorMove::Jump(1, 1));
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Jump(0, 4));
+    /// assert_eq!(textarea.cut().as_deref(), Some("o\nw"));
+    /// assert_eq!(textarea.lines(), ["hellorld"]);
+    /// assert_eq!(textarea.cursor(), (0, 4));
     ///
-    /// textarea.delete_next_word();
-    /// assert_eq!(textarea.lines(), [" def"]);
     /// textarea.undo();
-    /// assert_eq!(textarea.lines(), ["abc def"]);
+    /// assert_eq!(textarea.lines(), ["hello", "world"]);
     /// ```
-    pub fn undo(&mut self) -> bool {
-        if let Some(cursor) = self.history.undo(&mut self.lines) {
-            self.cursor = cursor;
-            true
-        } else {
-            false
+    pub fn cut(&mut self) -> Option<String> {
+        if self.selection_mode == SelectionMode::Block {
+            return self.cut_block();
         }
+        let (start, end) = self.selection_range()?;
+        self.selection_start = None;
+        let chunk = self.selected_chunk(start, end);
+        self.yank = chunk.join("\n");
+
+        self.delete_chunk(start, end, chunk);
+        Some(self.yank.clone())
     }
 
-    /// Redo the last undo change. This method returns if the redo modified text contents or not in the textarea.
+    /// Delete text between two 0-base character-wise (row, col) positions and return the deleted text. Lines in the
+    /// returned text are joined with `\n`. The positions can be given in any order and they are clamped to the end of
+    /// line and the last line. The end position is exclusive. The cursor moves to the start position and the selection
+    /// is cancelled. Unlike [`TextArea::cut`], the deleted text is not set to the yank buffer. Deleting is undone at
+    /// once by [`TextArea::undo`].
     /// ```
-    /// use tui_textarea::{TextArea, CursorMove};
+    /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::from(["abc def"]);
+    /// let mut textarea = TextArea::from(["hello", "world"]);
+    ///
+    /// let deleted = textarea.delete_range((1, 1), (0, 4));
+    /// assert_eq!(deleted, "o\nw");
+    /// assert_eq!(textarea.lines(), ["hellorld"]);
+    /// assert_eq!(textarea.cursor(), (0, 4));
     ///
-    /// textarea.delete_next_word();
-    /// assert_eq!(textarea.lines(), [" def"]);
     /// textarea.undo();
-    /// assert_eq!(textarea.lines(), ["abc def"]);
-    /// textarea.redo();
-    /// assert_eq!(textarea.lines(), [" def"]);
+    /// assert_eq!(textarea.lines(), ["hello", "world"]);
     /// ```
-    pub fn redo(&mut self) -> bool {
-        if let Some(cursor) = self.history.redo(&mut self.lines) {
-            self.cursor = cursor;
-            true
+    pub fn delete_range(&mut self, start: (usize, usize), end: (usize, usize)) -> String {
+        let clamp = |(row, col): (usize, usize)| {
+            let row = cmp::min(row, self.lines.len() - 1);
+            (row, cmp::min(col, self.lines[row].chars().count()))
+        };
+        let (start, end) = (clamp(start), clamp(end));
+        let (start, end) = if start <= end {
+            (start, end)
         } else {
-            false
-        }
-    }
+            (end, start)
+        };
 
-    pub(crate) fn line_spans<'b>(&'b self, line: &'b str, row: usize, lnum_len: u8) -> Line<'b> {
-        let mut hl = LineHighlighter::new(line, self.cursor_style, self.tab_len);
+        self.selection_start = None;
+        let chunk = self.selected_chunk(start, end);
+        let deleted = chunk.join("\n");
+        self.delete_chunk(start, end, chunk);
+        deleted
+    }
 
-        if let Some(style) = self.line_number_style {
-            hl.line_number(row, lnum_len, style);
+    /// Apply the list of edits at once, such as text edits returned from a language server or a formatter. All ranges
+    /// of the edits refer to the text before applying any of them, so the edits can be given in any order. Edits are
+    /// applied in reverse document order so that applying an edit does not shift the ranges of the edits before it.
+    /// Edits inserting text at the same position are inserted in the given order. Positions are clamped to the end of
+    /// line and the last line as [`TextArea::delete_range`] does.
+    ///
+    /// All edits are undone at once by [`TextArea::undo`]. The cursor keeps its position relative to the text around
+    /// it. When the cursor was in a replaced range, it moves to the end of the new text. This method returns if the
+    /// text was modified or not. When the ranges of some edits overlap, an error is returned and no edit is applied.
+    /// ```
+    /// use tui_textarea::{TextArea, TextEdit, TextEditError};
+    ///
+    /// let mut textarea = TextArea::from(["fn main(){", "println!()}"]);
+    ///
+    /// let edits = [
+    ///     TextEdit::new((1, 10), (1, 10), "\n"),
+    ///     TextEdit::new((0, 9), (0, 9), " "),
+    ///     TextEdit::new((1, 0), (1, 0), "    "),
+    /// ];
+    /// assert_eq!(textarea.apply_edits(&edits), Ok(true));
+    /// assert_eq!(textarea.lines(), ["fn main() {", "    println!()", "}"]);
+    ///
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["fn main(){", "println!()}"]);
+    ///
+    /// let edits = [
+    ///     TextEdit::new((0, 0), (0, 5), "foo"),
+    ///     TextEdit::new((0, 3), (1, 0), "bar"),
+    /// ];
+    /// assert_eq!(textarea.apply_edits(&edits), Err(TextEditError::Overlap(0, 1)));
+    /// assert_eq!(textarea.lines(), ["fn main(){", "println!()}"]);
+    /// ```
+    pub fn apply_edits(&mut self, edits: &[TextEdit]) -> Result<bool, TextEditError> {
+        let clamp = |(row, col): (usize, usize)| {
+            let row = cmp::min(row, self.lines.len() - 1);
+            (row, cmp::min(col, self.lines[row].chars().count()))
+        };
+        let mut ranges: Vec<_> = edits
+            .iter()
+            .enumerate()
+            .map(|(i, edit)| {
+                let (start, end) = (clamp(edit.range.0), clamp(edit.range.1));
+                if start <= end {
+                    (start, end, i)
+                } else {
+                    (end, start, i)
+                }
+            })
+            .collect();
+        ranges.sort_unstable();
+        for w in ranges.windows(2) {
+            let ((_, end, i), (start, _, j)) = (w[0], w[1]);
+            if end > start {
+                return Err(TextEditError::Overlap(cmp::min(i, j), cmp::max(i, j)));
+            }
         }
+        let first = match ranges.first() {
+            Some(&((row, _), _, _)) => row,
+            None => return Ok(false),
+        };
+        let last = ranges.iter().map(|&(_, (row, _), _)| row).max().unwrap();
 
-        if row == self.cursor.0 {
-            hl.cursor_line(self.cursor.1, self.cursor_line_style);
+        // Build the new text of the lines from the first row to the last row. Byte offsets in the text joined with
+        // newlines are used to track the cursor
+        let old = &self.lines[first..=last];
+        let offset = |(row, col): (usize, usize)| {
+            let line = &old[row - first];
+            let i = line.char_indices().nth(col).map_or(line.len(), |(i, _)| i);
+            old[..row - first]
+                .iter()
+                .map(|l| l.len() + 1)
+                .sum::<usize>()
+                + i
+        };
+        let text = old.join("\n");
+        let (row, col) = self.cursor;
+        let cursor = if first <= row && row <= last {
+            Some(offset((row, col)))
+        } else {
+            None
+        };
+        let mut new = String::with_capacity(text.len());
+        let mut new_cursor = None;
+        let mut prev = 0;
+        for &(start, end, i) in &ranges {
+            let (start, end) = (offset(start), offset(end));
+            new.push_str(&text[prev..start]);
+            match cursor {
+                Some(c) if new_cursor.is_none() && c <= start => {
+                    new_cursor = Some(new.len() - (start - c));
+                }
+                _ => {}
+            }
+            new.push_str(&edits[i].new_text.replace("\r\n", "\n"));
+            match cursor {
+                Some(c) if new_cursor.is_none() && c < end => new_cursor = Some(new.len()),
+                _ => {}
+            }
+            prev = end;
         }
+        new.push_str(&text[prev..]);
 
-        #[cfg(feature = "search")]
-        if let Some(matches) = self.search.matches(line) {
-            hl.search(matches, self.search.style);
+        let new_lines: Vec<String> = new.split('\n').map(|l| l.to_string()).collect();
+        if new_lines[..] == *old {
+            return Ok(false);
         }
 
-        hl.into_spans()
+        let cursor = match cursor {
+            Some(c) => {
+                let c = new_cursor.unwrap_or_else(|| new.len() - (text.len() - c));
+                let prefix = &new[..c];
+                let head = prefix.rfind('\n').map_or(0, |i| i + 1);
+                (
+                    first + prefix.matches('\n').count(),
+                    prefix[head..].chars().count(),
+                )
+            }
+            None if row > last => (row - old.len() + new_lines.len(), col),
+            None => (row, col),
+        };
+
+        let before = self.cursor;
+        let old = self
+            .lines
+            .splice(first..=last, new_lines.iter().cloned())
+            .collect();
+        self.cursor = cursor;
+        self.push_history(EditKind::SpliceLines(first, old, new_lines), before);
+        Ok(true)
     }
 
-    // pub(crate) fn syntax_line_spans<'b>(
-    //     &'b self,
-    //     mut h: &mut HighlightLines,
-    //     s: &SyntaxSet,
-    //     line: &'b str,
-    //     row: usize,
-    //     lnum_len: u8,
-    // ) -> Spans<'b> {
-    //     let mut hl = LineHighlighter::new(line, self.cursor_style, self.tab_len);
+    // Delete the chunk of text returned by `selected_chunk` in the range. The cursor moves to the start of the range
+    fn delete_chunk(&mut self, start: (usize, usize), end: (usize, usize), chunk: Vec<String>) {
+        let cursor_before = self.cursor;
+        self.cursor = start;
+        if start == end {
+            return;
+        }
 
-    //     if let Some(style) = self.line_number_style {
-    //         hl.line_number(row, lnum_len, style);
-    //     }
+        let line = &self.lines[start.0];
+        let i = line
+            .char_indices()
+            .nth(start.1)
+            .map(|(i, _)| i)
+            .unwrap_or(line.len());
 
-    //     if row == self.cursor.0 {
-    //         hl.cursor_line(self.cursor.1, self.cursor_line_style);
-    //     }
+        if chunk.len() == 1 {
+            let removed = chunk.into_iter().next().unwrap();
+            self.lines[start.0].replace_range(i..i + removed.len(), "");
+            self.push_history(EditKind::Remove(removed, i), cursor_before);
+        } else {
+            let last_len = chunk[chunk.len() - 1].len();
+            let rest = self.lines[end.0].split_off(last_len);
+            self.lines[start.0].truncate(i);
+            self.lines[start.0].push_str(&rest);
+            self.lines.drain(start.0 + 1..=end.0);
+            self.push_history(EditKind::RemoveChunk(chunk, start.0, i), cursor_before);
+        }
+    }
 
-    //     let ranges: Vec<(SyntectStyle, &str)> = h.highlight_line(line, s).unwrap();
-    //     let escaped = as_24_bit_terminal_escaped(&ranges[..], true);
+    // Cut the rectangle of the block selection line by line as one undo unit. The cursor stays on its row and moves to
+    // the left edge of the rectangle
+    fn cut_block(&mut self) -> Option<String> {
+        let (start, end) = self.selection_range()?;
+        let chunk = self.block_chunk(start, end);
+        self.yank = chunk.join("\n");
 
-    //     hl.push_spans(escaped.into_spans().unwrap().0);
+        let left = cmp::min(start.1, end.1);
+        let edits = chunk
+            .into_iter()
+            .enumerate()
+            .filter(|(_, removed)| !removed.is_empty())
+            .map(|(i, removed)| {
+                let row = start.0 + i;
+                let line = &self.lines[row];
+                let idx = line
+                    .char_indices()
+                    .nth(left)
+                    .map(|(i, _)| i)
+                    .unwrap_or(line.len());
+                let mut new = line.clone();
+                new.replace_range(idx..idx + removed.len(), "");
+                let delta = -(removed.chars().count() as isize);
+                (row, new, left, delta)
+            })
+            .collect();
+        self.replace_lines(edits);
+        self.selection_start = None;
+        Some(self.yank.clone())
+    }
 
-    //     #[cfg(feature = "search")]
-    //     if let Some(matches) = self.search.matches(line) {
-    //         hl.search(matches, self.search.style);
-    //     }
+    // Text in the rectangle whose corners are the positions as lines. The right column is exclusive. Lines shorter than
+    // the right column are clamped to their ends
+    fn block_chunk(&self, start: (usize, usize), end: (usize, usize)) -> Vec<String> {
+        let (left, right) = (cmp::min(start.1, end.1), cmp::max(start.1, end.1));
+        self.lines[start.0..=end.0]
+            .iter()
+            .map(|line| line.chars().skip(left).take(right - left).collect())
+            .collect()
+    }
 
-    //     hl.into_spans()
-    // }
+    // Text in the range of (row, col) positions as lines. The end position is exclusive
+    fn selected_chunk(&self, start: (usize, usize), end: (usize, usize)) -> Vec<String> {
+        let byte_idx = |row: usize, col| {
+            let line = &self.lines[row];
+            line.char_indices()
+                .nth(col)
+                .map(|(i, _)| i)
+                .unwrap_or(line.len())
+        };
+        let (i, j) = (byte_idx(start.0, start.1), byte_idx(end.0, end.1));
 
-    /// Build a tui-rs widget to render the current state of the textarea. The widget instance returned from this
-    /// method can be rendered with [`tui::terminal::Frame::render_widget`].
-    /// ```no_run
-    /// use tui::backend::CrosstermBackend;
-    /// use tui::layout::{Constraint, Direction, Layout};
-    /// use tui::Terminal;
-    /// use tui_textarea::TextArea;
+        if start.0 == end.0 {
+            return vec![self.lines[start.0][i..j].to_string()];
+        }
+
+        let mut chunk = Vec::with_capacity(end.0 - start.0 + 1);
+        chunk.push(self.lines[start.0][i..].to_string());
+        chunk.extend(self.lines[start.0 + 1..end.0].iter().cloned());
+        chunk.push(self.lines[end.0][..j].to_string());
+        chunk
+    }
+
+    /// Indent the current line by one level, or every line covered by the active selection. One level is a tab
+    /// character when hard tab indent is enabled by [`TextArea::set_hard_tab_indent`], or spaces of the tab length
+    /// otherwise. Empty lines in the selection are not indented. The cursor and the selection are kept on the same
+    /// characters. Indenting multiple lines is undone at once. This method returns if some line was indented or not.
+    /// Note that [`TextArea::indent`] returns the string of one indent level.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
     ///
-    /// let mut textarea = TextArea::default();
+    /// let mut textarea = TextArea::from(["foo", "", "bar"]);
     ///
-    /// let layout = Layout::default()
-    ///     .direction(Direction::Vertical)
-    ///     .constraints([Constraint::Min(1)].as_ref());
-    /// let backend = CrosstermBackend::new(std::io::stdout());
-    /// let mut term = Terminal::new(backend).unwrap();
+    /// textarea.set_tab_length(2);
+    /// textarea.indent_lines();
+    /// assert_eq!(textarea.lines(), ["  foo", "", "bar"]);
     ///
-    /// loop {
-    ///     term.draw(|f| {
-    ///         let chunks = layout.split(f.size());
-    ///         let widget = textarea.widget();
-    ///         f.render_widget(widget, chunks[0]);
-    ///     }).unwrap();
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// textarea.indent_lines();
+    /// assert_eq!(textarea.lines(), ["    foo", "", "  bar"]);
     ///
-    ///     // ...
-    /// }
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["  foo", "", "bar"]);
     /// ```
-    pub fn widget(&'a self) -> impl Widget + 'a {
-        Renderer::new(self)
+    pub fn indent_lines(&mut self) -> bool {
+        let indent = self.indent();
+        if indent.is_empty() {
+            return false;
+        }
+        let (first, last) = self.selected_rows();
+        let selected = self.selection_start.is_some();
+        let edits = (first..=last)
+            .filter(|&row| !selected || !self.lines[row].is_empty())
+            .map(|row| {
+                let line = format!("{}{}", indent, self.lines[row]);
+                (row, line, 0, indent.len() as isize)
+            })
+            .collect();
+        self.replace_lines(edits)
     }
 
-    pub fn syntax_widget(&'a self, theme: &'a str) -> impl Widget + 'a {
-        SyntaxRenderer::new(self, theme)
+    /// Dedent the current line by one level, or every line covered by the active selection. Up to tab length of
+    /// leading spaces or one leading tab character are removed from each line. The cursor and the selection are kept on
+    /// the same characters. Dedenting multiple lines is undone at once. This method returns if some line was dedented
+    /// or not. This is mapped to `Shift+Tab` by default.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["      foo", "\tbar", " baz"]);
+    ///
+    /// textarea.dedent_lines();
+    /// assert_eq!(textarea.lines(), ["  foo", "\tbar", " baz"]);
+    ///
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Jump(2, 1));
+    /// textarea.dedent_lines();
+    /// assert_eq!(textarea.lines(), ["foo", "bar", "baz"]);
+    /// ```
+    pub fn dedent_lines(&mut self) -> bool {
+        let (first, last) = self.selected_rows();
+        let tab_len = self.tab_len as usize;
+        let edits = (first..=last)
+            .filter_map(|row| {
+                let line = &self.lines[row];
+                let len = if line.starts_with('\t') {
+                    1
+                } else {
+                    line.chars().take(tab_len).take_while(|&c| c == ' ').count()
+                };
+                (len > 0).then(|| (row, line[len..].to_string(), 0, -(len as isize)))
+            })
+            .collect();
+        self.replace_lines(edits)
     }
 
-    /// Set the style of textarea. By default, textarea is not styled.
+    /// Toggle line comment of the current line, or every line covered by the active selection. When all the lines are
+    /// commented with the comment token, the token is removed from them. Otherwise the token is inserted at the first
+    /// non-whitespace character of every line. Blank lines in the selection are ignored. The cursor and the selection
+    /// are kept on the same characters. Toggling comments of multiple lines is undone at once. This method returns if
+    /// some line was modified or not.
     /// ```
-    /// use tui::style::{Style, Color};
-    /// use tui_textarea::TextArea;
+    /// use tui_textarea::{TextArea, CursorMove};
     ///
-    /// let mut textarea = TextArea::default();
-    /// let style = Style::default().fg(Color::Red);
-    /// textarea.set_style(style);
-    /// assert_eq!(textarea.style(), style);
+    /// let mut textarea = TextArea::from(["fn main() {", "    foo();", "}"]);
+    ///
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Jump(1, 4));
+    /// textarea.toggle_comment("// ");
+    /// assert_eq!(textarea.lines(), ["// fn main() {", "    // foo();", "}"]);
+    ///
+    /// textarea.toggle_comment("// ");
+    /// assert_eq!(textarea.lines(), ["fn main() {", "    foo();", "}"]);
     /// ```
-    pub fn set_style(&mut self, style: Style) {
-        self.style = style;
-    }
+    pub fn toggle_comment(&mut self, token: &str) -> bool {
+        // Comment token without trailing whitespaces such as "//" for "// " is also accepted on uncommenting
+        let bare = token.trim_end();
+        if bare.is_empty() {
+            return false;
+        }
 
-    /// Get the current style of textarea.
-    pub fn style(&self) -> Style {
-        self.style
+        let (first, last) = self.selected_rows();
+        let selected = self.selection_start.is_some();
+        let rows: Vec<_> = (first..=last)
+            .filter(|&row| !selected || !self.lines[row].trim().is_empty())
+            .map(|row| {
+                let line = &self.lines[row];
+                (row, line.len() - line.trim_start().len())
+            })
+            .collect();
+        let commented = rows
+            .iter()
+            .all(|&(row, i)| self.lines[row][i..].starts_with(bare));
+
+        let edits = rows
+            .into_iter()
+            .map(|(row, i)| {
+                let line = &self.lines[row];
+                let col = line[..i].chars().count();
+                if commented {
+                    let rest = &line[i..];
+                    let len = if rest.starts_with(token) {
+                        token.len()
+                    } else {
+                        bare.len()
+                    };
+                    let new = format!("{}{}", &line[..i], &rest[len..]);
+                    let delta = -(rest[..len].chars().count() as isize);
+                    (row, new, col, delta)
+                } else {
+                    let new = format!("{}{}{}", &line[..i], token, &line[i..]);
+                    (row, new, col, token.chars().count() as isize)
+                }
+            })
+            .collect();
+        self.replace_lines(edits)
     }
 
-    /// Set the block of textarea. By default, no block is set.
+    /// Duplicate the current line and insert the copy below it. When a selection is active, all the lines covered by
+    /// the selection are duplicated as a block. The cursor and the selection move to the copy. Duplicating is undone
+    /// at once by [`TextArea::undo`]. This method returns if the lines were duplicated or not in the textarea. Since
+    /// the textarea always has at least one line, it always returns `true`.
     /// ```
-    /// use tui_textarea::TextArea;
-    /// use tui::widgets::{Block, Borders};
+    /// use tui_textarea::{TextArea, CursorMove};
     ///
-    /// let mut textarea = TextArea::default();
-    /// let block = Block::default().borders(Borders::ALL).title("Block Title");
-    /// textarea.set_block(block);
-    /// assert!(textarea.block().is_some());
+    /// let mut textarea = TextArea::from(["foo", "bar"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Jump(0, 2));
+    /// assert!(textarea.duplicate_line());
+    /// assert_eq!(textarea.lines(), ["foo", "foo", "bar"]);
+    /// assert_eq!(textarea.cursor(), (1, 2));
     /// ```
-    pub fn set_block(&mut self, block: Block<'a>) {
-        self.block = Some(block);
-    }
+    pub fn duplicate_line(&mut self) -> bool {
+        let (first, last) = self.selected_rows();
+        let height = last - first + 1;
+        let mut chunk = Vec::with_capacity(height + 1);
+        chunk.push(String::new());
+        chunk.extend(self.lines[first..=last].iter().cloned());
 
-    /// Remove the block of textarea which was set by [`TextArea::set_block`].
-    /// ```
-    /// use tui_textarea::TextArea;
-    /// use tui::widgets::{Block, Borders};
-    ///
-    /// let mut textarea = TextArea::default();
-    /// let block = Block::default().borders(Borders::ALL).title("Block Title");
-    /// textarea.set_block(block);
-    /// textarea.remove_block();
-    /// assert!(textarea.block().is_none());
-    /// ```
-    pub fn remove_block(&mut self) {
-        self.block = None;
-    }
-
-    /// Get the block of textarea if exists.
-    pub fn block<'s>(&'s self) -> Option<&'s Block<'a>> {
-        self.block.as_ref()
+        let before = self.cursor;
+        let i = self.lines[last].len();
+        self.lines
+            .splice(last + 1..last + 1, chunk[1..].iter().cloned());
+        let selection_start = self.selection_start.map(|(r, c)| (r + height, c));
+        self.cursor = (before.0 + height, before.1);
+        self.push_history(EditKind::InsertChunk(chunk, last, i), before);
+        self.selection_start = selection_start;
+        true
     }
 
-    /// Set the length of tab character. Due to limitation of tui-rs, hard tab is not supported. Setting 0 disables tab
-    /// inputs.
+    /// Join the current line and the next line as `J` of Vim does. Whitespaces around the newline between them are
+    /// collapsed into one space. No space is inserted when either of the lines is blank. When a selection is active,
+    /// all the lines covered by the selection are joined. The cursor moves to the last join point. Joining lines is
+    /// undone at once by [`TextArea::undo`]. This method returns if the lines were joined or not. It returns `false`
+    /// on the last line.
     /// ```
-    /// use tui_textarea::{TextArea, Input, Key};
-    ///
-    /// let mut textarea = TextArea::default();
-    /// let tab_input = Input { key: Key::Tab, ctrl: false, alt: false };
+    /// use tui_textarea::TextArea;
     ///
-    /// textarea.set_tab_length(8);
-    /// textarea.input(tab_input.clone());
-    /// assert_eq!(textarea.lines(), ["        "]);
+    /// let mut textarea = TextArea::from(["foo  ", "   bar", "baz"]);
     ///
-    /// textarea.set_tab_length(2);
-    /// textarea.input(tab_input);
-    /// assert_eq!(textarea.lines(), ["          "]);
+    /// assert!(textarea.join_next_line());
+    /// assert_eq!(textarea.lines(), ["foo bar", "baz"]);
+    /// assert_eq!(textarea.cursor(), (0, 3));
     /// ```
-    pub fn set_tab_length(&mut self, len: u8) {
-        self.tab_len = len;
-    }
+    pub fn join_next_line(&mut self) -> bool {
+        let (first, last) = self.selected_rows();
+        let last = cmp::max(last, first + 1);
+        if last >= self.lines.len() {
+            return false;
+        }
 
-    /// Get how many spaces are used for representing tab character. The default value is 4.
-    pub fn tab_length(&self) -> u8 {
-        self.tab_len
+        let join = |joined: &mut String, line: &str| {
+            if !joined.is_empty() && !line.is_empty() {
+                joined.push(' ');
+            }
+            joined.push_str(line);
+        };
+        let mut joined = self.lines[first].trim_end().to_string();
+        for line in &self.lines[first + 1..last] {
+            join(&mut joined, line.trim());
+        }
+        let col = joined.chars().count();
+        join(&mut joined, self.lines[last].trim_start());
+
+        let before = self.cursor;
+        let old = self.lines.splice(first..=last, [joined.clone()]).collect();
+        self.cursor = (first, col);
+        self.push_history(EditKind::SpliceLines(first, old, vec![joined]), before);
+        true
     }
 
-    /// Set if a hard tab is used or not for indent. When `true` is set, typing a tab key inserts a hard tab instead of
-    /// spaces. By default, hard tab is disabled.
+    /// Move the current line up by swapping it with the line above. When a selection is active, all the lines covered
+    /// by the selection are moved as a block. The cursor and the selection move with the lines so the moved lines stay
+    /// in the viewport. Moving lines is undone at once by [`TextArea::undo`]. This method returns if the lines were
+    /// moved or not. Nothing happens when the first line is already at the top.
     /// ```
-    /// use tui_textarea::TextArea;
+    /// use tui_textarea::{TextArea, CursorMove};
     ///
-    /// let mut textarea = TextArea::default();
+    /// let mut textarea = TextArea::from(["foo", "bar", "baz"]);
     ///
-    /// textarea.set_hard_tab_indent(true);
-    /// textarea.insert_tab();
-    /// assert_eq!(textarea.lines(), ["\t"]);
+    /// textarea.move_cursor(CursorMove::Jump(2, 1));
+    /// assert!(textarea.move_line_up());
+    /// assert_eq!(textarea.lines(), ["foo", "baz", "bar"]);
+    /// assert_eq!(textarea.cursor(), (1, 1));
     /// ```
-    pub fn set_hard_tab_indent(&mut self, enabled: bool) {
-        self.hard_tab_indent = enabled;
+    pub fn move_line_up(&mut self) -> bool {
+        let (first, last) = self.selected_rows();
+        if first == 0 {
+            return false;
+        }
+        self.rotate_lines(first - 1, last, false);
+        true
     }
 
-    /// Get if a hard tab is used for indent or not.
+    /// Move the current line down by swapping it with the line below. When a selection is active, all the lines
+    /// covered by the selection are moved as a block. This method returns if the lines were moved or not. See
+    /// [`TextArea::move_line_up`] for more details.
     /// ```
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
+    /// let mut textarea = TextArea::from(["foo", "bar", "baz"]);
     ///
-    /// assert!(!textarea.hard_tab_indent());
-    /// textarea.set_hard_tab_indent(true);
-    /// assert!(textarea.hard_tab_indent());
+    /// assert!(textarea.move_line_down());
+    /// assert_eq!(textarea.lines(), ["bar", "foo", "baz"]);
+    /// assert_eq!(textarea.cursor(), (1, 0));
     /// ```
-    pub fn hard_tab_indent(&self) -> bool {
-        self.hard_tab_indent
+    pub fn move_line_down(&mut self) -> bool {
+        let (first, last) = self.selected_rows();
+        if last + 1 >= self.lines.len() {
+            return false;
+        }
+        self.rotate_lines(first, last + 1, true);
+        true
     }
 
-    /// Get a string for indent. It consists of spaces by default. When hard tab is enabled, it is a tab character.
-    /// ```
-    /// use tui_textarea::TextArea;
-    ///
-    /// let mut textarea = TextArea::default();
-    ///
-    /// assert_eq!(textarea.indent(), "    ");
-    /// textarea.set_tab_length(2);
-    /// assert_eq!(textarea.indent(), "  ");
-    /// textarea.set_hard_tab_indent(true);
-    /// assert_eq!(textarea.indent(), "\t");
-    /// ```
-    pub fn indent(&self) -> &'static str {
-        if self.hard_tab_indent {
-            "\t"
+    // Rotate lines in the range of rows by one line as one undo unit. The cursor and the selection move with the lines
+    // other than the one moved to the opposite end. The selection may end at the head of the line next to the moved
+    // lines. When no line follows them after moving down, it ends at the end of the last line instead
+    fn rotate_lines(&mut self, first: usize, last: usize, down: bool) {
+        let old = self.lines[first..=last].to_vec();
+        if down {
+            self.lines[first..=last].rotate_right(1);
         } else {
-            spaces(self.tab_len)
+            self.lines[first..=last].rotate_left(1);
         }
+        let replaced = old
+            .into_iter()
+            .zip(self.lines[first..=last].iter().cloned())
+            .enumerate()
+            .map(|(i, (old, new))| (first + i, old, new))
+            .collect();
+
+        let lines = &self.lines;
+        let shift = |(row, col): (usize, usize)| {
+            if !down {
+                (row - 1, col)
+            } else if row + 1 < lines.len() {
+                (row + 1, col)
+            } else {
+                (row, lines[row].chars().count())
+            }
+        };
+        let before = self.cursor;
+        let selection_start = self.selection_start.map(shift);
+        self.cursor = shift(self.cursor);
+        self.push_history(EditKind::ReplaceLines(replaced), before);
+        self.selection_start = selection_start;
     }
 
-    /// Set how many modifications are remembered for undo/redo. Setting 0 disables undo/redo.
-    pub fn set_max_histories(&mut self, max: usize) {
-        self.history = History::new(max);
+    // Range of rows covered by the active selection, or the cursor row when no selection is active. Both ends are
+    // inclusive. The last line is not covered when the selection ends at the head of the line
+    fn selected_rows(&self) -> (usize, usize) {
+        match self.selection_range() {
+            Some((start, end)) if end.0 > start.0 && end.1 == 0 => (start.0, end.0 - 1),
+            Some((start, end)) => (start.0, end.0),
+            None => (self.cursor.0, self.cursor.0),
+        }
     }
 
-    /// Get how many modifications are remembered for undo/redo. The default value is 50.
-    pub fn max_histories(&self) -> usize {
-        self.history.max_items()
+    // Replace lines as one undo unit. Each element is (row, new line, col, number of characters inserted at the col).
+    // The number is negative when characters were removed. The cursor and the selection are moved so that they stay on
+    // the same characters
+    fn replace_lines(&mut self, edits: Vec<(usize, String, usize, isize)>) -> bool {
+        if edits.is_empty() {
+            return false;
+        }
+
+        let shift = |(row, col): (usize, usize)| match edits.iter().find(|(r, ..)| *r == row) {
+            Some(&(_, _, at, delta)) if col >= at => {
+                if delta >= 0 {
+                    (row, col + delta as usize)
+                } else {
+                    (row, cmp::max(col.saturating_sub(delta.unsigned_abs()), at))
+                }
+            }
+            _ => (row, col),
+        };
+        let before = self.cursor;
+        let cursor = shift(self.cursor);
+        let selection_start = self.selection_start.map(shift);
+
+        let replaced = edits
+            .into_iter()
+            .map(|(row, new, _, _)| {
+                let old = std::mem::replace(&mut self.lines[row], new.clone());
+                (row, old, new)
+            })
+            .collect();
+        self.cursor = cursor;
+        self.push_history(EditKind::ReplaceLines(replaced), before);
+        self.selection_start = selection_start; // Keep the selection to modify the lines repeatedly
+        true
     }
 
-    /// Set the style of line at cursor. By default, the cursor line is styled with underline. To stop styling the
-    /// cursor line, set the default style.
+    /// Move the cursor to the position specified by the [`CursorMove`] parameter. For each kind of cursor moves, see
+    /// the document of [`CursorMove`].
     /// ```
-    /// use tui::style::{Style, Color};
-    /// use tui_textarea::TextArea;
-    ///
-    /// let mut textarea = TextArea::default();
+    /// use tui_textarea::{TextArea, CursorMove};
     ///
-    /// let style = Style::default().fg(Color::Red);
-    /// textarea.set_cursor_line_style(style);
-    /// assert_eq!(textarea.cursor_line_style(), style);
+    /// let mut textarea = TextArea::from(["abc", "def"]);
     ///
-    /// // Disable cursor line style
-    /// textarea.set_cursor_line_style(Style::default());
+    /// textarea.move_cursor(CursorMove::Forward);
+    /// assert_eq!(textarea.cursor(), (0, 1));
+    /// textarea.move_cursor(CursorMove::Down);
+    /// assert_eq!(textarea.cursor(), (1, 1));
     /// ```
-    pub fn set_cursor_line_style(&mut self, style: Style) {
-        self.cursor_line_style = style;
-    }
+    pub fn move_cursor(&mut self, m: CursorMove) {
+        use CursorMove::*;
 
-    /// Get the style of cursor line. By default it is styled with underline.
-    pub fn cursor_line_style(&self) -> Style {
-        self.cursor_line_style
+        // Moving vertically keeps the column where the cursor was before moving through shorter lines
+        let vertical = matches!(
+            m,
+            Up | Down | Top | Bottom | ParagraphForward | ParagraphBack | InViewport
+        );
+        let (row, col) = self.cursor;
+        let col = match self.desired_col {
+            Some((desired, at)) if vertical && at == self.cursor => desired,
+            _ => col,
+        };
+
+        if let Some(cursor) = m.next_cursor((row, col), self) {
+            // log::debug!("move cursor: {:?} -> {:?}", self.cursor, cursor);
+            self.cursor = cursor;
+        }
+        self.desired_col = vertical.then(|| (col, self.cursor));
+        self.wheel_scrolled_cursor = None;
     }
 
-    /// Set the style of line number. By setting the style with this method, line numbers are drawn in textarea, meant
-    /// that line numbers are disabled by default. If you want to show line numbers but don't want to style them, set
-    /// the default style.
+    /// Move the cursor forward to the head of the next word. Words are runs of alphanumeric characters and underscores,
+    /// or runs of punctuations. Whitespaces are skipped. When no word follows the cursor in the line, the cursor moves
+    /// to the head of the next line. This is the same as [`CursorMove::WordForward`].
     /// ```
-    /// use tui::style::{Style, Color};
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
+    /// let mut textarea = TextArea::from(["foo_bar(baz)", "qux"]);
     ///
-    /// // Show line numbers in dark gray background
-    /// let style = Style::default().bg(Color::DarkGray);
-    /// textarea.set_line_number_style(style);
-    /// assert_eq!(textarea.line_number_style(), Some(style));
+    /// textarea.move_word_forward();
+    /// assert_eq!(textarea.cursor(), (0, 7));
+    /// textarea.move_word_forward();
+    /// assert_eq!(textarea.cursor(), (0, 8));
+    /// textarea.move_word_forward();
+    /// assert_eq!(textarea.cursor(), (0, 11));
+    /// textarea.move_word_forward();
+    /// assert_eq!(textarea.cursor(), (1, 0));
     /// ```
-    pub fn set_line_number_style(&mut self, style: Style) {
-        self.line_number_style = Some(style);
+    pub fn move_word_forward(&mut self) {
+        self.move_cursor(CursorMove::WordForward);
     }
 
-    /// Remove the style of line number which was set by [`TextArea::set_line_number_style`]. After calling this
-    /// method, Line numbers will no longer be shown.
+    /// Move the cursor backward to the head of the previous word. Words are defined in the same way as
+    /// [`TextArea::move_word_forward`]. When no word precedes the cursor in the line, the cursor moves to the end of
+    /// the previous line. This is the same as [`CursorMove::WordBack`].
     /// ```
-    /// use tui::style::{Style, Color};
-    /// use tui_textarea::TextArea;
+    /// use tui_textarea::{TextArea, CursorMove};
     ///
-    /// let mut textarea = TextArea::default();
+    /// let mut textarea = TextArea::from(["qux", "foo_bar(baz)"]);
     ///
-    /// textarea.set_line_number_style(Style::default().bg(Color::DarkGray));
-    /// textarea.remove_line_number();
-    /// assert_eq!(textarea.line_number_style(), None);
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.move_word_backward();
+    /// assert_eq!(textarea.cursor(), (1, 11));
+    /// textarea.move_word_backward();
+    /// assert_eq!(textarea.cursor(), (1, 8));
+    /// textarea.move_word_backward();
+    /// assert_eq!(textarea.cursor(), (1, 7));
+    /// textarea.move_word_backward();
+    /// assert_eq!(textarea.cursor(), (1, 0));
+    /// textarea.move_word_backward();
+    /// assert_eq!(textarea.cursor(), (0, 3));
     /// ```
-    pub fn remove_line_number(&mut self) {
-        self.line_number_style = None;
-    }
-
-    /// Get the style of line number if set.
-    pub fn line_number_style(&self) -> Option<Style> {
-        self.line_number_style
+    pub fn move_word_backward(&mut self) {
+        self.move_cursor(CursorMove::WordBack);
     }
 
-    /// Set the style of cursor. By default, a cursor is rendered in the reversed color. Setting the same style as
-    /// cursor line hides a cursor.
+    /// Move the cursor to the head of the current line. When `smart` is `true`, the cursor moves to the first
+    /// non-whitespace character of the line instead, and it moves to the head of the line when it is already at the
+    /// first non-whitespace character. Repeated calls toggle the two positions like 'smart home' of editors. `Home` key
+    /// is mapped to the smart version by default.
     /// ```
-    /// use tui::style::{Style, Color};
-    /// use tui_textarea::TextArea;
+    /// use tui_textarea::{TextArea, CursorMove};
     ///
-    /// let mut textarea = TextArea::default();
+    /// let mut textarea = TextArea::from(["    abc"]);
     ///
-    /// let style = Style::default().bg(Color::Red);
-    /// textarea.set_cursor_style(style);
-    /// assert_eq!(textarea.cursor_style(), style);
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.move_cursor_line_start(false);
+    /// assert_eq!(textarea.cursor(), (0, 0));
+    ///
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.move_cursor_line_start(true);
+    /// assert_eq!(textarea.cursor(), (0, 4));
+    /// textarea.move_cursor_line_start(true);
+    /// assert_eq!(textarea.cursor(), (0, 0));
+    /// textarea.move_cursor_line_start(true);
+    /// assert_eq!(textarea.cursor(), (0, 4));
     /// ```
-    pub fn set_cursor_style(&mut self, style: Style) {
-        self.cursor_style = style;
-    }
-
-    /// Get the style of cursor.
-    pub fn cursor_style(&self) -> Style {
-        self.cursor_style
+    pub fn move_cursor_line_start(&mut self, smart: bool) {
+        let (row, col) = self.cursor;
+        let first_non_blank = self.lines[row]
+            .chars()
+            .take_while(|c| c.is_whitespace())
+            .count();
+        self.cursor.1 = if smart && col != first_non_blank {
+            first_non_blank
+        } else {
+            0
+        };
     }
 
-    /// Get slice of line texts. This method borrows the content, but not moves. Note that the returned slice will
-    /// never be empty because an empty text means a slice containing one empty line. This is correct since any text
-    /// file must end with a newline.
+    /// Move the cursor to the end of the current line. The column is character-wise so wide characters such as CJK
+    /// characters are counted as one column.
     /// ```
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
-    /// assert_eq!(textarea.lines(), [""]);
-    ///
-    /// textarea.insert_char('a');
-    /// assert_eq!(textarea.lines(), ["a"]);
-    ///
-    /// textarea.insert_newline();
-    /// assert_eq!(textarea.lines(), ["a", ""]);
+    /// let mut textarea = TextArea::from(["あいう"]);
     ///
-    /// textarea.insert_char('b');
-    /// assert_eq!(textarea.lines(), ["a", "b"]);
+    /// textarea.move_cursor_line_end();
+    /// assert_eq!(textarea.cursor(), (0, 3));
     /// ```
-    pub fn lines(&'a self) -> &'a [String] {
-        &self.lines
+    pub fn move_cursor_line_end(&mut self) {
+        let row = self.cursor.0;
+        self.cursor.1 = self.lines[row].chars().count();
     }
 
-    /// Convert [`TextArea`] instance into line texts.
+    /// Move the cursor to the head of the first line. Unlike [`CursorMove::Top`], the column is not kept. The viewport
+    /// is scrolled to show the cursor on the next render. This is mapped to `Ctrl+Home` by default.
     /// ```
-    /// use tui_textarea::TextArea;
-    ///
-    /// let mut textarea = TextArea::default();
+    /// use tui_textarea::{TextArea, CursorMove};
     ///
-    /// textarea.insert_char('a');
-    /// textarea.insert_newline();
-    /// textarea.insert_char('b');
+    /// let mut textarea = TextArea::from(["abc", "def"]);
     ///
-    /// assert_eq!(textarea.into_lines(), ["a", "b"]);
+    /// textarea.move_cursor(CursorMove::Jump(1, 2));
+    /// textarea.move_cursor_top();
+    /// assert_eq!(textarea.cursor(), (0, 0));
     /// ```
-    pub fn into_lines(self) -> Vec<String> {
-        self.lines
+    pub fn move_cursor_top(&mut self) {
+        self.cursor = (0, 0);
     }
 
-    /// Get the current cursor position. 0-base character-wise (row, col) cursor position.
+    /// Move the cursor to the end of the last line so that typed text is appended to the text. Unlike
+    /// [`CursorMove::Bottom`], the column is not kept. The viewport is scrolled to show the cursor on the next render.
+    /// This is mapped to `Ctrl+End` by default.
     /// ```
     /// use tui_textarea::TextArea;
     ///
-    /// let mut textarea = TextArea::default();
-    /// assert_eq!(textarea.cursor(), (0, 0));
-    ///
-    /// textarea.insert_char('a');
-    /// textarea.insert_newline();
-    /// textarea.insert_char('b');
+    /// let mut textarea = TextArea::from(["abc", "de"]);
     ///
-    /// assert_eq!(textarea.cursor(), (1, 1));
+    /// textarea.move_cursor_bottom();
+    /// assert_eq!(textarea.cursor(), (1, 2));
     /// ```
-    pub fn cursor(&self) -> (usize, usize) {
-        self.cursor
+    pub fn move_cursor_bottom(&mut self) {
+        let row = self.lines.len() - 1;
+        self.cursor = (row, self.lines[row].chars().count());
     }
 
-    /// Set text alignment. When [`Alignment::Center`] or [`Alignment::Right`] is set, line number is automatically
-    /// disabled because those alignments don't work well with line numbers.
+    /// Move the cursor to the (row, col) position and return the actual cursor position. Unlike
+    /// [`CursorMove::Jump`], this method accepts `usize` positions. When the position points outside the text, it is
+    /// clamped to fit within the text; row is clamped to the last line and col is clamped to the end of the line. Note
+    /// that row and col are 0-based and col is character-wise. The viewport is scrolled to show the cursor on the next
+    /// render.
     /// ```
     /// use tui_textarea::TextArea;
-    /// use tui::layout::Alignment;
     ///
-    /// let mut textarea = TextArea::default();
+    /// let mut textarea = TextArea::from(["abc", "de"]);
     ///
-    /// textarea.set_alignment(Alignment::Center);
-    /// assert_eq!(textarea.alignment(), Alignment::Center);
+    /// assert_eq!(textarea.move_cursor_to(0, 2), (0, 2));
+    /// assert_eq!(textarea.cursor(), (0, 2));
+    ///
+    /// // Out-of-range positions are clamped
+    /// assert_eq!(textarea.move_cursor_to(1, 10), (1, 2));
+    /// assert_eq!(textarea.move_cursor_to(100, 1), (1, 1));
     /// ```
-    pub fn set_alignment(&mut self, alignment: Alignment) {
-        if let Alignment::Center | Alignment::Right = alignment {
-            self.line_number_style = None;
-        }
-        self.alignment = alignment;
+    pub fn move_cursor_to(&mut self, row: usize, col: usize) -> (usize, usize) {
+        let row = cmp::min(row, self.lines.len() - 1);
+        let col = cmp::min(col, self.lines[row].chars().count());
+        self.cursor = (row, col);
+        self.cursor
     }
 
-    /// Get current text alignment. The default alignment is [`Alignment::Left`].
+    /// Move the cursor to the line. The line is a 0-base row index and it is clamped to the last line. The cursor is
+    /// placed at the head of the line, or at the first non-whitespace character when `first_non_blank` is `true`. The
+    /// viewport is scrolled so that the line is displayed at the middle of the textarea on the next render. This method
+    /// returns the resolved row index. It is useful to jump to a line reported by external tools like linters.
     /// ```
     /// use tui_textarea::TextArea;
-    /// use tui::layout::Alignment;
     ///
-    /// let mut textarea = TextArea::default();
+    /// let mut textarea = TextArea::from(["foo", "    bar", "baz"]);
     ///
-    /// assert_eq!(textarea.alignment(), Alignment::Left);
+    /// assert_eq!(textarea.move_cursor_to_line(1, true), 1);
+    /// assert_eq!(textarea.cursor(), (1, 4));
+    /// assert_eq!(textarea.move_cursor_to_line(1, false), 1);
+    /// assert_eq!(textarea.cursor(), (1, 0));
+    ///
+    /// // The line is clamped to the last line
+    /// assert_eq!(textarea.move_cursor_to_line(100, false), 2);
+    /// assert_eq!(textarea.cursor(), (2, 0));
     /// ```
-    pub fn alignment(&self) -> Alignment {
-        self.alignment
+    pub fn move_cursor_to_line(&mut self, line: usize, first_non_blank: bool) -> usize {
+        let row = cmp::min(line, self.lines.len() - 1);
+        let col = if first_non_blank {
+            self.lines[row]
+                .chars()
+                .take_while(|c| c.is_whitespace())
+                .count()
+        } else {
+            0
+        };
+        self.cursor = (row, col);
+        self.viewport.center_row(row as u32);
+        row
     }
 
-    /// Check if the textarea has a empty content.
+    /// Undo the last modification. This method returns if the undo modified text contents or not in the textarea.
+    /// Consecutive insertions of characters typed in a short period are grouped and undone at once. Pasted text
+    /// is not grouped with them.
     /// ```
-    /// use tui_textarea::TextArea;
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["abc def"]);
+    ///
+    /// textarea.delete_next_word();
+    /// assert_eq!(textarea.lines(), [" def"]);
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["abc def"]);
+    ///
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.insert_char('g');
+    /// textarea.insert_char('h');
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["abc def"]);
+    /// ```
+    pub fn undo(&mut self) -> bool {
+        if let Some(edit) = self.history.undo(&mut self.lines) {
+            self.cursor = edit.cursor_before();
+            let row = edit.first_row();
+            let rows = edit.rows_on_undo();
+            self.selection_start = None;
+            self.invalidate_caches(row);
+            self.shift_rows(rows);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Redo the last undo change. This method returns if the redo modified text contents or not in the textarea.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["abc def"]);
+    ///
+    /// textarea.delete_next_word();
+    /// assert_eq!(textarea.lines(), [" def"]);
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["abc def"]);
+    /// textarea.redo();
+    /// assert_eq!(textarea.lines(), [" def"]);
+    /// ```
+    pub fn redo(&mut self) -> bool {
+        if let Some(edit) = self.history.redo(&mut self.lines) {
+            self.cursor = edit.cursor_after();
+            let row = edit.first_row();
+            let rows = edit.rows_on_redo();
+            self.selection_start = None;
+            self.invalidate_caches(row);
+            self.shift_rows(rows);
+            true
+        } else {
+            false
+        }
+    }
+
+    // Position of the bracket to highlight as the match of the bracket at cursor. This is computed once per render.
+    // Only the rendered rows are searched since a bracket outside them is not highlighted
+    pub(crate) fn bracket_to_highlight(&self, rows: Range<usize>) -> Option<(usize, usize)> {
+        self.match_bracket_style?;
+        find_matching_bracket_within(&self.lines, self.cursor, rows)
+    }
+
+    pub(crate) fn line_highlighter<'b>(
+        &'b self,
+        line: &'b str,
+        row: usize,
+        lnum_len: u8,
+        bracket: Option<(usize, usize)>,
+        selection: Option<((usize, usize), (usize, usize))>,
+    ) -> LineHighlighter<'b> {
+        if self.placeholder_shown() {
+            let mut hl = LineHighlighter::new(&self.placeholder, self.cursor_style, self.tab_len);
+            self.line_number(&mut hl, row, lnum_len);
+            hl.cursor_line(0, self.placeholder_style);
+            return hl;
+        }
+
+        let mut hl = LineHighlighter::new(line, self.cursor_style, self.tab_len);
+
+        if let Some(mask) = self.mask {
+            hl.mask(mask);
+        } else if self.show_whitespace {
+            hl.show_whitespace();
+        }
+
+        self.line_number(&mut hl, row, lnum_len);
+
+        if row == self.cursor.0 {
+            hl.cursor_line(self.cursor.1, self.cursor_line_style);
+        }
+
+        if let (Some((r, c)), Some(style)) = (bracket, self.match_bracket_style) {
+            if r == row {
+                hl.bracket(c, style);
+            }
+        }
+
+        if let Some((start, end)) = selection {
+            if self.selection_mode == SelectionMode::Block {
+                let (left, right) = (cmp::min(start.1, end.1), cmp::max(start.1, end.1));
+                hl.selection(row, (row, left), (row, right), self.selection_style);
+            } else {
+                hl.selection(row, start, end, self.selection_style);
+            }
+        }
+
+        #[cfg(feature = "search")]
+        if let Some(matches) = self.search.matches(line) {
+            let current = if row == self.cursor.0 {
+                let i = line
+                    .char_indices()
+                    .nth(self.cursor.1)
+                    .map(|(i, _)| i)
+                    .unwrap_or(line.len());
+                Some((i, self.search.current_style))
+            } else {
+                None
+            };
+            hl.search(matches, self.search.style, current);
+        }
+
+        if let Some(ranges) = self.highlights.get(&row) {
+            hl.extra(ranges);
+        }
+
+        hl
+    }
+
+    fn line_number(&self, hl: &mut LineHighlighter<'_>, row: usize, lnum_len: u8) {
+        if !self.line_signs.is_empty() {
+            hl.sign(self.line_signs.get(&row).copied());
+        }
+        if let Some(style) = self.line_number_style {
+            let style = match self.cursor_line_number_style {
+                Some(s) if row == self.cursor.0 => s,
+                _ => style,
+            };
+            if self.line_number_relative {
+                hl.relative_line_number(row, self.cursor.0, lnum_len, style);
+            } else {
+                hl.line_number(row, lnum_len, style);
+            }
+        }
+    }
+
+    // Placeholder is shown instead of the text when the textarea is empty
+    pub(crate) fn placeholder_shown(&self) -> bool {
+        !self.placeholder.is_empty() && self.is_empty()
+    }
+
+    pub(crate) fn line_spans<'b>(
+        &'b self,
+        line: &'b str,
+        row: usize,
+        lnum_len: u8,
+        bracket: Option<(usize, usize)>,
+        selection: Option<((usize, usize), (usize, usize))>,
+    ) -> Line<'b> {
+        self.line_highlighter(line, row, lnum_len, bracket, selection)
+            .into_spans()
+    }
+
+    // Push the rendered spans of the line to the text. The line is split into multiple rows when lines are wrapped. The
+    // background of the cursor line is extended to the width
+    pub(crate) fn push_line<'b>(
+        &self,
+        text: &mut Vec<Line<'b>>,
+        spans: Line<'b>,
+        line: &str,
+        row: usize,
+        wrapper: Option<&Wrapper>,
+        width: usize,
+    ) {
+        let start = text.len();
+        if let Some(wrapper) = wrapper {
+            let gutter = usize::from(!self.line_signs.is_empty())
+                + usize::from(self.line_number_style.is_some());
+            let cursor_at_end = row == self.cursor.0 && line.chars().count() <= self.cursor.1;
+            text.extend(wrapper.wrap_line(spans, line, gutter, cursor_at_end));
+        } else {
+            text.push(spans);
+        }
+
+        if row != self.cursor.0 || self.alignment != Alignment::Left {
+            return;
+        }
+        if let Some(bg) = self.cursor_line_style.bg {
+            for line in &mut text[start..] {
+                let w = line.width();
+                if w < width {
+                    let fill = Span::styled(" ".repeat(width - w), Style::default().bg(bg));
+                    line.spans.push(fill);
+                }
+            }
+        }
+    }
+
+    /// Build a tui-rs widget to render the current state of the textarea. The widget instance returned from this
+    /// method can be rendered with [`tui::terminal::Frame::render_widget`].
+    ///
+    /// Rendering only processes the lines in the viewport so its cost is proportional to the height of the viewport,
+    /// not to the number of lines. The number of lines is known without scanning the text, and the matching bracket
+    /// is only searched in the rendered lines. [`TextArea::syntax_widget`] is an exception since syntax highlighting
+    /// needs to parse the text from the first line. The parse result is cached until the text is modified.
+    /// ```no_run
+    /// use tui::backend::CrosstermBackend;
+    /// use tui::layout::{Constraint, Direction, Layout};
+    /// use tui::Terminal;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let layout = Layout::default()
+    ///     .direction(Direction::Vertical)
+    ///     .constraints([Constraint::Min(1)].as_ref());
+    /// let backend = CrosstermBackend::new(std::io::stdout());
+    /// let mut term = Terminal::new(backend).unwrap();
+    ///
+    /// loop {
+    ///     term.draw(|f| {
+    ///         let chunks = layout.split(f.size());
+    ///         let widget = textarea.widget();
+    ///         f.render_widget(widget, chunks[0]);
+    ///     }).unwrap();
+    ///
+    ///     // ...
+    /// }
+    /// ```
+    pub fn widget(&'a self) -> impl Widget + 'a {
+        Renderer::new(self)
+    }
+
+    /// Build a widget to render the current state of the textarea with syntax highlighting. `theme` is a name of
+    /// theme such as `"base16-ocean.dark"`. See [`SyntaxRenderer`] to know how to configure the widget.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::from(["fn main() {}"]);
+    /// let widget = textarea.syntax_widget("base16-ocean.dark");
+    /// ```
+    pub fn syntax_widget(&'a self, theme: &'a str) -> SyntaxRenderer<'a> {
+        SyntaxRenderer::new(self, theme)
+    }
+
+    /// Set the style of textarea. By default, textarea is not styled. This style is the base of all other styles.
+    ///
+    /// When multiple styles are applied to the same text, a style replaces styles listed after it:
+    ///
+    /// 1. Cursor set by [`TextArea::set_cursor_style`]
+    /// 2. Highlights added by [`TextArea::add_highlight`]. They are patched on top of the style under them
+    /// 3. Matching bracket set by [`TextArea::set_match_bracket_style`]
+    /// 4. Selection set by [`TextArea::set_selection_style`]
+    /// 5. Current search match and other search matches set by `set_search_current_style` and `set_search_style`
+    /// 6. Cursor line set by [`TextArea::set_cursor_line_style`]
+    ///
+    /// The chosen style is patched on top of syntax highlighting by [`TextArea::syntax_widget`] and this style. Line
+    /// numbers are styled by [`TextArea::set_line_number_style`] and [`TextArea::set_cursor_line_number_style`]
+    /// separately. The ruler set by [`TextArea::set_ruler_column`] is patched on top of all of them.
+    /// ```
+    /// use tui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let style = Style::default().fg(Color::Red);
+    /// textarea.set_style(style);
+    /// assert_eq!(textarea.style(), style);
+    /// ```
+    pub fn set_style(&mut self, style: Style) {
+        self.style = style;
+    }
+
+    /// Get the current style of textarea.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Set the block of textarea. By default, no block is set.
+    /// ```
+    /// use tui_textarea::TextArea;
+    /// use tui::widgets::{Block, Borders};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let block = Block::default().borders(Borders::ALL).title("Block Title");
+    /// textarea.set_block(block);
+    /// assert!(textarea.block().is_some());
+    /// ```
+    pub fn set_block(&mut self, block: Block<'a>) {
+        self.block = Some(block);
+    }
+
+    /// Remove the block of textarea which was set by [`TextArea::set_block`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    /// use tui::widgets::{Block, Borders};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let block = Block::default().borders(Borders::ALL).title("Block Title");
+    /// textarea.set_block(block);
+    /// textarea.remove_block();
+    /// assert!(textarea.block().is_none());
+    /// ```
+    pub fn remove_block(&mut self) {
+        self.block = None;
+    }
+
+    /// Get the block of textarea if exists.
+    pub fn block<'s>(&'s self) -> Option<&'s Block<'a>> {
+        self.block.as_ref()
+    }
+
+    /// Get the inner area where the text is rendered when the textarea is rendered in the area. The margin of the block
+    /// set by [`TextArea::set_block`] is excluded. This is useful to calculate the layout before rendering.
+    /// ```
+    /// use tui::layout::Rect;
+    /// use tui::widgets::{Block, Borders};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let area = Rect { x: 0, y: 0, width: 10, height: 5 };
+    /// assert_eq!(textarea.inner_size(area), area);
+    ///
+    /// textarea.set_block(Block::default().borders(Borders::ALL));
+    /// assert_eq!(textarea.inner_size(area), Rect { x: 1, y: 1, width: 8, height: 3 });
+    /// ```
+    pub fn inner_size(&self, area: Rect) -> Rect {
+        match &self.block {
+            Some(b) => b.inner(area),
+            None => area,
+        }
+    }
+
+    /// Set the length of tab character. It is the width of tab stops used for rendering hard tabs and the number of
+    /// spaces inserted by a tab key. The length must be at least 1 so setting 0 is clamped to 1.
+    /// ```
+    /// use tui_textarea::{TextArea, Input, Key};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let tab_input = Input { key: Key::Tab, ctrl: false, alt: false, shift: false };
+    ///
+    /// textarea.set_tab_length(8);
+    /// textarea.input(tab_input.clone());
+    /// assert_eq!(textarea.lines(), ["        "]);
+    ///
+    /// textarea.set_tab_length(2);
+    /// textarea.input(tab_input);
+    /// assert_eq!(textarea.lines(), ["          "]);
+    ///
+    /// textarea.set_tab_length(0);
+    /// assert_eq!(textarea.tab_length(), 1);
+    /// ```
+    pub fn set_tab_length(&mut self, len: u8) {
+        self.tab_len = cmp::max(len, 1);
+        self.max_width.invalidate();
+    }
+
+    /// Get how many spaces are used for representing tab character. The default value is 4.
+    pub fn tab_length(&self) -> u8 {
+        self.tab_len
+    }
+
+    /// Set if a hard tab is used or not for indent. When `true` is set, typing a tab key inserts a hard tab instead of
+    /// spaces. By default, hard tab is disabled.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_hard_tab_indent(true);
+    /// textarea.insert_tab();
+    /// assert_eq!(textarea.lines(), ["\t"]);
+    /// ```
+    pub fn set_hard_tab_indent(&mut self, enabled: bool) {
+        self.hard_tab_indent = enabled;
+    }
+
+    /// Get if a hard tab is used for indent or not.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// assert!(!textarea.hard_tab_indent());
+    /// textarea.set_hard_tab_indent(true);
+    /// assert!(textarea.hard_tab_indent());
+    /// ```
+    pub fn hard_tab_indent(&self) -> bool {
+        self.hard_tab_indent
+    }
+
+    /// Get a string for indent. It consists of spaces by default. When hard tab is enabled, it is a tab character.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// assert_eq!(textarea.indent(), "    ");
+    /// textarea.set_tab_length(2);
+    /// assert_eq!(textarea.indent(), "  ");
+    /// textarea.set_hard_tab_indent(true);
+    /// assert_eq!(textarea.indent(), "\t");
+    /// ```
+    pub fn indent(&self) -> &'static str {
+        if self.hard_tab_indent {
+            "\t"
+        } else {
+            spaces(self.tab_len)
+        }
+    }
+
+    /// Set if auto-indent is enabled or not. When `true` is set, inserting a newline copies the leading whitespaces of
+    /// the current line onto the new line and the cursor is placed after them. The newline and the indent are undone at
+    /// once. By default, auto-indent is disabled.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["    foo"]);
+    ///
+    /// textarea.set_auto_indent(true);
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.insert_newline();
+    /// assert_eq!(textarea.lines(), ["    foo", "    "]);
+    /// assert_eq!(textarea.cursor(), (1, 4));
+    /// ```
+    pub fn set_auto_indent(&mut self, enabled: bool) {
+        self.auto_indent = enabled;
+    }
+
+    /// Get if auto-indent is enabled or not. See [`TextArea::set_auto_indent`] for more details.
+    pub fn auto_indent(&self) -> bool {
+        self.auto_indent
+    }
+
+    /// Set if auto-pairs is enabled or not. When `true` is set, typing an opening bracket `(`, `[`, `{` or a quote `"`,
+    /// `'` with [`TextArea::input`] also inserts the closing one and the cursor is placed between them. The pair is not
+    /// inserted when the cursor is followed by a word, or when a quote is typed right after a word. Typing the closing
+    /// character when it is next to the cursor moves the cursor over it instead of inserting a duplicate, and Backspace
+    /// between an empty pair deletes both characters. When some text is selected, typing an opening character wraps
+    /// the selected text with the pair instead and the selection is kept on the text. By default, auto-pairs is
+    /// disabled.
+    /// ```
+    /// use tui_textarea::{TextArea, Input, Key};
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_auto_pairs(true);
+    /// textarea.input(Input { key: Key::Char('('), ..Default::default() });
+    /// assert_eq!(textarea.lines(), ["()"]);
+    /// assert_eq!(textarea.cursor(), (0, 1));
+    ///
+    /// textarea.input(Input { key: Key::Char(')'), ..Default::default() });
+    /// assert_eq!(textarea.lines(), ["()"]);
+    /// assert_eq!(textarea.cursor(), (0, 2));
+    /// ```
+    pub fn set_auto_pairs(&mut self, enabled: bool) {
+        self.auto_pairs = enabled;
+    }
+
+    /// Get if auto-pairs is enabled or not. See [`TextArea::set_auto_pairs`] for more details.
+    pub fn auto_pairs(&self) -> bool {
+        self.auto_pairs
+    }
+
+    /// Set how many modifications are remembered for undo/redo. Setting 0 disables undo/redo.
+    pub fn set_max_histories(&mut self, max: usize) {
+        self.history = History::new(max);
+    }
+
+    /// Get how many modifications are remembered for undo/redo. The default value is 50.
+    pub fn max_histories(&self) -> usize {
+        self.history.max_items()
+    }
+
+    /// Set how many lines are scrolled by one mouse wheel event. Scrolling by mouse wheel doesn't move the cursor.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.scroll_lines_per_wheel(), 3);
+    ///
+    /// textarea.set_scroll_lines_per_wheel(1);
+    /// assert_eq!(textarea.scroll_lines_per_wheel(), 1);
+    /// ```
+    pub fn set_scroll_lines_per_wheel(&mut self, lines: u16) {
+        self.scroll_lines_per_wheel = lines;
+    }
+
+    /// Get how many lines are scrolled by one mouse wheel event. The default value is 3.
+    pub fn scroll_lines_per_wheel(&self) -> u16 {
+        self.scroll_lines_per_wheel
+    }
+
+    /// Set the style of line at cursor. By default, the cursor line is styled with underline. To stop styling the
+    /// cursor line, set the default style. When the style has background color, the background is extended to the
+    /// right edge of the textarea even past the end of the line.
+    /// ```
+    /// use tui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let style = Style::default().fg(Color::Red);
+    /// textarea.set_cursor_line_style(style);
+    /// assert_eq!(textarea.cursor_line_style(), style);
+    ///
+    /// // Disable cursor line style
+    /// textarea.set_cursor_line_style(Style::default());
+    /// ```
+    pub fn set_cursor_line_style(&mut self, style: Style) {
+        self.cursor_line_style = style;
+    }
+
+    /// Get the style of cursor line. By default it is styled with underline.
+    pub fn cursor_line_style(&self) -> Style {
+        self.cursor_line_style
+    }
+
+    /// Set the style of line number. By setting the style with this method, line numbers are drawn in textarea, meant
+    /// that line numbers are disabled by default. If you want to show line numbers but don't want to style them, set
+    /// the default style.
+    /// ```
+    /// use tui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// // Show line numbers in dark gray background
+    /// let style = Style::default().bg(Color::DarkGray);
+    /// textarea.set_line_number_style(style);
+    /// assert_eq!(textarea.line_number_style(), Some(style));
+    /// ```
+    pub fn set_line_number_style(&mut self, style: Style) {
+        self.line_number_style = Some(style);
+    }
+
+    /// Remove the style of line number which was set by [`TextArea::set_line_number_style`]. After calling this
+    /// method, Line numbers will no longer be shown.
+    /// ```
+    /// use tui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_line_number_style(Style::default().bg(Color::DarkGray));
+    /// textarea.remove_line_number();
+    /// assert_eq!(textarea.line_number_style(), None);
+    /// ```
+    pub fn remove_line_number(&mut self) {
+        self.line_number_style = None;
+    }
+
+    /// Get the style of line number if set.
+    pub fn line_number_style(&self) -> Option<Style> {
+        self.line_number_style
+    }
+
+    /// Set the style of the line number of the cursor line. It is used instead of the style set by
+    /// [`TextArea::set_line_number_style`] for the cursor line. When it is not set, the cursor line number is rendered
+    /// in the same style as other line numbers. Line numbers are shown only when their style is set.
+    /// ```
+    /// use tui::style::{Style, Modifier};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.cursor_line_number_style(), None);
+    ///
+    /// let style = Style::default().add_modifier(Modifier::BOLD);
+    /// textarea.set_line_number_style(Style::default());
+    /// textarea.set_cursor_line_number_style(style);
+    /// assert_eq!(textarea.cursor_line_number_style(), Some(style));
+    /// ```
+    pub fn set_cursor_line_number_style(&mut self, style: Style) {
+        self.cursor_line_number_style = Some(style);
+    }
+
+    /// Remove the style of the cursor line number which was set by [`TextArea::set_cursor_line_number_style`]. After
+    /// calling this method, the cursor line number is rendered in the same style as other line numbers.
+    /// ```
+    /// use tui::style::{Style, Modifier};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_cursor_line_number_style(Style::default().add_modifier(Modifier::BOLD));
+    /// textarea.remove_cursor_line_number_style();
+    /// assert_eq!(textarea.cursor_line_number_style(), None);
+    /// ```
+    pub fn remove_cursor_line_number_style(&mut self) {
+        self.cursor_line_number_style = None;
+    }
+
+    /// Get the style of the cursor line number if set.
+    pub fn cursor_line_number_style(&self) -> Option<Style> {
+        self.cursor_line_number_style
+    }
+
+    /// Show line numbers relative to the cursor line, like `relativenumber` option of Vim. The cursor line shows its
+    /// absolute line number aligned to left and other lines show their distances from the cursor line. Line numbers
+    /// are shown only when the style is set by [`TextArea::set_line_number_style`]. It is disabled by default.
+    /// ```
+    /// use tui::style::Style;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert!(!textarea.line_number_relative());
+    ///
+    /// textarea.set_line_number_style(Style::default());
+    /// textarea.set_line_number_relative(true);
+    /// assert!(textarea.line_number_relative());
+    /// ```
+    pub fn set_line_number_relative(&mut self, relative: bool) {
+        self.line_number_relative = relative;
+    }
+
+    /// Get if line numbers are relative to the cursor line.
+    pub fn line_number_relative(&self) -> bool {
+        self.line_number_relative
+    }
+
+    /// Set the sign shown in the gutter at the head of the line at the row, such as a marker of diagnostics or changes.
+    /// The sign is a character rendered in the style. Passing `None` removes the sign of the line. While any sign is
+    /// set, a sign column of two cells is reserved before line numbers so the text does not shift as signs are added or
+    /// removed. The sign should be a character whose display width is 1.
+    ///
+    /// Signs scroll with the lines. When lines are inserted or deleted by editing the text, signs move along with their
+    /// lines and signs of the deleted lines are removed. [`TextArea::set_text`] does not move signs.
+    /// ```
+    /// use tui::style::{Color, Style};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["foo", "bar"]);
+    /// let sign = ('●', Style::default().fg(Color::Red));
+    ///
+    /// textarea.set_line_sign(1, Some(sign));
+    /// assert_eq!(textarea.line_sign(1), Some(sign));
+    /// assert_eq!(textarea.line_sign(0), None);
+    ///
+    /// textarea.set_line_sign(1, None);
+    /// assert_eq!(textarea.line_sign(1), None);
+    /// ```
+    pub fn set_line_sign(&mut self, row: usize, sign: Option<(char, Style)>) {
+        if let Some(sign) = sign {
+            self.line_signs.insert(row, sign);
+        } else {
+            self.line_signs.remove(&row);
+        }
+    }
+
+    /// Get the sign of the line at the row set by [`TextArea::set_line_sign`].
+    pub fn line_sign(&self, row: usize) -> Option<(char, Style)> {
+        self.line_signs.get(&row).copied()
+    }
+
+    /// Remove all signs set by [`TextArea::set_line_sign`]. The sign column is no longer reserved.
+    /// ```
+    /// use tui::style::Style;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["foo", "bar"]);
+    /// textarea.set_line_sign(0, Some(('+', Style::default())));
+    /// textarea.set_line_sign(1, Some(('-', Style::default())));
+    ///
+    /// textarea.clear_line_signs();
+    /// assert_eq!(textarea.line_sign(0), None);
+    /// assert_eq!(textarea.line_sign(1), None);
+    /// ```
+    pub fn clear_line_signs(&mut self) {
+        self.line_signs.clear();
+    }
+
+    /// Highlight the range of character-wise columns in the line at the row with the style, such as underlining an
+    /// error reported by diagnostics. Where highlights overlap, their styles are layered in the order of addition. The
+    /// highlight is patched on top of other styles such as the selection and search matches, also where it overlaps
+    /// them partially, except for the cursor. See [`TextArea::set_style`] for details.
+    ///
+    /// Like [`TextArea::set_line_sign`], highlights move along with their lines when lines are inserted or deleted, and
+    /// highlights of the deleted lines are removed. Columns are not adjusted on editing text within a line.
+    /// ```
+    /// use tui::style::{Color, Modifier, Style};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["let x = y;"]);
+    ///
+    /// // Underline the undefined variable `y`
+    /// let style = Style::default().fg(Color::Red).add_modifier(Modifier::UNDERLINED);
+    /// textarea.add_highlight(0, 8..9, style);
+    ///
+    /// textarea.clear_highlights();
+    /// ```
+    pub fn add_highlight(&mut self, row: usize, cols: Range<usize>, style: Style) {
+        if cols.start < cols.end {
+            self.highlights.entry(row).or_default().push((cols, style));
+        }
+    }
+
+    /// Remove all highlights added by [`TextArea::add_highlight`].
+    pub fn clear_highlights(&mut self) {
+        self.highlights.clear();
+    }
+
+    /// Show whitespaces as visible glyphs. When enabled, tabs are rendered as `→` padded to the tab length and
+    /// trailing spaces are rendered as `·`, both in dim style. This is useful to find out mixed indentation or
+    /// unnecessary trailing spaces. Text contents are not modified. It is disabled by default.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert!(!textarea.show_whitespace());
+    ///
+    /// textarea.set_show_whitespace(true);
+    /// assert!(textarea.show_whitespace());
+    /// ```
+    pub fn set_show_whitespace(&mut self, show: bool) {
+        self.show_whitespace = show;
+    }
+
+    /// Get if whitespaces are shown as visible glyphs.
+    pub fn show_whitespace(&self) -> bool {
+        self.show_whitespace
+    }
+
+    /// Set the placeholder text. The placeholder is shown instead of the text while the textarea is empty. It is useful
+    /// to show a hint of what should be input. The placeholder is not a part of the text so [`TextArea::lines`] doesn't
+    /// return it. By default, no placeholder is shown.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_placeholder_text("Type your message...");
+    /// assert_eq!(textarea.placeholder_text(), "Type your message...");
+    /// assert_eq!(textarea.lines(), [""]);
+    /// ```
+    pub fn set_placeholder_text(&mut self, placeholder: impl Into<String>) {
+        self.placeholder = placeholder.into();
+    }
+
+    /// Get the placeholder text. An empty string is returned when no placeholder is set.
+    pub fn placeholder_text(&self) -> &'_ str {
+        self.placeholder.as_str()
+    }
+
+    /// Set the style of the placeholder text. By default, the placeholder is rendered in dark gray.
+    /// ```
+    /// use tui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let style = Style::default().fg(Color::Blue);
+    /// textarea.set_placeholder_style(style);
+    /// assert_eq!(textarea.placeholder_style(), style);
+    /// ```
+    pub fn set_placeholder_style(&mut self, style: Style) {
+        self.placeholder_style = style;
+    }
+
+    /// Get the style of the placeholder text.
+    pub fn placeholder_style(&self) -> Style {
+        self.placeholder_style
+    }
+
+    /// Set the character to mask the text. When it is set, each character in the text is rendered as the mask
+    /// character. This is useful for password input. Only rendering is affected so [`TextArea::lines`] and
+    /// [`TextArea::copy`] return the actual text. Setting `None` stops masking. By default, the text is not masked.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["hello"]);
+    ///
+    /// textarea.set_mask_char(Some('\u{2022}')); // '•'
+    /// assert_eq!(textarea.mask_char(), Some('\u{2022}'));
+    /// assert_eq!(textarea.lines(), ["hello"]);
+    ///
+    /// textarea.set_mask_char(None);
+    /// assert_eq!(textarea.mask_char(), None);
+    /// ```
+    pub fn set_mask_char(&mut self, mask: Option<char>) {
+        self.mask = mask;
+        self.max_width.invalidate();
+    }
+
+    /// Get the character to mask the text if set.
+    pub fn mask_char(&self) -> Option<char> {
+        self.mask
+    }
+
+    /// Set if the textarea is read-only. While it is read-only, [`TextArea::input`] and
+    /// [`TextArea::input_without_shortcuts`] ignore inputs which modify the text such as typing characters, deleting
+    /// text, pasting, undo and redo. Moving the cursor, scrolling and searching still work. Methods to edit the text
+    /// programmatically like [`TextArea::insert_str`] are not affected. By default, the textarea is not read-only.
+    /// ```
+    /// use tui_textarea::{TextArea, Input, Key};
+    ///
+    /// let mut textarea = TextArea::from(["hello"]);
+    ///
+    /// textarea.set_read_only(true);
+    /// assert!(textarea.is_read_only());
+    ///
+    /// let modified = textarea.input(Input { key: Key::Char('a'), ctrl: false, alt: false, shift: false });
+    /// assert!(!modified);
+    /// assert_eq!(textarea.lines(), ["hello"]);
+    ///
+    /// // Programmatic edit is still possible
+    /// textarea.insert_str("world ");
+    /// assert_eq!(textarea.lines(), ["world hello"]);
+    /// ```
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
+    /// Get if the textarea is read-only.
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Set the maximum number of characters in the textarea. Newlines are not counted. When the limit is reached,
+    /// [`TextArea::input`] and [`TextArea::input_without_shortcuts`] ignore inputs which insert characters and
+    /// [`TextArea::paste`] inserts only the characters fitting in the limit. Newlines are not counted so Enter still
+    /// inserts a newline, without the auto-indent when it doesn't fit. [`TextArea::insert_str`] also inserts only the
+    /// characters fitting in the limit. Other methods to edit the text programmatically like
+    /// [`TextArea::insert_char`] are not limited. Setting `None` removes the limit. By default, there is no limit.
+    /// ```
+    /// use tui_textarea::{TextArea, Input, Key};
+    ///
+    /// let mut textarea = TextArea::from(["abc"]);
+    /// textarea.move_cursor(tui_textarea::CursorMove::End);
+    ///
+    /// textarea.set_max_chars(Some(4));
+    /// assert_eq!(textarea.max_chars(), Some(4));
+    ///
+    /// assert!(textarea.input(Input { key: Key::Char('d'), ctrl: false, alt: false, shift: false }));
+    /// assert!(!textarea.input(Input { key: Key::Char('e'), ctrl: false, alt: false, shift: false }));
+    /// assert_eq!(textarea.lines(), ["abcd"]);
+    /// assert_eq!(textarea.char_count(), 4);
+    /// ```
+    pub fn set_max_chars(&mut self, max: Option<usize>) {
+        self.max_chars = max;
+    }
+
+    /// Get the maximum number of characters in the textarea if set.
+    pub fn max_chars(&self) -> Option<usize> {
+        self.max_chars
+    }
+
+    /// Set the maximum number of lines in the textarea. When the limit is reached, [`TextArea::input`] and
+    /// [`TextArea::input_without_shortcuts`] ignore inputs which insert newlines and [`TextArea::paste`] and
+    /// [`TextArea::insert_str`] insert only the lines fitting in the limit. Other methods to edit the text
+    /// programmatically like [`TextArea::insert_newline`] are not limited. Setting `None` removes the limit. By default, there is no limit.
+    /// ```
+    /// use tui_textarea::{TextArea, Input, Key};
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_max_lines(Some(2));
+    /// assert_eq!(textarea.max_lines(), Some(2));
+    ///
+    /// assert!(textarea.input(Input { key: Key::Enter, ctrl: false, alt: false, shift: false }));
+    /// assert!(!textarea.input(Input { key: Key::Enter, ctrl: false, alt: false, shift: false }));
+    /// assert_eq!(textarea.lines(), ["", ""]);
+    /// ```
+    pub fn set_max_lines(&mut self, max: Option<usize>) {
+        self.max_lines = max;
+    }
+
+    /// Get the maximum number of lines in the textarea if set.
+    pub fn max_lines(&self) -> Option<usize> {
+        self.max_lines
+    }
+
+    /// Get the number of characters in the textarea. Newlines are not counted. This is useful to show how many
+    /// characters can be input with [`TextArea::set_max_chars`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::from(["hello", "world"]);
+    /// assert_eq!(textarea.char_count(), 10);
+    /// ```
+    pub fn char_count(&self) -> usize {
+        self.lines.iter().map(|l| l.chars().count()).sum()
+    }
+
+    /// Get the width of the line at the row rendered on terminal. Tabs are expanded to the next tab stop and wide
+    /// characters such as CJK occupy two columns. When a mask character is set, the width of the masked line is
+    /// returned. Line numbers are not included. This method returns 0 when the row is out of range.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::from(["a\tb", "日本語"]);
+    /// assert_eq!(textarea.line_display_width(0), 5);
+    /// assert_eq!(textarea.line_display_width(1), 6);
+    /// assert_eq!(textarea.line_display_width(2), 0);
+    /// ```
+    pub fn line_display_width(&self, row: usize) -> usize {
+        match self.lines.get(row) {
+            Some(line) => self.screen_col(row, line.chars().count()) - self.gutter_width(),
+            None => 0,
+        }
+    }
+
+    // Truncate the lines to be inserted so that they fit in the maximum numbers of characters and lines. Lines after
+    // the line truncated by the character limit are dropped
+    pub(crate) fn fit_in_limits(&self, chunk: &mut Vec<String>) {
+        if let Some(m) = self.max_lines {
+            chunk.truncate(m.saturating_sub(self.lines.len()) + 1);
+        }
+        if let Some(m) = self.max_chars {
+            let mut chars = m.saturating_sub(self.char_count());
+            for i in 0..chunk.len() {
+                let line = &mut chunk[i];
+                if let Some((end, _)) = line.char_indices().nth(chars) {
+                    line.truncate(end);
+                    chunk.truncate(i + 1);
+                    break;
+                }
+                chars -= line.chars().count();
+            }
+        }
+    }
+
+    // Check if inserting the number of characters and newlines doesn't exceed the limits
+    fn within_limits(&self, chars: usize, newlines: usize) -> bool {
+        let chars_ok = self
+            .max_chars
+            .map_or(true, |m| chars == 0 || self.char_count() + chars <= m);
+        chars_ok && self.within_line_limit(newlines)
+    }
+
+    // Check if inserting the number of newlines doesn't exceed the line limit. Characters are not counted
+    fn within_line_limit(&self, newlines: usize) -> bool {
+        self.max_lines
+            .map_or(true, |m| newlines == 0 || self.lines.len() + newlines <= m)
+    }
+
+    /// Set the style of cursor. By default, a cursor is rendered in the reversed color. Setting the same style as
+    /// cursor line hides a cursor.
+    /// ```
+    /// use tui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let style = Style::default().bg(Color::Red);
+    /// textarea.set_cursor_style(style);
+    /// assert_eq!(textarea.cursor_style(), style);
+    /// ```
+    pub fn set_cursor_style(&mut self, style: Style) {
+        self.cursor_style = style;
+    }
+
+    /// Get the style of cursor.
+    pub fn cursor_style(&self) -> Style {
+        self.cursor_style
+    }
+
+    /// Set the style of text selection. The selected text is rendered with this style. By default, the selection is
+    /// rendered with light blue background.
+    /// ```
+    /// use tui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let style = Style::default().bg(Color::Green);
+    /// textarea.set_selection_style(style);
+    /// assert_eq!(textarea.selection_style(), style);
+    /// ```
+    pub fn set_selection_style(&mut self, style: Style) {
+        self.selection_style = style;
+    }
+
+    /// Get the style of text selection.
+    pub fn selection_style(&self) -> Style {
+        self.selection_style
+    }
+
+    /// Set the display column of the vertical ruler. The ruler is rendered at the column on every row of the textarea,
+    /// even below the last line, so that it can be used as a guide of line length such as 80 columns. The column is
+    /// 0-base and counted in display width where tabs and wide characters occupy multiple columns. The ruler follows
+    /// horizontal scroll and it is not rendered when the alignment is not [`Alignment::Left`]. Setting `None` removes
+    /// the ruler. By default, no ruler is rendered.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.ruler_column(), None);
+    ///
+    /// textarea.set_ruler_column(Some(80));
+    /// assert_eq!(textarea.ruler_column(), Some(80));
+    /// ```
+    pub fn set_ruler_column(&mut self, col: Option<u16>) {
+        self.ruler_column = col;
+    }
+
+    /// Get the display column of the vertical ruler if set.
+    pub fn ruler_column(&self) -> Option<u16> {
+        self.ruler_column
+    }
+
+    /// Set the style of the vertical ruler set by [`TextArea::set_ruler_column`]. By default, the ruler is rendered
+    /// with dark gray background.
+    /// ```
+    /// use tui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let style = Style::default().bg(Color::Red);
+    /// textarea.set_ruler_style(style);
+    /// assert_eq!(textarea.ruler_style(), style);
+    /// ```
+    pub fn set_ruler_style(&mut self, style: Style) {
+        self.ruler_style = style;
+    }
+
+    /// Get the style of the vertical ruler.
+    pub fn ruler_style(&self) -> Style {
+        self.ruler_style
+    }
+
+    /// Set the style of the bracket matching to the bracket at cursor. By setting the style with this method, the
+    /// matching bracket of `(`, `)`, `[`, `]`, `{`, `}` at cursor is highlighted. It is disabled by default.
+    /// ```
+    /// use tui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let style = Style::default().bg(Color::Yellow);
+    /// textarea.set_match_bracket_style(style);
+    /// assert_eq!(textarea.match_bracket_style(), Some(style));
+    /// ```
+    pub fn set_match_bracket_style(&mut self, style: Style) {
+        self.match_bracket_style = Some(style);
+    }
+
+    /// Remove the style of matching bracket which was set by [`TextArea::set_match_bracket_style`]. After calling this
+    /// method, matching brackets will no longer be highlighted.
+    pub fn remove_match_bracket_style(&mut self) {
+        self.match_bracket_style = None;
+    }
+
+    /// Get the style of matching bracket if set.
+    pub fn match_bracket_style(&self) -> Option<Style> {
+        self.match_bracket_style
+    }
+
+    /// Get the position of the bracket matching to the bracket at cursor. Brackets are `()`, `[]` and `{}`. Nested
+    /// brackets are considered and the matching bracket may be in another line. When the cursor is not on a bracket or
+    /// no matching bracket is found, this method returns `None`.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["fn f(a: (i32, i32)) {", "}"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Jump(0, 4));
+    /// assert_eq!(textarea.matching_bracket(), Some((0, 18)));
+    /// textarea.move_cursor(CursorMove::Jump(1, 0));
+    /// assert_eq!(textarea.matching_bracket(), Some((0, 20)));
+    /// textarea.move_cursor(CursorMove::Jump(0, 0));
+    /// assert_eq!(textarea.matching_bracket(), None);
+    /// ```
+    pub fn matching_bracket(&self) -> Option<(usize, usize)> {
+        find_matching_bracket(&self.lines, self.cursor)
+    }
+
+    /// Get slice of line texts. This method borrows the content, but not moves. Note that the returned slice will
+    /// never be empty because an empty text means a slice containing one empty line. This is correct since any text
+    /// file must end with a newline.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.lines(), [""]);
+    ///
+    /// textarea.insert_char('a');
+    /// assert_eq!(textarea.lines(), ["a"]);
+    ///
+    /// textarea.insert_newline();
+    /// assert_eq!(textarea.lines(), ["a", ""]);
+    ///
+    /// textarea.insert_char('b');
+    /// assert_eq!(textarea.lines(), ["a", "b"]);
+    /// ```
+    pub fn lines(&'a self) -> &'a [String] {
+        &self.lines
+    }
+
+    /// Get the text of the line where the cursor is. Since the text always has at least one line, this method always
+    /// returns a line.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["foo", "bar"]);
+    /// assert_eq!(textarea.current_line(), "foo");
+    ///
+    /// textarea.move_cursor(CursorMove::Down);
+    /// assert_eq!(textarea.current_line(), "bar");
+    ///
+    /// let textarea = TextArea::default();
+    /// assert_eq!(textarea.current_line(), "");
+    /// ```
+    pub fn current_line(&self) -> &str {
+        &self.lines[self.cursor.0]
+    }
+
+    /// Get the character under the cursor. When the cursor is on a grapheme cluster such as an emoji sequence or a
+    /// character with combining marks, its first character is returned. Use [`TextArea::grapheme_under_cursor`] to get
+    /// the whole cluster. This method returns `None` when the cursor is at the end of line.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["ab"]);
+    /// assert_eq!(textarea.char_under_cursor(), Some('a'));
+    ///
+    /// textarea.move_cursor(CursorMove::End);
+    /// assert_eq!(textarea.char_under_cursor(), None);
+    /// ```
+    pub fn char_under_cursor(&self) -> Option<char> {
+        self.lines[self.cursor.0].chars().nth(self.cursor.1)
+    }
+
+    /// Get the grapheme cluster under the cursor as a string slice. This method returns `None` when the cursor is at
+    /// the end of line.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["e\u{301}x"]);
+    /// assert_eq!(textarea.grapheme_under_cursor(), Some("e\u{301}"));
+    /// assert_eq!(textarea.char_under_cursor(), Some('e'));
+    ///
+    /// textarea.move_cursor(CursorMove::Forward);
+    /// assert_eq!(textarea.grapheme_under_cursor(), Some("x"));
+    /// ```
+    pub fn grapheme_under_cursor(&self) -> Option<&str> {
+        let (row, col) = self.cursor;
+        let line = &self.lines[row];
+        let (start, _) = line.char_indices().nth(col)?;
+        line[start..].graphemes(true).next()
+    }
+
+    /// Get the text of the line at the 0-base row. This method returns `None` when the row is out of range.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::from(["foo", "bar"]);
+    /// assert_eq!(textarea.line(1), Some("bar"));
+    /// assert_eq!(textarea.line(2), None);
+    /// ```
+    pub fn line(&self, row: usize) -> Option<&str> {
+        self.lines.get(row).map(String::as_str)
+    }
+
+    /// Convert [`TextArea`] instance into line texts.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.insert_char('a');
+    /// textarea.insert_newline();
+    /// textarea.insert_char('b');
+    ///
+    /// assert_eq!(textarea.into_lines(), ["a", "b"]);
+    /// ```
+    pub fn into_lines(self) -> Vec<String> {
+        self.lines
+    }
+
+    /// Get the whole text as a single string. This is the same text as written by [`TextArea::write_to`]. Lines are
+    /// joined with [`TextArea::line_ending`] and the line ending is added after the last line following
+    /// [`TextArea::trailing_newline`]. The text can be restored by [`TextArea::set_text`].
+    /// ```
+    /// use tui_textarea::{TextArea, LineEnding};
+    ///
+    /// let mut textarea = TextArea::from(["hello", "world"]);
+    /// textarea.set_line_ending(LineEnding::Lf);
+    /// assert_eq!(textarea.content(), "hello\nworld\n");
+    ///
+    /// textarea.set_line_ending(LineEnding::CrLf);
+    /// assert_eq!(textarea.content(), "hello\r\nworld\r\n");
+    ///
+    /// textarea.set_trailing_newline(false);
+    /// assert_eq!(textarea.content(), "hello\r\nworld");
+    /// ```
+    pub fn content(&self) -> String {
+        let ending = self.line_ending.as_str();
+        let mut s = self.lines.join(ending);
+        if self.ends_with_newline() {
+            s.push_str(ending);
+        }
+        s
+    }
+
+    // The last line is followed by the line ending when the trailing newline is enabled. An empty last line is always
+    // followed by it since the text without it is read back without the empty line
+    fn ends_with_newline(&self) -> bool {
+        self.trailing_newline || self.lines.len() > 1 && self.lines[self.lines.len() - 1].is_empty()
+    }
+
+    /// Replace the whole text with the string. The string is split into lines by `\n` or `\r\n`. The line ending is not
+    /// changed by this method. As [`TextArea::from_reader`] does, a newline at the end of the string does not make an
+    /// empty line and whether the string ends with a newline is set as [`TextArea::trailing_newline`]. So the text
+    /// returned from [`TextArea::content`] is restored as it was. The cursor moves to the head of the text, the
+    /// selection is cancelled and the undo/redo history is cleared.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["hello"]);
+    ///
+    /// textarea.set_text("foo\nbar");
+    /// assert_eq!(textarea.lines(), ["foo", "bar"]);
+    /// assert_eq!(textarea.cursor(), (0, 0));
+    /// assert!(!textarea.trailing_newline());
+    ///
+    /// textarea.set_text("foo\n");
+    /// assert_eq!(textarea.lines(), ["foo"]);
+    /// assert!(textarea.trailing_newline());
+    ///
+    /// textarea.set_text("");
+    /// assert_eq!(textarea.lines(), [""]);
+    /// ```
+    pub fn set_text(&mut self, s: &str) {
+        let text = match s.strip_suffix('\n') {
+            Some(t) => t.strip_suffix('\r').unwrap_or(t),
+            None => s,
+        };
+        self.trailing_newline = text.len() < s.len();
+        self.lines = text
+            .split('\n')
+            .map(|l| l.strip_suffix('\r').unwrap_or(l).to_string())
+            .collect();
+        self.cursor = (0, 0);
+        self.selection_start = None;
+        self.wheel_scrolled_cursor = None;
+        self.history = History::new(self.history.max_items());
+        self.invalidate_caches(0);
+    }
+
+    /// Replace the whole text with the string while keeping the cursor near the same position, such as applying the
+    /// output of an external formatter. The string is split into lines by `\n` or `\r\n`. As
+    /// [`TextArea::set_text`] does, a newline at the end of the string does not make an empty line since formatters
+    /// usually output text ending with a newline. [`TextArea::trailing_newline`] is not changed by this method.
+    ///
+    /// Lines which are the same at the head and at the end of the text are kept and only the lines between them are
+    /// replaced. The replacement is undone at once by [`TextArea::undo`]. When the cursor was on a replaced line, it
+    /// moves to the new line with the same text ignoring indentation, or to the line at the proportional position. Its
+    /// column is shifted by the change of the indentation so that the cursor stays on the same token when the text was
+    /// reindented. This method returns if the text was modified or not.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["fn main() {", "println!();", "}"]);
+    /// textarea.move_cursor(CursorMove::Jump(1, 2)); // On 'i' of "println"
+    ///
+    /// assert!(textarea.replace_all_text("fn main() {\n    println!();\n}\n"));
+    /// assert_eq!(textarea.lines(), ["fn main() {", "    println!();", "}"]);
+    /// assert_eq!(textarea.cursor(), (1, 6));
+    ///
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["fn main() {", "println!();", "}"]);
+    /// ```
+    pub fn replace_all_text(&mut self, new_text: &str) -> bool {
+        let text = match new_text.strip_suffix('\n') {
+            Some(s) => s.strip_suffix('\r').unwrap_or(s),
+            None => new_text,
+        };
+        let new: Vec<String> = text
+            .split('\n')
+            .map(|l| l.strip_suffix('\r').unwrap_or(l).to_string())
+            .collect();
+
+        let old = &self.lines;
+        let prefix = old.iter().zip(&new).take_while(|(o, n)| o == n).count();
+        if prefix == old.len() && prefix == new.len() {
+            return false;
+        }
+        let suffix = old[prefix..]
+            .iter()
+            .rev()
+            .zip(new[prefix..].iter().rev())
+            .take_while(|(o, n)| o == n)
+            .count();
+        let (old_end, new_end) = (old.len() - suffix, new.len() - suffix);
+
+        let (row, col) = self.cursor;
+        let cursor = if row < prefix {
+            (row, col)
+        } else if row >= old_end {
+            (row - old_end + new_end, col)
+        } else if prefix == new_end {
+            (cmp::min(prefix, new.len() - 1), 0) // The cursor line was removed
+        } else {
+            // Find the line with the same text ignoring indentation nearest to the proportional position
+            let (old_len, new_len) = (old_end - prefix, new_end - prefix);
+            let target = (row - prefix) * new_len / old_len;
+            let line = old[row].trim();
+            let new_row = (0..new_len)
+                .filter(|&i| new[prefix + i].trim() == line)
+                .min_by_key(|&i| cmp::max(i, target) - cmp::min(i, target))
+                .unwrap_or(target);
+            let new_row = prefix + new_row;
+
+            let indent = |l: &str| l.chars().take_while(|c| c.is_whitespace()).count();
+            let (old_indent, new_indent) = (indent(&old[row]), indent(&new[new_row]));
+            let col = if col >= old_indent {
+                new_indent + (col - old_indent)
+            } else {
+                cmp::min(col, new_indent)
+            };
+            (new_row, cmp::min(col, new[new_row].chars().count()))
+        };
+
+        let before = self.cursor;
+        let inserted = new[prefix..new_end].to_vec();
+        let removed = self
+            .lines
+            .splice(prefix..old_end, inserted.iter().cloned())
+            .collect();
+        self.cursor = cursor;
+        self.push_history(EditKind::SpliceLines(prefix, removed, inserted), before);
+        true
+    }
+
+    /// Write the text to the writer. Each line is followed by [`TextArea::line_ending`]. The line ending after the last
+    /// line is written following [`TextArea::trailing_newline`]. Text loaded by [`TextArea::from_reader`] is written
+    /// back as it was unless it was modified or it mixed line endings. The written text is the same as
+    /// [`TextArea::content`].
+    /// ```
+    /// use tui_textarea::{TextArea, LineEnding};
+    ///
+    /// let mut textarea = TextArea::from(["hello", "world"]);
+    /// textarea.set_line_ending(LineEnding::Lf);
+    ///
+    /// let mut buf = vec![];
+    /// textarea.write_to(&mut buf).unwrap();
+    /// assert_eq!(buf, b"hello\nworld\n");
+    /// ```
+    pub fn write_to<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        let ending = self.line_ending.as_str().as_bytes();
+        for (i, line) in self.lines.iter().enumerate() {
+            if i > 0 {
+                w.write_all(ending)?;
+            }
+            w.write_all(line.as_bytes())?;
+        }
+        if self.ends_with_newline() {
+            w.write_all(ending)?;
+        }
+        Ok(())
+    }
+
+    /// Set if [`TextArea::write_to`] and [`TextArea::content`] add a newline after the last line. By default, it is
+    /// `true` for a new textarea and it is set to whether the loaded text ended with a newline by
+    /// [`TextArea::from_reader`] and [`TextArea::set_text`].
+    ///
+    /// The trailing newline is a terminator of the last line, not a separator before an extra line. So it never makes
+    /// an empty line which the cursor can move to, and this setting doesn't change [`TextArea::lines`]. An empty last
+    /// line in the textarea is a real line and it is always followed by the trailing newline, even when this setting
+    /// is disabled, since the text would be read back without the empty line otherwise. Text written by
+    /// [`TextArea::write_to`] is read back to the same lines and the same setting by [`TextArea::from_reader`]. Only an
+    /// empty last line with this setting disabled is read back with the setting enabled.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["hello"]);
+    /// assert!(textarea.trailing_newline());
+    ///
+    /// textarea.set_trailing_newline(false);
+    /// let mut buf = vec![];
+    /// textarea.write_to(&mut buf).unwrap();
+    /// assert_eq!(buf, b"hello");
+    /// ```
+    pub fn set_trailing_newline(&mut self, enabled: bool) {
+        self.trailing_newline = enabled;
+    }
+
+    /// Get if [`TextArea::write_to`] and [`TextArea::content`] add a newline after the last line.
+    pub fn trailing_newline(&self) -> bool {
+        self.trailing_newline
+    }
+
+    /// Set the line ending used by [`TextArea::write_to`] and [`TextArea::content`]. [`TextArea::from_reader`] sets the
+    /// line ending detected in the loaded text. By default, it is the line ending of the platform.
+    /// ```
+    /// use tui_textarea::{TextArea, LineEnding};
+    ///
+    /// let mut textarea = TextArea::from_reader("hello\r\nworld\r\n".as_bytes()).unwrap();
+    /// assert_eq!(textarea.line_ending(), LineEnding::CrLf);
+    ///
+    /// textarea.set_line_ending(LineEnding::Lf);
+    /// let mut buf = vec![];
+    /// textarea.write_to(&mut buf).unwrap();
+    /// assert_eq!(buf, b"hello\nworld\n");
+    /// ```
+    pub fn set_line_ending(&mut self, ending: LineEnding) {
+        self.line_ending = ending;
+    }
+
+    /// Get the line ending used by [`TextArea::write_to`] and [`TextArea::content`].
+    pub fn line_ending(&self) -> LineEnding {
+        self.line_ending
+    }
+
+    /// Get the current cursor position. 0-base character-wise (row, col) cursor position.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.cursor(), (0, 0));
+    ///
+    /// textarea.insert_char('a');
+    /// textarea.insert_newline();
+    /// textarea.insert_char('b');
+    ///
+    /// assert_eq!(textarea.cursor(), (1, 1));
+    /// ```
+    pub fn cursor(&self) -> (usize, usize) {
+        self.cursor
+    }
+
+    /// Start text selection at the current cursor position. While the selection is active, moving the cursor extends
+    /// the selection from the start position to the cursor. Modifying the text or undo/redo cancels the selection. When
+    /// the selection is already active, this method restarts it at the cursor.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["hello", "world"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Forward);
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Down);
+    /// assert_eq!(textarea.selection_range(), Some(((0, 1), (1, 1))));
+    /// ```
+    pub fn start_selection(&mut self) {
+        self.selection_start = Some(self.cursor);
+        self.shift_selection = false;
+    }
+
+    /// Select the whole text. The selection starts at the head of the first line and the cursor moves to the end of the
+    /// last line. The viewport is scrolled to show the cursor on the next render.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["hello", "world"]);
+    ///
+    /// textarea.select_all();
+    /// assert_eq!(textarea.selection_range(), Some(((0, 0), (1, 5))));
+    /// assert_eq!(textarea.cursor(), (1, 5));
+    /// ```
+    pub fn select_all(&mut self) {
+        self.move_cursor_bottom();
+        self.selection_start = Some((0, 0));
+        self.shift_selection = false;
+        self.desired_col = None;
+    }
+
+    /// Select the word under the cursor. A word is a run of word characters, punctuations or whitespaces. When the
+    /// cursor is at the end of line, the word before the cursor is selected. The selection starts at the head of the
+    /// word and the cursor moves to the end of the word. On an empty line, an empty selection is started at the cursor.
+    /// This is useful to implement selecting a word by double click.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["hello, world"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Jump(0, 2));
+    /// textarea.select_word();
+    /// assert_eq!(textarea.selection_range(), Some(((0, 0), (0, 5))));
+    /// assert_eq!(textarea.cursor(), (0, 5));
+    /// ```
+    pub fn select_word(&mut self) {
+        let (row, col) = self.cursor;
+        let line = &self.lines[row];
+        let range = find_word_range(line, col)
+            .or_else(|| col.checked_sub(1).and_then(|c| find_word_range(line, c)));
+        if let Some((start, end)) = range {
+            self.selection_start = Some((row, start));
+            self.cursor = (row, end);
+        } else {
+            self.selection_start = Some(self.cursor);
+        }
+        self.shift_selection = false;
+        self.desired_col = None;
+    }
+
+    /// Get the word under the cursor and its character-wise column range in the cursor line. The end of the range is
+    /// exclusive. Words are split by whitespaces and punctuations as [`TextArea::select_word`]. When the cursor is at
+    /// the end of line, the word before the cursor is returned. This method returns `None` when the cursor is on a
+    /// whitespace or a punctuation. This is useful to trigger completion or to look up the word.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["hello, world"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Jump(0, 2));
+    /// assert_eq!(textarea.word_under_cursor(), Some(("hello", 0..5)));
+    ///
+    /// textarea.move_cursor(CursorMove::Jump(0, 5));
+    /// assert_eq!(textarea.word_under_cursor(), None);
+    /// ```
+    pub fn word_under_cursor(&self) -> Option<(&str, Range<usize>)> {
+        let (row, col) = self.cursor;
+        let line = &self.lines[row];
+        let (start, end) = find_word_range(line, col)
+            .or_else(|| col.checked_sub(1).and_then(|c| find_word_range(line, c)))?;
+        if !line.chars().nth(start).map_or(false, is_word_char) {
+            return None;
+        }
+        let byte = |col| line.char_indices().nth(col).map_or(line.len(), |(i, _)| i);
+        Some((&line[byte(start)..byte(end)], start..end))
+    }
+
+    /// Select the current line including its trailing newline. The selection starts at the head of the line and the
+    /// cursor moves to the head of the next line. On the last line, the cursor moves to the end of the line since it
+    /// has no trailing newline. This is useful to implement selecting a line by triple click.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["hello", "world"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Forward);
+    /// textarea.select_line();
+    /// assert_eq!(textarea.selection_range(), Some(((0, 0), (1, 0))));
+    /// assert_eq!(textarea.copy().as_deref(), Some("hello\n"));
+    /// ```
+    pub fn select_line(&mut self) {
+        let row = self.cursor.0;
+        self.selection_start = Some((row, 0));
+        self.cursor = if row + 1 < self.lines.len() {
+            (row + 1, 0)
+        } else {
+            (row, self.lines[row].chars().count())
+        };
+        self.shift_selection = false;
+        self.desired_col = None;
+    }
+
+    /// Cancel the active text selection. The cursor is not moved. This method does nothing when no selection is active.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["hello"]);
+    ///
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.cancel_selection();
+    /// assert_eq!(textarea.selection_range(), None);
+    /// assert_eq!(textarea.cursor(), (0, 5));
+    /// ```
+    pub fn cancel_selection(&mut self) {
+        self.selection_start = None;
+    }
+
+    /// Get the range of the active text selection as 0-base character-wise (row, col) positions. The first position is
+    /// always before or equal to the second one regardless of the direction the cursor moved. The end position is
+    /// exclusive. This method returns `None` when no selection is active.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["hello", "world"]);
+    /// assert_eq!(textarea.selection_range(), None);
+    ///
+    /// textarea.move_cursor(CursorMove::Jump(1, 3));
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Top);
+    /// assert_eq!(textarea.selection_range(), Some(((0, 3), (1, 3))));
+    /// ```
+    pub fn selection_range(&self) -> Option<((usize, usize), (usize, usize))> {
+        let start = self.selection_start?;
+        if start <= self.cursor {
+            Some((start, self.cursor))
+        } else {
+            Some((self.cursor, start))
+        }
+    }
+
+    /// Set how text is selected. In [`SelectionMode::Block`], the selection is the rectangle whose corners are the
+    /// selection start and the cursor. [`TextArea::copy`] and [`TextArea::cut`] handle the text in the rectangle line
+    /// by line, and only the columns in the rectangle are highlighted. [`TextArea::selection_range`] still returns the
+    /// two corners. The default is [`SelectionMode::Normal`].
+    ///
+    /// Block selection has some limitations for now. Columns are character-wise so the rectangle is not aligned on
+    /// screen when lines contain tabs or wide characters. Other editing methods such as [`TextArea::insert_str`] and
+    /// [`TextArea::paste`] don't handle the rectangle and edit the text at the cursor as usual.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove, SelectionMode};
+    ///
+    /// let mut textarea = TextArea::from(["abcd", "efgh", "ijkl"]);
+    ///
+    /// textarea.set_selection_mode(SelectionMode::Block);
+    /// textarea.move_cursor(CursorMove::Forward);
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Jump(2, 3));
+    /// assert_eq!(textarea.copy().as_deref(), Some("bc\nfg\njk"));
+    /// ```
+    pub fn set_selection_mode(&mut self, mode: SelectionMode) {
+        self.selection_mode = mode;
+    }
+
+    /// Get how text is selected. See [`TextArea::set_selection_mode`] for more details.
+    /// ```
+    /// use tui_textarea::{TextArea, SelectionMode};
+    ///
+    /// let textarea = TextArea::default();
+    /// assert_eq!(textarea.selection_mode(), SelectionMode::Normal);
+    /// ```
+    pub fn selection_mode(&self) -> SelectionMode {
+        self.selection_mode
+    }
+
+    /// Enable or disable soft wrapping of lines. When enabled, lines longer than the width of the textarea are wrapped
+    /// into multiple rows instead of scrolling horizontally. Line numbers are shown only on the first row of each line.
+    /// Wrapping only affects rendering and it doesn't modify the text. It is disabled by default. How lines are broken
+    /// can be configured by [`TextArea::set_wrap_mode`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert!(!textarea.wrap());
+    ///
+    /// textarea.set_wrap(true);
+    /// assert!(textarea.wrap());
+    /// ```
+    pub fn set_wrap(&mut self, wrap: bool) {
+        self.wrap = wrap;
+    }
+
+    /// Get if soft wrapping of lines is enabled.
+    pub fn wrap(&self) -> bool {
+        self.wrap
+    }
+
+    /// Set how lines are broken when soft wrapping is enabled by [`TextArea::set_wrap`]. [`WrapMode::Char`] breaks
+    /// lines at any character and [`WrapMode::Word`] breaks lines at whitespaces. The default is [`WrapMode::Char`].
+    /// ```
+    /// use tui_textarea::{TextArea, WrapMode};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.wrap_mode(), WrapMode::Char);
+    ///
+    /// textarea.set_wrap(true);
+    /// textarea.set_wrap_mode(WrapMode::Word);
+    /// assert_eq!(textarea.wrap_mode(), WrapMode::Word);
+    /// ```
+    pub fn set_wrap_mode(&mut self, mode: WrapMode) {
+        self.wrap_mode = mode;
+    }
+
+    /// Get how lines are broken when soft wrapping is enabled.
+    pub fn wrap_mode(&self) -> WrapMode {
+        self.wrap_mode
+    }
+
+    // Wrapper to wrap lines in the viewport of the width. Line numbers are excluded from the width
+    pub(crate) fn wrapper(&self, width: u16) -> Option<Wrapper> {
+        if !self.wrap {
+            return None;
+        }
+        let width = (width as usize).saturating_sub(self.gutter_width());
+        Some(Wrapper::new(width, self.tab_len, self.wrap_mode).mask(self.mask))
+    }
+
+    // Width of signs and line numbers rendered at the head of each line
+    pub(crate) fn gutter_width(&self) -> usize {
+        let sign = if self.line_signs.is_empty() { 0 } else { 2 };
+        if self.line_number_style.is_some() {
+            sign + num_digits(self.lines.len()) as usize + 2
+        } else {
+            sign
+        }
+    }
+
+    // Visual column of the character-wise column in the row on the screen. Tabs and wide characters occupy multiple
+    // columns. Line numbers are counted since they are scrolled horizontally with the text
+    pub(crate) fn screen_col(&self, row: usize, col: usize) -> usize {
+        let col = match self.mask {
+            Some(mask) => col * mask.width().unwrap_or(0),
+            None => display_col(&self.lines[row], col, self.tab_len),
+        };
+        col + self.gutter_width()
+    }
+
+    // Character-wise column of the character rendered at the visual column in the row. It is the inverse of
+    // `screen_col`. Columns on line numbers are mapped to the head of line and columns past the end of line are
+    // clamped to the end of line
+    pub(crate) fn col_at_screen_col(&self, row: usize, screen_col: usize) -> usize {
+        let line = &self.lines[row];
+        let col = screen_col.saturating_sub(self.gutter_width());
+        match self.mask {
+            Some(mask) => cmp::min(
+                col / cmp::max(mask.width().unwrap_or(0), 1),
+                line.chars().count(),
+            ),
+            None => char_col(line, col, self.tab_len),
+        }
+    }
+
+    // Map the (x, y) position on terminal to the (row, col) position in the text with the viewport of the last render.
+    // Positions past the end of line are clamped to the end of line, and positions below the last line are clamped to
+    // the last line. Clicks on line numbers are mapped to the head of line.
+    fn position_at(&self, x: u16, y: u16) -> (usize, usize) {
+        let (top_row, top_col, width, _) = self.viewport.rect();
+        let (origin_x, origin_y) = self.viewport.origin();
+        let x = x.saturating_sub(origin_x) as usize;
+        let y = y.saturating_sub(origin_y) as usize;
+        let last_row = self.lines.len() - 1;
+        let mut row = cmp::min(top_row as usize, last_row);
+
+        if let Some(wrapper) = self.wrapper(width) {
+            // Lines are never scrolled horizontally while wrapping
+            let x = x.saturating_sub(self.gutter_width());
+            let mut y = y;
+            loop {
+                let line = &self.lines[row];
+                let cursor_at_end = row == self.cursor.0 && line.chars().count() <= self.cursor.1;
+                let rows = wrapper.rows(line, cursor_at_end);
+                if y < rows || row == last_row {
+                    return (row, wrapper.column(line, y, x));
+                }
+                y -= rows;
+                row += 1;
+            }
+        }
+
+        row = cmp::min(row + y, last_row);
+        let x = match self.alignment {
+            Alignment::Left => top_col as usize + x,
+            _ => {
+                // Lines are not scrolled horizontally. The cursor at end of line occupies one more cell
+                let cursor_at_end =
+                    row == self.cursor.0 && self.lines[row].chars().count() <= self.cursor.1;
+                let w = self.line_display_width(row) + cursor_at_end as usize;
+                x.saturating_sub(self.alignment_offset(width as usize, w))
+            }
+        };
+        (row, self.col_at_screen_col(row, x))
+    }
+
+    // Offset of a rendered row with the width from the left edge of the viewport by the alignment
+    fn alignment_offset(&self, width: usize, w: usize) -> usize {
+        match self.alignment {
+            Alignment::Center => (width / 2).saturating_sub(w / 2),
+            Alignment::Right => width.saturating_sub(w),
+            Alignment::Left => 0,
+        }
+    }
+
+    /// Set text alignment. When [`Alignment::Center`] or [`Alignment::Right`] is set, line number is automatically
+    /// disabled because those alignments don't work well with line numbers. The cursor is rendered on the aligned
+    /// text and mouse clicks are mapped to the aligned text as well. Lines are not scrolled horizontally unless they
+    /// are aligned to left.
+    /// ```
+    /// use tui_textarea::TextArea;
+    /// use tui::layout::Alignment;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_alignment(Alignment::Center);
+    /// assert_eq!(textarea.alignment(), Alignment::Center);
+    /// ```
+    pub fn set_alignment(&mut self, alignment: Alignment) {
+        if let Alignment::Center | Alignment::Right = alignment {
+            self.line_number_style = None;
+        }
+        self.alignment = alignment;
+    }
+
+    /// Get current text alignment. The default alignment is [`Alignment::Left`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    /// use tui::layout::Alignment;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// assert_eq!(textarea.alignment(), Alignment::Left);
+    /// ```
+    pub fn alignment(&self) -> Alignment {
+        self.alignment
+    }
+
+    /// Get the version of the text. The version is incremented every time the text is modified by editing methods, key
+    /// inputs, undo/redo or [`TextArea::set_text`]. Polling this value after [`TextArea::input`] is the simplest way
+    /// to detect changes. Note that the version is not restored on undo so it differs from the version before the edit.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let v = textarea.version();
+    ///
+    /// textarea.insert_str("hello");
+    /// assert_ne!(textarea.version(), v);
+    ///
+    /// let v = textarea.version();
+    /// textarea.undo();
+    /// assert_ne!(textarea.version(), v);
+    /// ```
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Check if the text was modified since the textarea was created or [`TextArea::reset_modified`] was called last
+    /// time. Any modification including undo/redo marks the text as modified, even if undo restored the text to the
+    /// state at the last reset.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert!(!textarea.is_modified());
+    ///
+    /// textarea.insert_str("hello");
+    /// assert!(textarea.is_modified());
+    /// ```
+    pub fn is_modified(&self) -> bool {
+        self.version != self.saved_version
+    }
+
+    /// Mark the current text as unmodified. This is typically called after saving the text with
+    /// [`TextArea::write_to`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.insert_str("hello");
+    /// textarea.reset_modified();
+    /// assert!(!textarea.is_modified());
+    ///
+    /// textarea.undo();
+    /// assert!(textarea.is_modified());
+    /// ```
+    pub fn reset_modified(&mut self) {
+        self.saved_version = self.version;
+    }
+
+    /// Check if the textarea has a empty content.
+    /// ```
+    /// use tui_textarea::TextArea;
     ///
     /// let textarea = TextArea::default();
     /// assert!(textarea.is_empty());
@@ -1419,8 +4533,8 @@ impl<'a> TextArea<'a> {
         &self.yank
     }
 
-    /// Set a yanked text. The text can be inserted by [`TextArea::paste`]. The string passed to method must not contain
-    /// any newlines.
+    /// Set a yanked text. The text can be inserted by [`TextArea::paste`]. The text can contain newlines (`\n`). This
+    /// method is useful to paste text in the system clipboard.
     /// ```
     /// use tui_textarea::TextArea;
     ///
@@ -1429,6 +4543,11 @@ impl<'a> TextArea<'a> {
     /// textarea.set_yank_text("hello, world");
     /// textarea.paste();
     /// assert_eq!(textarea.lines(), ["hello, world"]);
+    ///
+    /// textarea.set_yank_text("\nfoo\nbar");
+    /// textarea.paste();
+    /// assert_eq!(textarea.lines(), ["hello, world", "foo", "bar"]);
+    /// assert_eq!(textarea.cursor(), (2, 3));
     /// ```
     pub fn set_yank_text(&mut self, text: impl Into<String>) {
         self.yank = text.into();
@@ -1443,6 +4562,9 @@ impl<'a> TextArea<'a> {
     ///
     /// When the pattern is invalid, the search pattern will not be updated and an error will be returned.
     ///
+    /// To search case-insensitively, add `(?i)` flag to the pattern like `(?i)hello`. To set a regular expression
+    /// built by yourself, use [`TextArea::set_search_pattern_regex`].
+    ///
     /// ```
     /// use tui_textarea::TextArea;
     ///
@@ -1469,8 +4591,65 @@ impl<'a> TextArea<'a> {
         self.search.set_pattern(query.as_ref())
     }
 
-    /// Get a regular expression which was set by [`TextArea::set_search_pattern`]. When no text search is ongoing, this
-    /// method returns `None`.
+    /// Set a compiled regular expression for text search. This is useful when the regular expression is built with
+    /// [`regex::RegexBuilder`] to configure options like case-insensitivity. Setting an empty pattern stops the text
+    /// search. Other behaviors are the same as [`TextArea::set_search_pattern`].
+    ///
+    /// ```
+    /// use regex::RegexBuilder;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["Hello", "HELLO", "hello"]);
+    ///
+    /// let re = RegexBuilder::new("hello").case_insensitive(true).build().unwrap();
+    /// textarea.set_search_pattern_regex(re);
+    /// assert_eq!(textarea.search_pattern().unwrap().as_str(), "hello");
+    ///
+    /// textarea.search_forward(false);
+    /// assert_eq!(textarea.cursor(), (1, 0));
+    /// textarea.search_forward(false);
+    /// assert_eq!(textarea.cursor(), (2, 0));
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn set_search_pattern_regex(&mut self, re: regex::Regex) {
+        self.search.set_regex(re);
+    }
+
+    /// Set options of text search. Case-insensitivity is applied to the pattern set by
+    /// [`TextArea::set_search_pattern`]. A regular expression set by [`TextArea::set_search_pattern_regex`] keeps its
+    /// own flags. Whole word matching is applied to both. Highlights of matches and search navigations follow the
+    /// options. By default, all options are disabled.
+    ///
+    /// ```
+    /// use tui_textarea::{TextArea, SearchOptions};
+    ///
+    /// let mut textarea = TextArea::from(["Foo foobar foo"]);
+    /// textarea.set_search_pattern("foo").unwrap();
+    /// assert_eq!(textarea.search_match_count(), 2);
+    ///
+    /// let options = SearchOptions::default().case_insensitive(true).whole_word(true);
+    /// textarea.set_search_options(options);
+    /// assert_eq!(textarea.search_match_count(), 2); // "Foo" and "foo"
+    ///
+    /// textarea.set_search_options(options.whole_word(false));
+    /// assert_eq!(textarea.search_match_count(), 3);
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn set_search_options(&mut self, options: SearchOptions) {
+        self.search.set_options(options);
+    }
+
+    /// Get options of text search set by [`TextArea::set_search_options`].
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_options(&self) -> SearchOptions {
+        self.search.options()
+    }
+
+    /// Get a regular expression which was set by [`TextArea::set_search_pattern`] or
+    /// [`TextArea::set_search_pattern_regex`]. When no text search is ongoing, this method returns `None`.
     ///
     /// ```
     /// use tui_textarea::TextArea;
@@ -1489,8 +4668,9 @@ impl<'a> TextArea<'a> {
     }
 
     /// Search the pattern set by [`TextArea::set_search_pattern`] forward and move the cursor to the next match
-    /// position based on the current cursor position. Text search wraps around a text buffer. It returns `true` when
-    /// some match was found. Otherwise it returns `false`.
+    /// position based on the current cursor position. Text search wraps around a text buffer unless it is disabled by
+    /// [`TextArea::set_search_wrap`]. The textarea scrolls to the new cursor position on next rendering. It returns
+    /// `true` when some match was found. Otherwise it returns `false`.
     ///
     /// The `match_cursor` parameter represents if the search matches to the current cursor position or not. When `true`
     /// is set and the cursor position matches to the pattern, the cursor will not move. When `false`, the cursor will
@@ -1537,8 +4717,9 @@ impl<'a> TextArea<'a> {
     }
 
     /// Search the pattern set by [`TextArea::set_search_pattern`] backward and move the cursor to the next match
-    /// position based on the current cursor position. Text search wraps around a text buffer. It returns `true` when
-    /// some match was found. Otherwise it returns `false`.
+    /// position based on the current cursor position. Text search wraps around a text buffer unless it is disabled by
+    /// [`TextArea::set_search_wrap`]. The textarea scrolls to the new cursor position on next rendering. It returns
+    /// `true` when some match was found. Otherwise it returns `false`.
     ///
     /// The `match_cursor` parameter represents if the search matches to the current cursor position or not. When `true`
     /// is set and the cursor position matches to the pattern, the cursor will not move. When `false`, the cursor will
@@ -1560,24 +4741,199 @@ impl<'a> TextArea<'a> {
     /// textarea.search_back(true);
     /// assert_eq!(textarea.cursor(), (2, 0));
     ///
-    /// // When `match_current` parameter is set to `false`, match at the cursor position is ignored
-    /// textarea.search_back(false);
+    /// // When `match_current` parameter is set to `false`, match at the cursor position is ignored
+    /// textarea.search_back(false);
+    /// assert_eq!(textarea.cursor(), (1, 0));
+    ///
+    /// // `false` is returned when no match was found
+    /// textarea.set_search_pattern("bye+").unwrap();
+    /// let match_found = textarea.search_back(false);
+    /// assert!(!match_found);
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_back(&mut self, match_cursor: bool) -> bool {
+        if let Some(cursor) = self.search.back(&self.lines, self.cursor, match_cursor) {
+            self.cursor = cursor;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Replace the match of the pattern set by [`TextArea::set_search_pattern`] at or after the cursor with the
+    /// replacement string and move the cursor to the end of the inserted replacement. The replacement is inserted
+    /// literally. Text search wraps around a text buffer unless it is disabled by [`TextArea::set_search_wrap`]. Empty
+    /// matches are never replaced. The replacement can be undone as one undo unit. This method returns if some match
+    /// was replaced or not.
+    ///
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["foo bar foo", "foo"]);
+    /// textarea.set_search_pattern("foo").unwrap();
+    ///
+    /// assert!(textarea.replace_next("baz"));
+    /// assert_eq!(textarea.lines(), ["baz bar foo", "foo"]);
+    /// assert_eq!(textarea.cursor(), (0, 3));
+    ///
+    /// assert!(textarea.replace_next("baz"));
+    /// assert_eq!(textarea.lines(), ["baz bar baz", "foo"]);
+    ///
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["baz bar foo", "foo"]);
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn replace_next(&mut self, replacement: &str) -> bool {
+        let before = self.cursor;
+        let (row, col) = self.cursor;
+        let line = &self.lines[row];
+        let i = line
+            .char_indices()
+            .nth(col)
+            .map(|(i, _)| i)
+            .unwrap_or(line.len());
+        let (row, start, end) = if let Some(m) = self.search.next_match(&self.lines, row, i) {
+            m
+        } else {
+            return false;
+        };
+
+        let old = self.lines[row].clone();
+        let mut new = String::with_capacity(old.len() - (end - start) + replacement.len());
+        new.push_str(&old[..start]);
+        new.push_str(replacement);
+        new.push_str(&old[end..]);
+        self.lines[row] = new.clone();
+
+        let col = old[..start].chars().count() + replacement.chars().count();
+        self.cursor = (row, col);
+        self.push_history(EditKind::ReplaceLines(vec![(row, old, new)]), before);
+        true
+    }
+
+    /// Replace all matches of the pattern set by [`TextArea::set_search_pattern`] in the text with the replacement
+    /// string. The replacement is inserted literally. Matches do not overlap and empty matches are never replaced. All
+    /// the replacements can be undone as one undo unit. The cursor stays in the same line. This method returns the
+    /// number of the replaced matches.
+    ///
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["aaaaa", "bab"]);
+    /// textarea.set_search_pattern("aa").unwrap();
+    ///
+    /// assert_eq!(textarea.replace_all("x"), 2);
+    /// assert_eq!(textarea.lines(), ["xxa", "bab"]);
+    ///
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["aaaaa", "bab"]);
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn replace_all(&mut self, replacement: &str) -> usize {
+        let mut replaced = vec![];
+        let mut count = 0;
+        for (row, line) in self.lines.iter_mut().enumerate() {
+            if let Some((new, n)) = self.search.replace_line(line, replacement) {
+                let old = std::mem::replace(line, new.clone());
+                replaced.push((row, old, new));
+                count += n;
+            }
+        }
+        if count == 0 {
+            return 0;
+        }
+
+        let before = self.cursor;
+        let (row, col) = self.cursor;
+        self.cursor = (row, cmp::min(col, self.lines[row].chars().count()));
+        self.push_history(EditKind::ReplaceLines(replaced), before);
+        count
+    }
+
+    /// Get the number of matches of the pattern set by [`TextArea::set_search_pattern`] in the whole text. Empty
+    /// matches are not counted. The matches are computed on the first call and cached until the pattern or the text is
+    /// modified. When no text search is ongoing, this method returns 0.
+    ///
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["foo bar foo", "bar", "foo"]);
+    /// assert_eq!(textarea.search_match_count(), 0);
+    ///
+    /// textarea.set_search_pattern("foo").unwrap();
+    /// assert_eq!(textarea.search_match_count(), 3);
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_match_count(&self) -> usize {
+        self.search.match_count(&self.lines)
+    }
+
+    /// Get the 0-based index of the match where the cursor is on among all matches counted by
+    /// [`TextArea::search_match_count`]. This is useful to show the position like "2/3" with the count. When the cursor
+    /// is not on any match, this method returns `None`.
+    ///
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["foo bar foo", "bar", "foo"]);
+    /// textarea.set_search_pattern("foo").unwrap();
+    /// assert_eq!(textarea.search_current_index(), Some(0));
+    ///
+    /// textarea.search_forward(false);
+    /// assert_eq!(textarea.search_current_index(), Some(1));
+    ///
+    /// textarea.move_cursor(tui_textarea::CursorMove::Down);
+    /// assert_eq!(textarea.search_current_index(), None);
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_current_index(&self) -> Option<usize> {
+        let (row, col) = self.cursor;
+        let line = &self.lines[row];
+        let i = line
+            .char_indices()
+            .nth(col)
+            .map(|(i, _)| i)
+            .unwrap_or(line.len());
+        self.search.match_index(&self.lines, row, i)
+    }
+
+    /// Set if [`TextArea::search_forward`] and [`TextArea::search_back`] wrap around the text buffer. When `false`,
+    /// they stop at the end (or the head) of the text and return `false` when no match was found after (or before) the
+    /// cursor. It is enabled by default.
+    ///
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["hello", "world"]);
+    /// textarea.set_search_pattern("hello").unwrap();
+    ///
+    /// textarea.set_search_wrap(false);
+    /// assert!(!textarea.search_wrap());
+    ///
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// assert!(!textarea.search_forward(false));
     /// assert_eq!(textarea.cursor(), (1, 0));
     ///
-    /// // `false` is returned when no match was found
-    /// textarea.set_search_pattern("bye+").unwrap();
-    /// let match_found = textarea.search_back(false);
-    /// assert!(!match_found);
+    /// textarea.set_search_wrap(true);
+    /// assert!(textarea.search_forward(false));
+    /// assert_eq!(textarea.cursor(), (0, 0));
     /// ```
     #[cfg(feature = "search")]
     #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
-    pub fn search_back(&mut self, match_cursor: bool) -> bool {
-        if let Some(cursor) = self.search.back(&self.lines, self.cursor, match_cursor) {
-            self.cursor = cursor;
-            true
-        } else {
-            false
-        }
+    pub fn set_search_wrap(&mut self, wrap: bool) {
+        self.search.wrap = wrap;
+    }
+
+    /// Get if text search wraps around the text buffer.
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_wrap(&self) -> bool {
+        self.search.wrap
     }
 
     /// Get the text style at matches of text search. The default style is colored with blue in background.
@@ -1615,9 +4971,39 @@ impl<'a> TextArea<'a> {
         self.search.style = style;
     }
 
+    /// Set the text style at the match of text search where the cursor is on. It distinguishes the match which
+    /// [`TextArea::search_forward`] or [`TextArea::search_back`] moved the cursor to from other matches. The default
+    /// style is colored with cyan in background.
+    ///
+    /// ```
+    /// use tui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.search_current_style(), Style::default().bg(Color::Cyan));
+    ///
+    /// let yellow_bg = Style::default().bg(Color::Yellow);
+    /// textarea.set_search_current_style(yellow_bg);
+    /// assert_eq!(textarea.search_current_style(), yellow_bg);
+    /// ```
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn set_search_current_style(&mut self, style: Style) {
+        self.search.current_style = style;
+    }
+
+    /// Get the text style at the match of text search where the cursor is on.
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_current_style(&self) -> Style {
+        self.search.current_style
+    }
+
     /// Scroll the textarea. See [`Scrolling`] for the argument.
     /// The cursor will not move until it goes out the viewport. When the cursor position is outside the viewport after scroll,
     /// the cursor position will be adjusted to stay in the viewport using the same logic as [`CursorMove::InViewport`].
+    /// The viewport does not scroll past the end of the text. Scrolling down stops when the last line is at the bottom
+    /// of the viewport and the cursor moves toward the last line instead.
     ///
     /// ```
     /// # use tui::buffer::Buffer;
@@ -1627,8 +5013,8 @@ impl<'a> TextArea<'a> {
     ///
     /// // Let's say terminal height is 8.
     ///
-    /// // Create textarea with 20 lines "0", "1", "2", "3", ...
-    /// let mut textarea: TextArea = (0..20).into_iter().map(|i| i.to_string()).collect();
+    /// // Create textarea with 30 lines "0", "1", "2", "3", ...
+    /// let mut textarea: TextArea = (0..30).into_iter().map(|i| i.to_string()).collect();
     /// # // Call `render` at least once to populate terminal size
     /// # let r = Rect { x: 0, y: 0, width: 24, height: 8 };
     /// # let mut b = Buffer::empty(r.clone());
@@ -1654,8 +5040,454 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.cursor(), (12, 0));
     /// ```
     pub fn scroll(&mut self, scrolling: impl Into<Scrolling>) {
+        let (top_before, _) = self.viewport.scroll_top();
+        let row_before = self.cursor.0;
         scrolling.into().scroll(&mut self.viewport);
         self.move_cursor(CursorMove::InViewport);
+        // The cursor moves as if the viewport could scroll past the end so that scrolling down reaches the last line
+        let (top, _) = self.viewport.scroll_top();
+        let (max_row, max_col) = self.max_scroll_top();
+        if top > max_row {
+            let last = self.lines.len() - 1;
+            let target = row_before + top.saturating_sub(top_before) as usize;
+            while self.cursor.0 < cmp::min(target, last) {
+                self.move_cursor(CursorMove::Down);
+            }
+        }
+        self.viewport.clamp(max_row, max_col);
+    }
+
+    // Maximum scroll top position where the last line is at the bottom of the viewport and the end of the longest line
+    // is at the right edge of the viewport. When lines are wrapped, the wrapped rows of the lines are counted. Before
+    // the first render, the size of the viewport is unknown so the viewport can scroll until the last line or the end
+    // of the longest line
+    fn max_scroll_top(&self) -> (u32, u32) {
+        let (_, _, width, height) = self.viewport.rect();
+        let height = cmp::max(height as usize, 1);
+        let rows = if let Some(wrapper) = self.wrapper(width) {
+            // Lines from the top row must fill the viewport with their wrapped rows
+            let rows_of = |row: usize| {
+                let line = &self.lines[row];
+                let cursor_at_end = row == self.cursor.0 && line.chars().count() <= self.cursor.1;
+                wrapper.rows(line, cursor_at_end)
+            };
+            let mut row = self.lines.len() - 1;
+            let mut filled = rows_of(row);
+            while row > 0 && filled + rows_of(row - 1) <= height {
+                row -= 1;
+                filled += rows_of(row);
+            }
+            row
+        } else {
+            self.lines.len() - cmp::min(height, self.lines.len())
+        };
+        let cols = if self.wrap() {
+            0 // Lines are never scrolled horizontally while wrapping
+        } else {
+            // +1 for the cursor at end of line
+            let content = self.gutter_width() + self.max_line_display_width() + 1;
+            content.saturating_sub(cmp::max(width as usize, 1))
+        };
+        let clamp = |n: usize| u32::try_from(n).unwrap_or(u32::MAX);
+        (clamp(rows), clamp(cols))
+    }
+
+    /// Scroll down the textarea by one page and move the cursor to stay in the viewport. This is a shortcut of
+    /// [`Scrolling::PageDown`]. Before the first render, the height of the viewport is unknown so the textarea scrolls
+    /// by one line.
+    /// ```
+    /// # use tui::buffer::Buffer;
+    /// # use tui::layout::Rect;
+    /// # use tui::widgets::Widget;
+    /// use tui_textarea::TextArea;
+    ///
+    /// // Let's say terminal height is 8.
+    ///
+    /// // Create textarea with 20 lines "0", "1", "2", "3", ...
+    /// let mut textarea: TextArea = (0..20).into_iter().map(|i| i.to_string()).collect();
+    /// # // Call `render` at least once to populate terminal size
+    /// # let r = Rect { x: 0, y: 0, width: 24, height: 8 };
+    /// # let mut b = Buffer::empty(r.clone());
+    /// # textarea.widget().render(r.clone(), &mut b);
+    ///
+    /// textarea.scroll_page_down();
+    /// assert_eq!(textarea.cursor(), (8, 0));
+    /// ```
+    pub fn scroll_page_down(&mut self) {
+        self.scroll(Scrolling::PageDown);
+    }
+
+    /// Scroll up the textarea by one page and move the cursor to stay in the viewport. This is a shortcut of
+    /// [`Scrolling::PageUp`]. Before the first render, the height of the viewport is unknown so the textarea scrolls by
+    /// one line.
+    /// ```
+    /// # use tui::buffer::Buffer;
+    /// # use tui::layout::Rect;
+    /// # use tui::widgets::Widget;
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// // Let's say terminal height is 8.
+    ///
+    /// // Create textarea with 20 lines "0", "1", "2", "3", ...
+    /// let mut textarea: TextArea = (0..20).into_iter().map(|i| i.to_string()).collect();
+    /// # // Call `render` at least once to populate terminal size
+    /// # let r = Rect { x: 0, y: 0, width: 24, height: 8 };
+    /// # let mut b = Buffer::empty(r.clone());
+    /// # textarea.widget().render(r.clone(), &mut b);
+    ///
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// # textarea.widget().render(r.clone(), &mut b);
+    /// textarea.scroll_page_up();
+    /// assert_eq!(textarea.cursor(), (11, 0));
+    /// ```
+    pub fn scroll_page_up(&mut self) {
+        self.scroll(Scrolling::PageUp);
+    }
+
+    /// Scroll down the textarea by half of the page and move the cursor to stay in the viewport. This is a shortcut of
+    /// [`Scrolling::HalfPageDown`]. Before the first render, the height of the viewport is unknown so the textarea
+    /// scrolls by one line.
+    /// ```
+    /// # use tui::buffer::Buffer;
+    /// # use tui::layout::Rect;
+    /// # use tui::widgets::Widget;
+    /// use tui_textarea::TextArea;
+    ///
+    /// // Let's say terminal height is 8.
+    ///
+    /// // Create textarea with 20 lines "0", "1", "2", "3", ...
+    /// let mut textarea: TextArea = (0..20).into_iter().map(|i| i.to_string()).collect();
+    /// # // Call `render` at least once to populate terminal size
+    /// # let r = Rect { x: 0, y: 0, width: 24, height: 8 };
+    /// # let mut b = Buffer::empty(r.clone());
+    /// # textarea.widget().render(r.clone(), &mut b);
+    ///
+    /// textarea.scroll_half_page_down();
+    /// assert_eq!(textarea.cursor(), (4, 0));
+    /// ```
+    pub fn scroll_half_page_down(&mut self) {
+        self.scroll(Scrolling::HalfPageDown);
+    }
+
+    /// Scroll up the textarea by half of the page and move the cursor to stay in the viewport. This is a shortcut of
+    /// [`Scrolling::HalfPageUp`]. Before the first render, the height of the viewport is unknown so the textarea
+    /// scrolls by one line.
+    /// ```
+    /// # use tui::buffer::Buffer;
+    /// # use tui::layout::Rect;
+    /// # use tui::widgets::Widget;
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// // Let's say terminal height is 8.
+    ///
+    /// // Create textarea with 20 lines "0", "1", "2", "3", ...
+    /// let mut textarea: TextArea = (0..20).into_iter().map(|i| i.to_string()).collect();
+    /// # // Call `render` at least once to populate terminal size
+    /// # let r = Rect { x: 0, y: 0, width: 24, height: 8 };
+    /// # let mut b = Buffer::empty(r.clone());
+    /// # textarea.widget().render(r.clone(), &mut b);
+    ///
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// # textarea.widget().render(r.clone(), &mut b);
+    /// textarea.scroll_half_page_up();
+    /// assert_eq!(textarea.cursor(), (15, 0));
+    /// ```
+    pub fn scroll_half_page_up(&mut self) {
+        self.scroll(Scrolling::HalfPageUp);
+    }
+
+    /// Scroll the textarea vertically so that the line of the cursor is at the middle of the viewport, like `zz` in
+    /// Vim. The cursor does not move. Before the first render, the height of the viewport is unknown so the line is
+    /// put at the top of the viewport.
+    /// ```
+    /// # use tui::buffer::Buffer;
+    /// # use tui::layout::Rect;
+    /// # use tui::widgets::Widget;
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// // Let's say terminal height is 8.
+    ///
+    /// // Create textarea with 20 lines "0", "1", "2", "3", ...
+    /// let mut textarea: TextArea = (0..20).into_iter().map(|i| i.to_string()).collect();
+    /// # // Call `render` at least once to populate terminal size
+    /// # let r = Rect { x: 0, y: 0, width: 24, height: 8 };
+    /// # let mut b = Buffer::empty(r.clone());
+    /// # textarea.widget().render(r.clone(), &mut b);
+    ///
+    /// textarea.move_cursor(CursorMove::Jump(10, 0));
+    /// textarea.scroll_cursor_center();
+    /// assert_eq!(textarea.scrollbar_state().0, 6);
+    /// assert_eq!(textarea.cursor(), (10, 0));
+    /// ```
+    pub fn scroll_cursor_center(&mut self) {
+        self.viewport.center_row(self.cursor.0 as u32);
+    }
+
+    /// Scroll the textarea vertically so that the line of the cursor is at the top of the viewport, like `zt` in Vim.
+    /// The cursor does not move.
+    /// ```
+    /// # use tui::buffer::Buffer;
+    /// # use tui::layout::Rect;
+    /// # use tui::widgets::Widget;
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// // Let's say terminal height is 8.
+    ///
+    /// // Create textarea with 20 lines "0", "1", "2", "3", ...
+    /// let mut textarea: TextArea = (0..20).into_iter().map(|i| i.to_string()).collect();
+    /// # // Call `render` at least once to populate terminal size
+    /// # let r = Rect { x: 0, y: 0, width: 24, height: 8 };
+    /// # let mut b = Buffer::empty(r.clone());
+    /// # textarea.widget().render(r.clone(), &mut b);
+    ///
+    /// textarea.move_cursor(CursorMove::Jump(10, 0));
+    /// textarea.scroll_cursor_top();
+    /// assert_eq!(textarea.scrollbar_state().0, 10);
+    /// ```
+    pub fn scroll_cursor_top(&mut self) {
+        self.viewport.set_top_row(self.cursor.0 as u32);
+    }
+
+    /// Scroll the textarea vertically so that the line of the cursor is at the bottom of the viewport, like `zb` in
+    /// Vim. The cursor does not move. Before the first render, the height of the viewport is unknown so the line is
+    /// put at the top of the viewport.
+    /// ```
+    /// # use tui::buffer::Buffer;
+    /// # use tui::layout::Rect;
+    /// # use tui::widgets::Widget;
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// // Let's say terminal height is 8.
+    ///
+    /// // Create textarea with 20 lines "0", "1", "2", "3", ...
+    /// let mut textarea: TextArea = (0..20).into_iter().map(|i| i.to_string()).collect();
+    /// # // Call `render` at least once to populate terminal size
+    /// # let r = Rect { x: 0, y: 0, width: 24, height: 8 };
+    /// # let mut b = Buffer::empty(r.clone());
+    /// # textarea.widget().render(r.clone(), &mut b);
+    ///
+    /// textarea.move_cursor(CursorMove::Jump(10, 0));
+    /// textarea.scroll_cursor_bottom();
+    /// assert_eq!(textarea.scrollbar_state().0, 3);
+    /// ```
+    pub fn scroll_cursor_bottom(&mut self) {
+        self.viewport.bottom_row(self.cursor.0 as u32);
+    }
+
+    /// Get the offset of horizontal scroll in columns. Lines are scrolled horizontally when the cursor goes past the
+    /// right edge of the viewport. The offset is counted in display width including line numbers since they are
+    /// scrolled with the text. It reflects the viewport of the last render. It is always 0 while lines are wrapped by
+    /// [`TextArea::set_wrap`]. With [`TextArea::max_line_display_width`], a horizontal scrollbar can be rendered.
+    /// ```
+    /// # use tui::buffer::Buffer;
+    /// # use tui::layout::Rect;
+    /// # use tui::widgets::Widget;
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["0123456789"]);
+    /// assert_eq!(textarea.horizontal_scroll(), 0);
+    ///
+    /// // Let's say the textarea is rendered in an area whose width is 4
+    /// textarea.move_cursor(CursorMove::End);
+    /// # let r = Rect { x: 0, y: 0, width: 4, height: 2 };
+    /// # let mut b = Buffer::empty(r.clone());
+    /// # textarea.widget().render(r, &mut b);
+    /// assert_eq!(textarea.horizontal_scroll(), 7);
+    /// assert_eq!(textarea.max_line_display_width(), 10);
+    /// ```
+    pub fn horizontal_scroll(&self) -> u16 {
+        let (_, col) = self.viewport.scroll_top();
+        u16::try_from(col).unwrap_or(u16::MAX)
+    }
+
+    /// Get the maximum display width of all lines. The width is calculated as [`TextArea::line_display_width`] does.
+    /// The result is cached until the text is modified so calling this method on every render is cheap.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["abc", "日本語", "\tx"]);
+    /// assert_eq!(textarea.max_line_display_width(), 6);
+    ///
+    /// textarea.set_tab_length(8);
+    /// assert_eq!(textarea.max_line_display_width(), 9);
+    /// ```
+    pub fn max_line_display_width(&self) -> usize {
+        self.max_width.get_or_compute(|| {
+            (0..self.lines.len())
+                .map(|row| self.line_display_width(row))
+                .max()
+                .unwrap_or(0)
+        })
+    }
+
+    /// Get the state of vertical scroll as `(top, visible, total)` to render a scrollbar alongside the textarea. `top`
+    /// is the 0-base row of the first line in the viewport, `visible` is the height of the viewport, and `total` is
+    /// the number of lines. They reflect the viewport of the last render so `visible` is 0 before the textarea is
+    /// rendered first. When lines are wrapped by [`TextArea::set_wrap`], `visible` is the number of rows on screen
+    /// and a line may occupy multiple rows, so the size of the scrollbar thumb is an approximation.
+    /// ```
+    /// # use tui::buffer::Buffer;
+    /// # use tui::layout::Rect;
+    /// # use tui::widgets::Widget;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea: TextArea = (0..20).map(|i| i.to_string()).collect();
+    /// assert_eq!(textarea.scrollbar_state(), (0, 0, 20));
+    ///
+    /// // Let's say the textarea is rendered in an area whose height is 8
+    /// # let r = Rect { x: 0, y: 0, width: 24, height: 8 };
+    /// # let mut b = Buffer::empty(r.clone());
+    /// # textarea.widget().render(r, &mut b);
+    /// assert_eq!(textarea.scrollbar_state(), (0, 8, 20));
+    ///
+    /// textarea.scroll((5, 0));
+    /// assert_eq!(textarea.scrollbar_state(), (5, 8, 20));
+    /// ```
+    pub fn scrollbar_state(&self) -> (usize, usize, usize) {
+        let (top, _, _, height) = self.viewport.rect();
+        (top as usize, height as usize, self.lines.len())
+    }
+
+    /// Get the area where the text was rendered on the last render as `(x, y, width, height)`. The area is inside the
+    /// block set by [`TextArea::set_block`]. Since the area is stored while rendering, it reflects the previous frame
+    /// and it is `(0, 0, 0, 0)` before the textarea is rendered first.
+    /// ```
+    /// # use tui::buffer::Buffer;
+    /// # use tui::layout::Rect;
+    /// # use tui::widgets::Widget;
+    /// use tui::widgets::{Block, Borders};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.viewport_rect(), (0, 0, 0, 0));
+    ///
+    /// textarea.set_block(Block::default().borders(Borders::ALL));
+    /// // Let's say the textarea is rendered in an area at (2, 1) whose size is 24x8
+    /// # let r = Rect { x: 2, y: 1, width: 24, height: 8 };
+    /// # let mut b = Buffer::empty(r.clone());
+    /// # textarea.widget().render(r, &mut b);
+    /// assert_eq!(textarea.viewport_rect(), (3, 2, 22, 6));
+    /// ```
+    pub fn viewport_rect(&self) -> (u16, u16, u16, u16) {
+        let (x, y) = self.viewport.origin();
+        let (_, _, width, height) = self.viewport.rect();
+        (x, y, width, height)
+    }
+
+    /// Get the range of rows of the lines on screen on the last render. It is useful to load data lazily only for the
+    /// visible lines. When lines are wrapped by [`TextArea::set_wrap`], the last line in the range may be partially
+    /// visible. Like [`TextArea::viewport_rect`], it reflects the previous frame and the range is empty before the
+    /// textarea is rendered first.
+    /// ```
+    /// # use tui::buffer::Buffer;
+    /// # use tui::layout::Rect;
+    /// # use tui::widgets::Widget;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea: TextArea = (0..20).map(|i| i.to_string()).collect();
+    /// assert_eq!(textarea.viewport_visible_rows(), 0..0);
+    ///
+    /// // Let's say the textarea is rendered in an area whose height is 8
+    /// # let r = Rect { x: 0, y: 0, width: 24, height: 8 };
+    /// # let mut b = Buffer::empty(r.clone());
+    /// # textarea.widget().render(r, &mut b);
+    /// assert_eq!(textarea.viewport_visible_rows(), 0..8);
+    ///
+    /// textarea.scroll((15, 0));
+    /// # textarea.widget().render(r, &mut b);
+    /// assert_eq!(textarea.viewport_visible_rows(), 12..20);
+    /// ```
+    pub fn viewport_visible_rows(&self) -> Range<usize> {
+        let (top, _, width, height) = self.viewport.rect();
+        let top = cmp::min(top as usize, self.lines.len());
+        let height = height as usize;
+        let wrapper = if let Some(w) = self.wrapper(width) {
+            w
+        } else {
+            return top..cmp::min(top + height, self.lines.len());
+        };
+
+        // Count visual rows of the wrapped lines until the viewport is filled
+        let (mut bottom, mut rows) = (top, 0);
+        while bottom < self.lines.len() && rows < height {
+            let line = &self.lines[bottom];
+            let cursor_at_end = bottom == self.cursor.0 && line.chars().count() <= self.cursor.1;
+            rows += wrapper.rows(line, cursor_at_end);
+            bottom += 1;
+        }
+        top..bottom
+    }
+
+    /// Get the absolute position of the cursor on terminal as `(x, y)` when the textarea is rendered in the `area`. The
+    /// `area` includes the block set by [`TextArea::set_block`] as the area passed to the widget. It is useful to put
+    /// your own popup such as a completion menu at the cursor. Like [`TextArea::viewport_rect`], the position is
+    /// calculated with the scroll position of the last render. This method returns `None` when the cursor is out of the
+    /// viewport.
+    /// ```
+    /// # use tui::buffer::Buffer;
+    /// # use tui::widgets::Widget;
+    /// use tui::layout::Rect;
+    /// use tui::widgets::{Block, Borders};
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea: TextArea = (0..20).map(|i| format!("line {}", i)).collect();
+    /// textarea.set_block(Block::default().borders(Borders::ALL));
+    /// let area = Rect { x: 2, y: 1, width: 24, height: 8 };
+    ///
+    /// textarea.move_cursor(CursorMove::Jump(3, 5));
+    /// # let mut b = Buffer::empty(area);
+    /// # textarea.widget().render(area, &mut b);
+    /// assert_eq!(textarea.cursor_screen_position(area), Some((8, 5)));
+    ///
+    /// // The cursor line is scrolled out
+    /// textarea.scroll((10, 0));
+    /// textarea.move_cursor(CursorMove::Jump(3, 5));
+    /// assert_eq!(textarea.cursor_screen_position(area), None);
+    /// ```
+    pub fn cursor_screen_position(&self, area: Rect) -> Option<(u16, u16)> {
+        let inner = self.inner_size(area);
+        let (top_row, top_col, _, _) = self.viewport.rect();
+        let (top_row, top_col) = (top_row as usize, top_col as usize);
+        let (width, height) = (inner.width as usize, inner.height as usize);
+        let (row, col) = self.cursor;
+        if row < top_row {
+            return None;
+        }
+
+        let (x, y) = if let Some(wrapper) = self.wrapper(inner.width) {
+            // Lines are never scrolled horizontally while wrapping
+            let mut y = 0;
+            for line in &self.lines[top_row..row] {
+                y += wrapper.rows(line, false);
+                if y >= height {
+                    return None;
+                }
+            }
+            // Each visual row is aligned separately
+            let line = &self.lines[row];
+            let (dy, dx) = wrapper.position(line, col);
+            let w = wrapper.row_width(line, dy, line.chars().count() <= col);
+            (
+                self.alignment_offset(width, w) + self.gutter_width() + dx,
+                y + dy,
+            )
+        } else {
+            let x = match self.alignment {
+                Alignment::Left => self.screen_col(row, col).checked_sub(top_col)?,
+                _ => {
+                    // The same offset as `position_at`. The cursor at end of line occupies one more cell
+                    let cursor_at_end = self.lines[row].chars().count() <= col;
+                    let w = self.line_display_width(row) + cursor_at_end as usize;
+                    self.alignment_offset(width, w) + self.screen_col(row, col)
+                }
+            };
+            (x, row - top_row)
+        };
+
+        if x >= width || y >= height {
+            return None;
+        }
+        Some((inner.x + x as u16, inner.y + y as u16))
     }
 }
 
@@ -1670,7 +5502,7 @@ mod tests {
         use crate::tui::layout::Rect;
         use crate::tui::widgets::Widget;
 
-        let mut textarea: TextArea = (0..20).into_iter().map(|i| i.to_string()).collect();
+        let mut textarea: TextArea = (0..30).into_iter().map(|i| i.to_string()).collect();
         let r = Rect {
             x: 0,
             y: 0,
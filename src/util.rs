@@ -1,3 +1,8 @@
+use std::collections::HashMap;
+use std::mem;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthChar;
+
 pub fn spaces(size: u8) -> &'static str {
     const SPACES: &str = "                                                                                                                                                                                                                                                                ";
     &SPACES[..size as usize]
@@ -6,3 +11,84 @@ pub fn spaces(size: u8) -> &'static str {
 pub fn num_digits(i: usize) -> u8 {
     f64::log10(i as f64) as u8 + 1
 }
+
+// Width of a tab character at the visual column. A tab advances the column to the next tab stop
+pub fn tab_width(col: usize, tab_len: u8) -> usize {
+    if tab_len == 0 {
+        0
+    } else {
+        tab_len as usize - col % tab_len as usize
+    }
+}
+
+// Width of the character at the visual column on terminal
+pub fn char_width(c: char, col: usize, tab_len: u8) -> usize {
+    if c == '\t' {
+        tab_width(col, tab_len)
+    } else {
+        c.width().unwrap_or(0)
+    }
+}
+
+// Visual column of the character-wise column in the line
+pub fn display_col(line: &str, col: usize, tab_len: u8) -> usize {
+    line.chars()
+        .take(col)
+        .fold(0, |w, c| w + char_width(c, w, tab_len))
+}
+
+// Character-wise column of the character rendered at the visual column in the line. It is the inverse of
+// `display_col`. Visual columns past the end of line are clamped to the end of line
+pub fn char_col(line: &str, display_col: usize, tab_len: u8) -> usize {
+    let mut w = 0;
+    for (i, c) in line.chars().enumerate() {
+        w += char_width(c, w, tab_len);
+        if display_col < w {
+            return i;
+        }
+    }
+    line.chars().count()
+}
+
+// Character-wise columns of grapheme cluster boundaries in the line, including the end of line
+fn grapheme_cols(line: &str) -> impl Iterator<Item = usize> + '_ {
+    line.graphemes(true).scan(0, |col, g| {
+        *col += g.chars().count();
+        Some(*col)
+    })
+}
+
+// Character-wise column of the next grapheme cluster boundary after the column. When the column is at end of line, the
+// column is returned as-is
+pub fn next_grapheme_col(line: &str, col: usize) -> usize {
+    grapheme_cols(line).find(|&c| c > col).unwrap_or(col)
+}
+
+// Character-wise column of the previous grapheme cluster boundary before the column. When the column is at head of
+// line, 0 is returned
+pub fn prev_grapheme_col(line: &str, col: usize) -> usize {
+    grapheme_cols(line)
+        .take_while(|&c| c < col)
+        .last()
+        .unwrap_or(0)
+}
+
+// Shift the rows of the values attached to rows when the removed rows from the row are replaced with the inserted rows.
+// Values attached to the removed rows are dropped
+pub fn shift_rows<T>(map: &mut HashMap<usize, T>, (row, removed, inserted): (usize, usize, usize)) {
+    if removed == 0 && inserted == 0 {
+        return;
+    }
+    *map = mem::take(map)
+        .into_iter()
+        .filter_map(|(r, v)| {
+            if r < row {
+                Some((r, v))
+            } else if r < row + removed {
+                None
+            } else {
+                Some((r - removed + inserted, v))
+            }
+        })
+        .collect();
+}
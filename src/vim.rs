@@ -0,0 +1,438 @@
+use crate::cursor::CursorMove;
+use crate::input::{Input, Key};
+use crate::scroll::Scrolling;
+use crate::textarea::TextArea;
+use std::fmt;
+
+/// Mode of the Vim-like key mappings by [`Vim`].
+///
+/// This type is marked as `#[non_exhaustive]` since the variants depend on enabled features.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VimMode {
+    /// Mode to move the cursor and run commands. This is the initial mode.
+    Normal,
+    /// Mode to insert text. Inputs are handled by the default key mappings of [`TextArea::input`].
+    Insert,
+    /// Mode to select text from the position where the mode started to the cursor.
+    Visual,
+    /// Mode to type a search pattern after `/`. The pattern is available via [`Vim::search_input`].
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    Search,
+}
+
+impl Default for VimMode {
+    fn default() -> Self {
+        Self::Normal
+    }
+}
+
+impl fmt::Display for VimMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Normal => write!(f, "NORMAL"),
+            Self::Insert => write!(f, "INSERT"),
+            Self::Visual => write!(f, "VISUAL"),
+            #[cfg(feature = "search")]
+            Self::Search => write!(f, "SEARCH"),
+        }
+    }
+}
+
+/// State machine of Vim-like modal key mappings for [`TextArea`]. Inputs are translated into method calls of
+/// [`TextArea`] following the current mode. The following keys are supported.
+///
+/// - Normal mode
+///   - Motions: `h`, `j`, `k`, `l`, `w`, `b`, `0`, `^`, `$`, `{`, `}`, `gg`, `G` and arrow keys
+///   - Edits: `x`, `X`, `D`, `C`, `J`, `p`, `P`, `u`, `Ctrl+R`
+///   - Operators: `d`, `y` and `c` followed by a motion, or `dd`, `yy` and `cc` for the current line
+///   - Entering insert mode: `i`, `a`, `I`, `A`, `o`, `O`
+///   - Scrolling: `Ctrl+E`, `Ctrl+Y`, `Ctrl+D`, `Ctrl+U`, `Ctrl+F`, `Ctrl+B`, `zz`, `zt`, `zb`
+///   - Visual mode: `v`
+///   - Search: `/`, `n`, `N` (only when `search` feature is enabled)
+/// - Insert mode: `Esc` goes back to normal mode. Other inputs are handled by [`TextArea::input`]
+/// - Visual mode: Motions extend the selection. `y`, `d`, `x`, `c`, `>` and `<` operate on the selection. `Esc` or `v`
+///   goes back to normal mode
+/// - Search mode: `Enter` searches the typed pattern forward. `Esc` cancels it
+///
+/// Since the selection of [`TextArea`] is exclusive at the cursor, the character under the cursor is not included in
+/// the selection in visual mode. Yanked text ending with a newline such as text yanked by `yy` or `dd` is pasted as
+/// lines by `p` and `P`. When [`TextArea::set_max_chars`] or [`TextArea::set_max_lines`] is set, only the lines
+/// fitting in the limits are pasted. As Vim does, the cursor in normal mode is put on a character and never after the last
+/// character of a line, and `x`, `D`, `C` and `cc` never join lines. Edits are ignored while the textarea is
+/// read-only.
+/// ```
+/// use tui_textarea::{TextArea, Input, Key, Vim, VimMode};
+///
+/// let mut textarea = TextArea::from(["hello", "world"]);
+/// let mut vim = Vim::new();
+///
+/// let key = |c| Input { key: Key::Char(c), ctrl: false, alt: false, shift: false };
+///
+/// // Delete the first line and paste it after the second line
+/// vim.input(&mut textarea, key('d'));
+/// vim.input(&mut textarea, key('d'));
+/// vim.input(&mut textarea, key('p'));
+/// assert_eq!(textarea.lines(), ["world", "hello"]);
+///
+/// // Insert text
+/// vim.input(&mut textarea, key('i'));
+/// assert_eq!(vim.mode(), VimMode::Insert);
+/// vim.input(&mut textarea, key('!'));
+/// assert_eq!(textarea.lines(), ["world", "!hello"]);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Vim {
+    mode: VimMode,
+    pending: Option<char>, // Operator or prefix key waiting for the next key such as `d` of `dw`
+    #[cfg(feature = "search")]
+    search: String,
+}
+
+// Cursor move for the motion key in normal mode and visual mode
+fn motion(input: &Input) -> Option<CursorMove> {
+    if input.ctrl || input.alt {
+        return None;
+    }
+    let m = match input.key {
+        Key::Char('h') | Key::Left => CursorMove::Back,
+        Key::Char('j') | Key::Down => CursorMove::Down,
+        Key::Char('k') | Key::Up => CursorMove::Up,
+        Key::Char('l') | Key::Right => CursorMove::Forward,
+        Key::Char('w') => CursorMove::WordForward,
+        Key::Char('b') => CursorMove::WordBack,
+        Key::Char('0' | '^') | Key::Home => CursorMove::Head,
+        Key::Char('$') | Key::End => CursorMove::End,
+        Key::Char('}') => CursorMove::ParagraphForward,
+        Key::Char('{') => CursorMove::ParagraphBack,
+        Key::Char('G') => CursorMove::Bottom,
+        _ => return None,
+    };
+    Some(m)
+}
+
+impl Vim {
+    /// Create a new state of key mappings in normal mode.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the current mode. This is useful to show the mode in a status line.
+    /// ```
+    /// use tui_textarea::{TextArea, Input, Key, Vim, VimMode};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let mut vim = Vim::new();
+    /// assert_eq!(vim.mode(), VimMode::Normal);
+    ///
+    /// vim.input(&mut textarea, Input { key: Key::Char('v'), ctrl: false, alt: false, shift: false });
+    /// assert_eq!(vim.mode(), VimMode::Visual);
+    /// assert_eq!(vim.mode().to_string(), "VISUAL");
+    /// ```
+    pub fn mode(&self) -> VimMode {
+        self.mode
+    }
+
+    /// Get the search pattern being typed in [`VimMode::Search`]. This method returns `None` in other modes.
+    #[cfg(feature = "search")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "search")))]
+    pub fn search_input(&self) -> Option<&str> {
+        (self.mode == VimMode::Search).then(|| self.search.as_str())
+    }
+
+    /// Handle the input following the current mode. This method returns if the input modified text contents or not in
+    /// the textarea.
+    pub fn input(&mut self, textarea: &mut TextArea<'_>, input: impl Into<Input>) -> bool {
+        let input = input.into();
+        let version = textarea.version();
+        match self.mode {
+            VimMode::Normal => self.normal(textarea, input),
+            VimMode::Insert => self.insert(textarea, input),
+            VimMode::Visual => self.visual(textarea, input),
+            #[cfg(feature = "search")]
+            VimMode::Search => self.search(textarea, input),
+        }
+        if self.mode == VimMode::Normal {
+            clamp_cursor(textarea);
+        }
+        textarea.version() != version
+    }
+
+    fn normal(&mut self, textarea: &mut TextArea<'_>, input: Input) {
+        if let Some(op) = self.pending.take() {
+            self.operator(textarea, op, input);
+            return;
+        }
+        if let Some(m) = motion(&input) {
+            textarea.move_cursor(m);
+            return;
+        }
+
+        let read_only = textarea.is_read_only();
+        if input.ctrl && !input.alt {
+            match input.key {
+                Key::Char('r') if !read_only => {
+                    textarea.redo();
+                }
+                Key::Char('e') => textarea.scroll((1, 0)),
+                Key::Char('y') => textarea.scroll((-1, 0)),
+                Key::Char('d') => textarea.scroll(Scrolling::HalfPageDown),
+                Key::Char('u') => textarea.scroll(Scrolling::HalfPageUp),
+                Key::Char('f') => textarea.scroll(Scrolling::PageDown),
+                Key::Char('b') => textarea.scroll(Scrolling::PageUp),
+                _ => {}
+            }
+            return;
+        }
+        if input.alt {
+            return;
+        }
+
+        match input.key {
+            Key::Char(c @ ('g' | 'z' | 'd' | 'y' | 'c')) => self.pending = Some(c),
+            Key::Char('v') => {
+                textarea.start_selection();
+                self.mode = VimMode::Visual;
+            }
+            #[cfg(feature = "search")]
+            Key::Char('/') => {
+                self.search.clear();
+                self.mode = VimMode::Search;
+            }
+            #[cfg(feature = "search")]
+            Key::Char('n') => {
+                textarea.search_forward(false);
+            }
+            #[cfg(feature = "search")]
+            Key::Char('N') => {
+                textarea.search_back(false);
+            }
+            _ if read_only => {}
+            Key::Char('x') | Key::Delete => {
+                let col = textarea.cursor().1;
+                textarea.delete_str(col, 1);
+            }
+            Key::Char('X') if textarea.cursor().1 > 0 => {
+                textarea.delete_char();
+            }
+            Key::Char('D') => {
+                let col = textarea.cursor().1;
+                textarea.delete_str(col, usize::MAX);
+            }
+            Key::Char('C') => {
+                let col = textarea.cursor().1;
+                textarea.delete_str(col, usize::MAX);
+                self.mode = VimMode::Insert;
+            }
+            Key::Char('J') => {
+                textarea.join_next_line();
+            }
+            Key::Char('p') => paste(textarea, false),
+            Key::Char('P') => paste(textarea, true),
+            Key::Char('u') => {
+                textarea.undo();
+            }
+            Key::Char('i') => self.mode = VimMode::Insert,
+            Key::Char('a') => {
+                textarea.move_cursor(CursorMove::Forward);
+                self.mode = VimMode::Insert;
+            }
+            Key::Char('A') => {
+                textarea.move_cursor(CursorMove::End);
+                self.mode = VimMode::Insert;
+            }
+            Key::Char('I') => {
+                textarea.move_cursor(CursorMove::Head);
+                self.mode = VimMode::Insert;
+            }
+            Key::Char('o') => {
+                textarea.insert_newline_below();
+                self.mode = VimMode::Insert;
+            }
+            Key::Char('O') => {
+                textarea.insert_newline_above();
+                self.mode = VimMode::Insert;
+            }
+            _ => {}
+        }
+    }
+
+    // Handle the key following the pending key such as `w` of `dw`
+    fn operator(&mut self, textarea: &mut TextArea<'_>, op: char, input: Input) {
+        if input.ctrl || input.alt || (matches!(op, 'd' | 'c') && textarea.is_read_only()) {
+            return;
+        }
+        match (op, input.key) {
+            ('g', Key::Char('g')) => textarea.move_cursor(CursorMove::Top),
+            ('z', Key::Char('z')) => textarea.scroll_cursor_center(),
+            ('z', Key::Char('t')) => textarea.scroll_cursor_top(),
+            ('z', Key::Char('b')) => textarea.scroll_cursor_bottom(),
+            ('d', Key::Char('d')) | ('y', Key::Char('y')) | ('c', Key::Char('c')) => {
+                self.operate_line(textarea, op)
+            }
+            ('d' | 'y' | 'c', _) => {
+                if let Some(m) = motion(&input) {
+                    self.operate(textarea, op, m);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Apply the operator to the text from the cursor to the position after the motion
+    fn operate(&mut self, textarea: &mut TextArea<'_>, op: char, m: CursorMove) {
+        textarea.start_selection();
+        textarea.move_cursor(m);
+        match op {
+            'y' => {
+                if let Some(((row, col), _)) = textarea.selection_range() {
+                    textarea.copy();
+                    textarea.move_cursor_to(row, col);
+                }
+            }
+            'c' => {
+                textarea.cut();
+                self.mode = VimMode::Insert;
+            }
+            _ => {
+                textarea.cut();
+            }
+        }
+    }
+
+    // Apply the operator to the current line. The yanked text ends with a newline so that it is pasted as lines
+    fn operate_line(&mut self, textarea: &mut TextArea<'_>, op: char) {
+        let row = textarea.cursor().0;
+        let line = textarea.lines()[row].clone();
+        match op {
+            'y' => {}
+            'c' => {
+                textarea.move_cursor(CursorMove::Head);
+                textarea.delete_str(0, usize::MAX);
+                self.mode = VimMode::Insert;
+            }
+            _ => {
+                textarea.delete_line();
+            }
+        }
+        textarea.set_yank_text(format!("{}\n", line));
+    }
+
+    fn insert(&mut self, textarea: &mut TextArea<'_>, input: Input) {
+        if let Key::Esc = input.key {
+            if textarea.cursor().1 > 0 {
+                textarea.move_cursor(CursorMove::Back);
+            }
+            self.mode = VimMode::Normal;
+        } else {
+            textarea.input(input);
+        }
+    }
+
+    fn visual(&mut self, textarea: &mut TextArea<'_>, input: Input) {
+        if let Some(m) = motion(&input) {
+            textarea.move_cursor(m);
+            return;
+        }
+        if input.ctrl || input.alt {
+            return;
+        }
+
+        let read_only = textarea.is_read_only();
+        match input.key {
+            Key::Esc | Key::Char('v') => textarea.cancel_selection(),
+            Key::Char('y') => {
+                if let Some(((row, col), _)) = textarea.selection_range() {
+                    textarea.copy();
+                    textarea.move_cursor_to(row, col);
+                }
+            }
+            Key::Char('d' | 'x') if !read_only => {
+                textarea.cut();
+            }
+            Key::Char('c') if !read_only => {
+                textarea.cut();
+                self.mode = VimMode::Insert;
+                return;
+            }
+            Key::Char('>') if !read_only => {
+                textarea.indent_lines();
+                textarea.cancel_selection();
+            }
+            Key::Char('<') if !read_only => {
+                textarea.dedent_lines();
+                textarea.cancel_selection();
+            }
+            _ => return,
+        }
+        self.mode = VimMode::Normal;
+    }
+
+    #[cfg(feature = "search")]
+    fn search(&mut self, textarea: &mut TextArea<'_>, input: Input) {
+        match input.key {
+            Key::Enter => {
+                if textarea.set_search_pattern(&self.search).is_ok() {
+                    textarea.search_forward(false);
+                }
+                self.mode = VimMode::Normal;
+            }
+            Key::Esc => self.mode = VimMode::Normal,
+            Key::Backspace => {
+                if self.search.pop().is_none() {
+                    self.mode = VimMode::Normal;
+                }
+            }
+            Key::Char(c) if !input.ctrl && !input.alt => self.search.push(c),
+            _ => {}
+        }
+    }
+}
+
+// Put the cursor on the last character when it is after the end of line in normal mode
+fn clamp_cursor(textarea: &mut TextArea<'_>) {
+    let (row, col) = textarea.cursor();
+    let len = textarea.lines()[row].chars().count();
+    if len > 0 && col >= len {
+        textarea.move_cursor_to(row, len - 1);
+    }
+}
+
+// Paste the yanked text after the cursor, or before the cursor when `before` is true. Text ending with a newline is
+// pasted as lines below or above the current line
+fn paste(textarea: &mut TextArea<'_>, before: bool) {
+    let yank = textarea.yank_text().to_string();
+    let lines = if let Some(lines) = yank.strip_suffix('\n') {
+        lines
+    } else {
+        let (row, col) = textarea.cursor();
+        if !before && col < textarea.lines()[row].chars().count() {
+            textarea.move_cursor(CursorMove::Forward);
+        }
+        textarea.paste();
+        return;
+    };
+
+    // Only the whole lines fitting in the limits are pasted not to join a part of a line with the current line. The
+    // last element is the empty line after the last newline, or the line which doesn't fit in the limits
+    let mut chunk: Vec<String> = lines.split('\n').map(|l| l.to_string()).collect();
+    chunk.push(String::new());
+    textarea.fit_in_limits(&mut chunk);
+    chunk.pop();
+    if chunk.is_empty() {
+        return;
+    }
+    let lines = chunk.join("\n");
+
+    let row = textarea.cursor().0;
+    if before {
+        textarea.move_cursor(CursorMove::Head);
+        textarea.insert_str(format!("{}\n", lines));
+        textarea.move_cursor_to(row, 0);
+    } else {
+        textarea.move_cursor(CursorMove::End);
+        textarea.insert_str(format!("\n{}", lines));
+        textarea.move_cursor_to(row + 1, 0);
+    }
+}
@@ -1,20 +1,29 @@
 use crate::textarea::TextArea;
 use crate::tui::buffer::Buffer;
-use crate::tui::layout::Rect;
+use crate::tui::layout::{Alignment, Rect};
+use crate::tui::style::{Color, Modifier, Style};
 use crate::tui::text::Text;
 use crate::tui::widgets::{Paragraph, Widget};
-use crate::util::{num_digits};
+use crate::util::num_digits;
 
-use ratatui::text::{Line, Span};
+use once_cell::sync::Lazy;
 use std::cmp;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::io::{self, BufRead, Seek};
+use std::path::Path;
+use std::ptr;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
 
 use syntect::{
-    highlighting::{ThemeSet},
-    parsing::SyntaxSet,
+    highlighting::{
+        FontStyle, HighlightState, Highlighter, RangedHighlightIterator, Style as SyntectStyle,
+        Theme, ThemeSet,
+    },
+    parsing::{ParseState, ScopeStack, SyntaxReference, SyntaxSet},
+    LoadingError,
 };
 
-// &mut 'a (u16, u16, u16, u16) is not available since Renderer instance totally takes over the ownership of TextArea
+// &mut 'a (u32, u32, u16, u16) is not available since Renderer instance totally takes over the ownership of TextArea
 // instance. In the case, the TextArea instance cannot be accessed from any other objects since it is mutablly
 // borrowed.
 //
@@ -22,35 +31,48 @@ use syntect::{
 // point we stick with using `tui::terminal::Frame::render_widget` because it is simpler API. Users don't need to
 // manage states of textarea instances separately.
 // https://docs.rs/tui/latest/tui/terminal/struct.Frame.html#method.render_stateful_widget
+//
+// Scroll top position is stored as two u32 values so that texts longer or wider than 65535 can be scrolled. The
+// viewport size is stored separately as two u16 values since terminal size is u16. The position of the viewport on
+// terminal is also stored to map terminal coordinates such as mouse clicks into text positions.
 #[derive(Default)]
-pub struct Viewport(AtomicU64);
+pub struct Viewport {
+    top: AtomicU64,
+    size: AtomicU32,
+    origin: AtomicU32,
+}
 
 impl Clone for Viewport {
     fn clone(&self) -> Self {
-        let u = self.0.load(Ordering::Relaxed);
-        Viewport(AtomicU64::new(u))
+        let top = self.top.load(Ordering::Relaxed);
+        let size = self.size.load(Ordering::Relaxed);
+        let origin = self.origin.load(Ordering::Relaxed);
+        Viewport {
+            top: AtomicU64::new(top),
+            size: AtomicU32::new(size),
+            origin: AtomicU32::new(origin),
+        }
     }
 }
 
 impl Viewport {
-    pub fn scroll_top(&self) -> (u16, u16) {
-        let u = self.0.load(Ordering::Relaxed);
-        ((u >> 16) as u16, u as u16)
+    pub fn scroll_top(&self) -> (u32, u32) {
+        let u = self.top.load(Ordering::Relaxed);
+        ((u >> 32) as u32, u as u32)
     }
 
-    pub fn rect(&self) -> (u16, u16, u16, u16) {
-        let u = self.0.load(Ordering::Relaxed);
-        let width = (u >> 48) as u16;
-        let height = (u >> 32) as u16;
-        let row = (u >> 16) as u16;
-        let col = u as u16;
+    pub fn rect(&self) -> (u32, u32, u16, u16) {
+        let (row, col) = self.scroll_top();
+        let u = self.size.load(Ordering::Relaxed);
+        let width = (u >> 16) as u16;
+        let height = u as u16;
         (row, col, width, height)
     }
 
-    pub fn position(&self) -> (u16, u16, u16, u16) {
+    pub fn position(&self) -> (u32, u32, u32, u32) {
         let (row_top, col_top, width, height) = self.rect();
-        let row_bottom = row_top.saturating_add(height).saturating_sub(1);
-        let col_bottom = col_top.saturating_add(width).saturating_sub(1);
+        let row_bottom = row_top.saturating_add(height as u32).saturating_sub(1);
+        let col_bottom = col_top.saturating_add(width as u32).saturating_sub(1);
 
         (
             row_top,
@@ -60,104 +82,662 @@ impl Viewport {
         )
     }
 
-    fn store(&self, row: u16, col: u16, width: u16, height: u16) {
-        // Pack four u16 values into one u64 value
-        let u =
-            ((width as u64) << 48) | ((height as u64) << 32) | ((row as u64) << 16) | col as u64;
-        self.0.store(u, Ordering::Relaxed);
+    // (x, y) position of the top-left corner of the viewport on terminal
+    pub fn origin(&self) -> (u16, u16) {
+        let u = self.origin.load(Ordering::Relaxed);
+        ((u >> 16) as u16, u as u16)
+    }
+
+    fn store(&self, row: u32, col: u32, area: Rect) {
+        // Pack two u32 values into one u64 value, and two u16 values into one u32 value
+        let top = ((row as u64) << 32) | col as u64;
+        let size = ((area.width as u32) << 16) | area.height as u32;
+        let origin = ((area.x as u32) << 16) | area.y as u32;
+        self.top.store(top, Ordering::Relaxed);
+        self.size.store(size, Ordering::Relaxed);
+        self.origin.store(origin, Ordering::Relaxed);
+    }
+
+    // Scroll vertically so that the row is displayed at the middle of the viewport on the next render. When the
+    // textarea has not been rendered yet, the height is unknown so the row is displayed at the top
+    pub fn center_row(&mut self, row: u32) {
+        let height = *self.size.get_mut() as u16;
+        self.set_top_row(centered_scroll_top(row, height as u32));
+    }
+
+    // Scroll vertically so that the row is displayed at the bottom of the viewport on the next render
+    pub fn bottom_row(&mut self, row: u32) {
+        let height = cmp::max(*self.size.get_mut() as u16, 1);
+        self.set_top_row((row + 1).saturating_sub(height as u32));
+    }
+
+    pub fn set_top_row(&mut self, row: u32) {
+        let u = self.top.get_mut();
+        *u = (*u & 0x0000_0000_ffff_ffff) | ((row as u64) << 32);
     }
 
     pub fn scroll(&mut self, rows: i16, cols: i16) {
-        fn apply_scroll(pos: u16, delta: i16) -> u16 {
+        fn apply_scroll(pos: u32, delta: i16) -> u32 {
             if delta >= 0 {
-                pos.saturating_add(delta as u16)
+                pos.saturating_add(delta as u32)
             } else {
-                pos.saturating_sub(-delta as u16)
+                pos.saturating_sub(delta.unsigned_abs() as u32)
             }
         }
 
-        let u = self.0.get_mut();
-        let row = apply_scroll((*u >> 16) as u16, rows);
-        let col = apply_scroll(*u as u16, cols);
-        *u = (*u & 0xffff_ffff_0000_0000) | ((row as u64) << 16) | (col as u64);
+        let u = self.top.get_mut();
+        let row = apply_scroll((*u >> 32) as u32, rows);
+        let col = apply_scroll(*u as u32, cols);
+        *u = ((row as u64) << 32) | (col as u64);
+    }
+
+    // Clamp the scroll top position not to scroll past the end of the content
+    pub fn clamp(&mut self, max_row: u32, max_col: u32) {
+        let u = self.top.get_mut();
+        let row = cmp::min((*u >> 32) as u32, max_row);
+        let col = cmp::min(*u as u32, max_col);
+        *u = ((row as u64) << 32) | (col as u64);
     }
 }
 
+// Scroll top position which keeps the cursor visible with minimal scroll
+fn next_scroll_top(prev_top: u32, cursor: u32, length: u32) -> u32 {
+    if cursor < prev_top {
+        cursor
+    } else if prev_top + length <= cursor {
+        cursor + 1 - length
+    } else {
+        prev_top
+    }
+}
+
+// Paint the cells at the ruler column on all rows of the area. The column is shifted by line numbers and horizontal
+// scroll as the text is
+fn render_ruler(textarea: &TextArea<'_>, area: Rect, top_col: u32, buf: &mut Buffer) {
+    let col = match textarea.ruler_column() {
+        Some(col) if textarea.alignment() == Alignment::Left => {
+            col as usize + textarea.gutter_width()
+        }
+        _ => return,
+    };
+    let x = match col.checked_sub(top_col as usize) {
+        Some(x) if x < area.width as usize => area.x + x as u16,
+        _ => return, // The ruler is out of the viewport
+    };
+    let style = textarea.ruler_style();
+    for y in area.top()..area.bottom() {
+        buf.get_mut(x, y).set_style(style);
+    }
+}
+
+// Scroll top position which puts the cursor at the middle of the viewport
+fn centered_scroll_top(cursor: u32, length: u32) -> u32 {
+    cursor.saturating_sub(length / 2)
+}
+
+// Scroll top position of the textarea rendered in the area. When lines are wrapped, the viewport always starts at the
+// head of a line and never scrolls horizontally
+fn scroll_top(textarea: &TextArea<'_>, width: u16, height: u16) -> (u32, u32) {
+    let (row, col) = textarea.cursor();
+    let (top_row, top_col) = textarea.viewport.scroll_top();
+    if textarea.wheel_scrolled_cursor == Some((row, col)) {
+        return (top_row, top_col); // The cursor has not moved since the viewport was scrolled by mouse wheel
+    }
+
+    let wrapper = if let Some(w) = textarea.wrapper(width) {
+        w
+    } else {
+        let top_row = next_scroll_top(top_row, row as u32, height as u32);
+        if textarea.alignment() != Alignment::Left {
+            return (top_row, 0); // Paragraph scrolls lines horizontally only when they are aligned to left
+        }
+        let col = textarea.screen_col(row, col);
+        let mut top_col = next_scroll_top(top_col, col as u32, width as u32);
+        // A wide character partially scrolled out at the left edge is not rendered and the rest of the line is shifted
+        // to left. Hide the whole character not to misalign the cursor
+        let left = textarea.col_at_screen_col(row, top_col as usize);
+        if (textarea.screen_col(row, left) as u32) < top_col && top_col < col as u32 {
+            top_col += 1;
+        }
+        return (top_row, top_col);
+    };
+
+    let top_row = top_row as usize;
+    if row < top_row {
+        return (row as u32, 0);
+    }
+
+    // Find the smallest scroll which keeps the visual row of the cursor in the viewport
+    let lines = textarea.lines();
+    let mut rows = wrapper.position(&lines[row], col).0 + 1;
+    let mut top = row;
+    while top > top_row {
+        let r = wrapper.rows(&lines[top - 1], false);
+        if rows + r > height as usize {
+            break;
+        }
+        rows += r;
+        top -= 1;
+    }
+    (top as u32, 0)
+}
+
+// Loading the default syntaxes and themes is heavy. They are loaded only once and shared by all renderers
+pub(crate) static DEFAULT_SYNTAX_SET: Lazy<Arc<SyntaxSet>> =
+    Lazy::new(|| Arc::new(SyntaxSet::load_defaults_nonewlines()));
+pub(crate) static DEFAULT_THEME_SET: Lazy<Arc<ThemeSet>> =
+    Lazy::new(|| Arc::new(ThemeSet::load_defaults()));
+// Generation of syntax sets rebuilt by renderers. The address of a rebuilt set may be reused by the next rebuild so
+// the address alone cannot tell whether the cached highlights are still valid
+static SYNTAX_SET_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+fn loading_error_to_io(err: LoadingError) -> io::Error {
+    match err {
+        LoadingError::Io(err) => err,
+        err => io::Error::new(io::ErrorKind::InvalidData, err),
+    }
+}
+
+/// Color depth used for rendering syntax highlighting. Colors in syntect themes are 24-bit RGB. On terminals which
+/// don't support true colors, they are converted to the nearest colors in the palette.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorDepth {
+    /// 24-bit RGB colors. This is the default.
+    TrueColor,
+    /// 256 colors palette of xterm.
+    Ansi256,
+    /// 16 basic ANSI colors.
+    Ansi16,
+}
+
+impl Default for ColorDepth {
+    fn default() -> Self {
+        Self::TrueColor
+    }
+}
+
+impl ColorDepth {
+    fn quantize(self, color: Color) -> Color {
+        let (r, g, b) = match color {
+            Color::Rgb(r, g, b) => (r, g, b),
+            c => return c,
+        };
+        match self {
+            Self::TrueColor => color,
+            Self::Ansi256 => Color::Indexed(ansi256(r, g, b)),
+            Self::Ansi16 => ansi16(r, g, b),
+        }
+    }
+
+    fn quantize_style(self, mut style: Style) -> Style {
+        if self != Self::TrueColor {
+            style.fg = style.fg.map(|c| self.quantize(c));
+            style.bg = style.bg.map(|c| self.quantize(c));
+        }
+        style
+    }
+}
+
+fn color_distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+    d(r1, r2) + d(g1, g2) + d(b1, b2)
+}
+
+// Nearest color in the 6x6x6 color cube or the grayscale ramp of xterm 256 colors
+fn ansi256(r: u8, g: u8, b: u8) -> u8 {
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    fn level_index(v: u8) -> usize {
+        match v {
+            0..=47 => 0,
+            48..=114 => 1,
+            _ => (v as usize - 35) / 40,
+        }
+    }
+
+    let (ri, gi, bi) = (level_index(r), level_index(g), level_index(b));
+    let cube = (16 + 36 * ri + 6 * gi + bi) as u8;
+    let cube_dist = color_distance((r, g, b), (LEVELS[ri], LEVELS[gi], LEVELS[bi]));
+
+    let avg = (r as usize + g as usize + b as usize) / 3;
+    let gray_index = cmp::min(avg.saturating_sub(3) / 10, 23);
+    let gray_level = (8 + gray_index * 10) as u8;
+    let gray_dist = color_distance((r, g, b), (gray_level, gray_level, gray_level));
+
+    if gray_dist < cube_dist {
+        232 + gray_index as u8
+    } else {
+        cube
+    }
+}
+
+fn ansi16(r: u8, g: u8, b: u8) -> Color {
+    const PALETTE: [(Color, (u8, u8, u8)); 16] = [
+        (Color::Black, (0, 0, 0)),
+        (Color::Red, (128, 0, 0)),
+        (Color::Green, (0, 128, 0)),
+        (Color::Yellow, (128, 128, 0)),
+        (Color::Blue, (0, 0, 128)),
+        (Color::Magenta, (128, 0, 128)),
+        (Color::Cyan, (0, 128, 128)),
+        (Color::Gray, (192, 192, 192)),
+        (Color::DarkGray, (128, 128, 128)),
+        (Color::LightRed, (255, 0, 0)),
+        (Color::LightGreen, (0, 255, 0)),
+        (Color::LightYellow, (255, 255, 0)),
+        (Color::LightBlue, (0, 0, 255)),
+        (Color::LightMagenta, (255, 0, 255)),
+        (Color::LightCyan, (0, 255, 255)),
+        (Color::White, (255, 255, 255)),
+    ];
+    PALETTE
+        .iter()
+        .min_by_key(|(_, rgb)| color_distance((r, g, b), *rgb))
+        .map(|(c, _)| *c)
+        .unwrap()
+}
+
+fn syntect_style(style: SyntectStyle) -> Style {
+    let SyntectStyle {
+        foreground: fg,
+        background: bg,
+        font_style,
+    } = style;
+
+    let mut style = Style::default().fg(Color::Rgb(fg.r, fg.g, fg.b));
+    if bg.a > 0 {
+        style = style.bg(Color::Rgb(bg.r, bg.g, bg.b));
+    }
+    if font_style.contains(FontStyle::BOLD) {
+        style = style.add_modifier(Modifier::BOLD);
+    }
+    if font_style.contains(FontStyle::ITALIC) {
+        style = style.add_modifier(Modifier::ITALIC);
+    }
+    if font_style.contains(FontStyle::UNDERLINE) {
+        style = style.add_modifier(Modifier::UNDERLINED);
+    }
+    style
+}
+
+#[derive(Clone)]
+struct HighlightedLine {
+    ranges: Vec<(usize, usize, Style)>,
+    // States after highlighting this line. They are necessary to resume highlighting the next line
+    parse: ParseState,
+    highlight: HighlightState,
+}
+
+type SyntaxCacheKey = (usize, u64, String, String);
+
+#[derive(Clone, Default)]
+struct SyntaxCacheState {
+    // Syntax set (address and generation), syntax name and theme name which were used for highlighting the cached lines
+    key: Option<SyntaxCacheKey>,
+    lines: Vec<HighlightedLine>,
+    #[cfg(test)]
+    highlight_count: usize,
+}
+
+impl SyntaxCacheState {
+    fn highlight_until(
+        &mut self,
+        lines: &[String],
+        end: usize,
+        syntax_set: &SyntaxSet,
+        syntax: &SyntaxReference,
+        theme: &Theme,
+        key: SyntaxCacheKey,
+    ) {
+        if self.key.as_ref() != Some(&key) {
+            self.lines.clear();
+            self.key = Some(key);
+        }
+        if self.lines.len() >= end {
+            return;
+        }
+
+        let highlighter = Highlighter::new(theme);
+        let (mut parse, mut highlight) = if let Some(last) = self.lines.last() {
+            (last.parse.clone(), last.highlight.clone())
+        } else {
+            (
+                ParseState::new(syntax),
+                HighlightState::new(&highlighter, ScopeStack::new()),
+            )
+        };
+
+        for line in &lines[self.lines.len()..end] {
+            let ops = parse.parse_line(line, syntax_set).unwrap_or_default();
+            let ranges = RangedHighlightIterator::new(&mut highlight, &ops, line, &highlighter)
+                .map(|(style, _, range)| (range.start, range.end, syntect_style(style)))
+                .collect();
+            self.lines.push(HighlightedLine {
+                ranges,
+                parse: parse.clone(),
+                highlight: highlight.clone(),
+            });
+            #[cfg(test)]
+            {
+                self.highlight_count += 1;
+            }
+        }
+    }
+}
+
+// Cache of syntax highlighting results of lines. Highlighting a line depends on the results of all lines before it
+// (e.g. multi-line comments). So when some line is modified, results of the line and all lines after it are
+// invalidated.
+//
+// `Mutex` is used for the same reason as `Viewport` uses an atomic value. The cache is updated while rendering the
+// textarea, where the textarea is immutably borrowed.
+#[derive(Default)]
+pub struct SyntaxCache(Mutex<SyntaxCacheState>);
+
+impl Clone for SyntaxCache {
+    fn clone(&self) -> Self {
+        let state = self
+            .0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        SyntaxCache(Mutex::new(state))
+    }
+}
+
+impl SyntaxCache {
+    pub fn invalidate(&mut self, row: usize) {
+        let state = self.0.get_mut().unwrap_or_else(PoisonError::into_inner);
+        state.lines.truncate(row);
+    }
+}
+
+// Index of the syntax in the syntax set. The syntax must be a reference to an element of the set
+fn syntax_index(syntax_set: &SyntaxSet, syntax: &SyntaxReference) -> usize {
+    syntax_set
+        .syntaxes()
+        .iter()
+        .position(|s| ptr::eq(s, syntax))
+        .unwrap_or(0)
+}
+
+/// A widget to render [`TextArea`] with syntax highlighting powered by [syntect](https://docs.rs/syntect). This widget
+/// is created by [`TextArea::syntax_widget`]. The language to highlight is Rust by default. It can be changed with
+/// [`SyntaxRenderer::language`].
+/// ```
+/// use tui_textarea::TextArea;
+///
+/// let textarea = TextArea::from(["def hello():", "    print('hello')"]);
+/// let widget = textarea.syntax_widget("base16-ocean.dark").language("py");
+/// ```
 pub struct SyntaxRenderer<'a> {
     textarea: &'a TextArea<'a>,
-    syntax_set: SyntaxSet,
-    theme_set: ThemeSet,
+    syntax_set: Arc<SyntaxSet>,
+    theme_set: Arc<ThemeSet>,
     theme: &'a str,
-    // syntax: &'a SyntaxReference,
+    syntax: usize,              // Index of the syntax in the syntax set
+    syntax_set_generation: u64, // Bumped when the syntax set is rebuilt
+    color_depth: ColorDepth,
 }
 
 impl<'a> SyntaxRenderer<'a> {
+    /// Create a new renderer for the textarea with the theme name. The theme must be one of themes loaded by
+    /// [`syntect::highlighting::ThemeSet::load_defaults`]. The default syntaxes and themes are loaded only once on the
+    /// first call and shared by all renderers.
     pub fn new(textarea: &'a TextArea<'a>, theme: &'a str) -> Self {
-        let ps = SyntaxSet::load_defaults_nonewlines();
-        let ts = ThemeSet::load_defaults();
+        Self::with_sets(
+            textarea,
+            DEFAULT_SYNTAX_SET.clone(),
+            DEFAULT_THEME_SET.clone(),
+            theme,
+        )
+    }
+
+    /// Create a new renderer with your own syntax set and theme set. Keep the sets in your application and pass
+    /// clones of the [`Arc`]s on each render to avoid loading them every time. Note that lines in textarea don't
+    /// contain newlines so the syntax set should be loaded with 'nonewlines' mode.
+    /// ```
+    /// use std::sync::Arc;
+    /// use syntect::highlighting::ThemeSet;
+    /// use syntect::parsing::SyntaxSet;
+    /// use tui_textarea::{SyntaxRenderer, TextArea};
+    ///
+    /// let syntax_set = Arc::new(SyntaxSet::load_defaults_nonewlines());
+    /// let theme_set = Arc::new(ThemeSet::load_defaults());
+    ///
+    /// let textarea = TextArea::from(["fn main() {}"]);
+    /// let widget = SyntaxRenderer::with_sets(
+    ///     &textarea,
+    ///     syntax_set.clone(),
+    ///     theme_set.clone(),
+    ///     "base16-ocean.dark",
+    /// );
+    /// ```
+    pub fn with_sets(
+        textarea: &'a TextArea<'a>,
+        syntax_set: Arc<SyntaxSet>,
+        theme_set: Arc<ThemeSet>,
+        theme: &'a str,
+    ) -> Self {
+        let syntax = syntax_index(
+            &syntax_set,
+            syntax_set
+                .find_syntax_by_extension("rs")
+                .unwrap_or_else(|| syntax_set.find_syntax_plain_text()),
+        );
 
         Self {
             textarea,
-            syntax_set: ps,
-            theme_set: ts,
+            syntax_set,
+            theme_set,
             theme,
+            syntax,
+            syntax_set_generation: 0,
+            color_depth: ColorDepth::default(),
         }
     }
 
-    #[inline]
-    fn text(&self, _top_row: usize, _height: usize) -> Text<'a> {
-        Text::default()
-        // let syntax = self.syntax_set.find_syntax_by_extension("rs").unwrap();
-        // let mut h = HighlightLines::new(syntax, &self.theme_set.themes[self.theme]);
-
-        // let lines_len = self.textarea.lines().len();
-        // let lnum_len = num_digits(lines_len);
-        // let bottom_row = cmp::min(top_row + height, lines_len);
-        // let mut lines = Vec::with_capacity(bottom_row - top_row);
-        // for (i, line) in self.textarea.lines()[top_row..bottom_row]
-        //     .iter()
-        //     .enumerate()
-        // {
-        //     let ranges: Vec<(SyntectStyle, &str)> =
-        //         h.highlight_line(line, &self.syntax_set).unwrap();
-        //     let escaped = as_24_bit_terminal_escaped(&ranges[..], true);
-        //     lines.push(escaped.into_spans().unwrap());
-        //     // lines.push(self.textarea.syntax_line_spans(
-        //     //     &mut h,
-        //     //     &self.syntax_set,
-        //     //     line.as_str(),
-        //     //     top_row + i,
-        //     //     lnum_len,
-        //     // ));
-        // }
-        // Text::from(lines)
+    /// Set the language to highlight by file extension (e.g. `"py"`) or by syntax name (e.g. `"Python"`). When no
+    /// syntax is found for the argument, the text is rendered as plain text.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::from(["[package]", "name = \"foo\""]);
+    ///
+    /// // By file extension
+    /// let widget = textarea.syntax_widget("base16-ocean.dark").language("toml");
+    /// // By syntax name
+    /// let widget = textarea.syntax_widget("base16-ocean.dark").language("JSON");
+    /// ```
+    pub fn language(mut self, name_or_ext: &str) -> Self {
+        let syntax_set = &self.syntax_set;
+        let syntax = syntax_set
+            .find_syntax_by_extension(name_or_ext)
+            .or_else(|| syntax_set.find_syntax_by_name(name_or_ext))
+            .unwrap_or_else(|| syntax_set.find_syntax_plain_text());
+        self.syntax = syntax_index(syntax_set, syntax);
+        self
     }
-}
 
-impl<'a> Widget for SyntaxRenderer<'a> {
-    fn render(self, area: Rect, buf: &mut Buffer) {
-        let Rect { width, height, .. } = if let Some(b) = self.textarea.block() {
-            b.inner(area)
-        } else {
-            area
+    /// Set the color depth of syntax highlighting. Colors of the theme are converted into the nearest colors of the
+    /// color depth. The default value is [`ColorDepth::TrueColor`].
+    /// ```
+    /// use tui_textarea::{ColorDepth, TextArea};
+    ///
+    /// let textarea = TextArea::from(["fn main() {}"]);
+    /// let widget = textarea
+    ///     .syntax_widget("base16-ocean.dark")
+    ///     .color_depth(ColorDepth::Ansi256);
+    /// ```
+    pub fn color_depth(mut self, depth: ColorDepth) -> Self {
+        self.color_depth = depth;
+        self
+    }
+
+    /// Load a theme from `.tmTheme` file and register it. The theme is registered with its `name` field. When the
+    /// theme has no name, the file name without extension is used instead. The registered theme can be selected by the
+    /// theme name passed to [`TextArea::syntax_widget`]. Errors on parsing the file are returned as
+    /// [`io::ErrorKind::InvalidData`].
+    ///
+    /// Note that the theme is registered only to this renderer. When creating a renderer on each render, consider
+    /// loading themes into your own [`ThemeSet`] once and passing it to [`SyntaxRenderer::with_sets`].
+    /// ```no_run
+    /// use std::path::Path;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::default();
+    /// let mut widget = textarea.syntax_widget("MyTheme");
+    /// widget.add_theme_from_path(Path::new("MyTheme.tmTheme")).unwrap();
+    /// ```
+    pub fn add_theme_from_path(&mut self, path: &Path) -> io::Result<()> {
+        let theme = ThemeSet::get_theme(path).map_err(loading_error_to_io)?;
+        let name = match &theme.name {
+            Some(name) => name.clone(),
+            None => path
+                .file_stem()
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_default(),
         };
+        Arc::make_mut(&mut self.theme_set)
+            .themes
+            .insert(name, theme);
+        Ok(())
+    }
 
-        fn next_scroll_top(prev_top: u16, cursor: u16, length: u16) -> u16 {
-            if cursor < prev_top {
-                cursor
-            } else if prev_top + length <= cursor {
-                cursor + 1 - length
-            } else {
-                prev_top
+    /// Load a theme in `.tmTheme` format from the reader and register it with its `name` field. Unlike
+    /// [`SyntaxRenderer::add_theme_from_path`], the theme must have its name. Otherwise an error with
+    /// [`io::ErrorKind::InvalidData`] is returned.
+    /// ```
+    /// use std::io::Cursor;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let theme = r#"<?xml version="1.0" encoding="UTF-8"?>
+    /// <plist version="1.0">
+    /// <dict>
+    ///   <key>name</key><string>MyTheme</string>
+    ///   <key>settings</key><array><dict><key>settings</key><dict></dict></dict></array>
+    /// </dict>
+    /// </plist>"#;
+    ///
+    /// let textarea = TextArea::default();
+    /// let mut widget = textarea.syntax_widget("MyTheme");
+    /// widget.add_theme_from_reader(&mut Cursor::new(theme)).unwrap();
+    /// ```
+    pub fn add_theme_from_reader<R: BufRead + Seek>(&mut self, reader: &mut R) -> io::Result<()> {
+        let theme = ThemeSet::load_from_reader(reader).map_err(loading_error_to_io)?;
+        let name = theme.name.clone().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, "name of the theme is not set")
+        })?;
+        Arc::make_mut(&mut self.theme_set)
+            .themes
+            .insert(name, theme);
+        Ok(())
+    }
+
+    /// Load additional syntax definitions from `.sublime-syntax` files in the directory and add them to the syntax set.
+    /// The added syntaxes can be selected with [`SyntaxRenderer::language`]. Call this method before selecting the
+    /// language.
+    ///
+    /// Note that the syntax set is rebuilt on each call. When creating a renderer on each render, consider building
+    /// your own [`SyntaxSet`] once and passing it to [`SyntaxRenderer::with_sets`].
+    /// ```no_run
+    /// use std::path::Path;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let textarea = TextArea::default();
+    /// let mut widget = textarea.syntax_widget("base16-ocean.dark");
+    /// widget.add_syntaxes_from_folder(Path::new("syntaxes")).unwrap();
+    /// let widget = widget.language("zig");
+    /// ```
+    pub fn add_syntaxes_from_folder(&mut self, dir: &Path) -> Result<(), LoadingError> {
+        let mut builder = SyntaxSet::clone(&self.syntax_set).into_builder();
+        builder.add_from_folder(dir, false)?;
+        let syntax_set = builder.build();
+
+        // Indices of syntaxes may change in the new set. Resolve the selected syntax again by its name
+        let syntax = syntax_set
+            .find_syntax_by_name(&self.syntax().name)
+            .unwrap_or_else(|| syntax_set.find_syntax_plain_text());
+        self.syntax = syntax_index(&syntax_set, syntax);
+        self.syntax_set = Arc::new(syntax_set);
+        self.syntax_set_generation = SYNTAX_SET_GENERATION.fetch_add(1, Ordering::Relaxed) + 1;
+        Ok(())
+    }
+
+    fn syntax(&self) -> &SyntaxReference {
+        &self.syntax_set.syntaxes()[self.syntax]
+    }
+
+    #[inline]
+    fn text(&self, top_row: usize, top_col: usize, height: usize, width: u16) -> Text<'a> {
+        let lines = self.textarea.lines();
+        let lnum_len = num_digits(lines.len());
+        let bottom_row = cmp::min(top_row + height, lines.len());
+        let top_row = cmp::min(top_row, bottom_row); // Viewport may be past the end of text after lines were removed
+
+        let mut cache = self
+            .textarea
+            .syntax_cache
+            .0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if let Some(theme) = self.theme_set.themes.get(self.theme) {
+            let syntax = self.syntax();
+            let key = (
+                Arc::as_ptr(&self.syntax_set) as usize,
+                self.syntax_set_generation,
+                syntax.name.clone(),
+                self.theme.to_string(),
+            );
+            cache.highlight_until(lines, bottom_row, &self.syntax_set, syntax, theme, key);
+        } else {
+            cache.lines.clear(); // Unknown theme. Render the text without highlighting
+        }
+
+        let bracket = self.textarea.bracket_to_highlight(top_row..bottom_row);
+        let selection = self.textarea.selection_range();
+        let wrapper = self.textarea.wrapper(width);
+        let fill = width as usize + top_col; // Cursor line is filled to the right edge of the viewport
+        let mut text = Vec::with_capacity(bottom_row - top_row);
+        for (i, line) in lines[top_row..bottom_row].iter().enumerate() {
+            let row = top_row + i;
+            let mut hl = self
+                .textarea
+                .line_highlighter(line, row, lnum_len, bracket, selection);
+            let highlighted = cache.lines.get(row);
+            if let Some(highlighted) = highlighted.filter(|_| !self.textarea.placeholder_shown()) {
+                let depth = self.color_depth;
+                hl.syntax(
+                    highlighted
+                        .ranges
+                        .iter()
+                        .map(|&(s, e, style)| (s, e, depth.quantize_style(style))),
+                );
+            }
+            self.textarea.push_line(
+                &mut text,
+                hl.into_spans(),
+                line,
+                row,
+                wrapper.as_ref(),
+                fill,
+            );
+            if text.len() >= height {
+                text.truncate(height);
+                break;
             }
         }
+        Text::from(text)
+    }
+}
 
-        let cursor = self.textarea.cursor();
-        let (top_row, top_col) = self.textarea.viewport.scroll_top();
-        let top_row = next_scroll_top(top_row, cursor.0 as u16, height);
-        let top_col = next_scroll_top(top_col, cursor.1 as u16, width);
+impl<'a> Widget for SyntaxRenderer<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let inner_area = self.textarea.inner_size(area);
+        let Rect { width, height, .. } = inner_area;
+
+        let (top_row, top_col) = scroll_top(self.textarea, width, height);
 
-        let text = self.text(top_row as usize, height as usize);
+        let text = self.text(top_row as usize, top_col as usize, height as usize, width);
         let mut inner = Paragraph::new(text)
             .style(self.textarea.style())
             .alignment(self.textarea.alignment());
@@ -165,15 +745,15 @@ impl<'a> Widget for SyntaxRenderer<'a> {
             inner = inner.block(b.clone());
         }
         if top_col != 0 {
-            inner = inner.scroll((0, top_col));
+            // Paragraph can scroll horizontally up to u16::MAX columns
+            inner = inner.scroll((0, u16::try_from(top_col).unwrap_or(u16::MAX)));
         }
 
         // Store scroll top position for rendering on the next tick
-        self.textarea
-            .viewport
-            .store(top_row, top_col, width, height);
+        self.textarea.viewport.store(top_row, top_col, inner_area);
 
         inner.render(area, buf);
+        render_ruler(self.textarea, inner_area, top_col, buf);
     }
 }
 
@@ -185,129 +765,40 @@ impl<'a> Renderer<'a> {
     }
 
     #[inline]
-    fn text(&self, top_row: usize, height: usize) -> Text<'a> {
-        let lines = &self.0.text().lines;
-        let mut cursor = self.0.cursor();
-        let cursor_style = self.0.cursor_style();
-        // let style = self.0.cursor_line_style();
-        let num_style = self.0.line_number_style();
-
-        // let style = Style::default()
-        //     .fg(Color::Yellow)
-        //     .add_modifier(Modifier::ITALIC);
-        // let mut raw_text = Text::raw("The first line\nThe second line");
-        // let styled_text = Text::styled(String::from("The first line\nThe second line"), style);
-
-        // raw_text.patch_style(style);
+    fn text(&self, top_row: usize, top_col: usize, height: usize, width: u16) -> Text<'a> {
         let lines_len = self.0.lines().len();
-        let lnum_len = num_digits(lines_len) as usize;
-        cursor.1 += lnum_len + 1;
+        let lnum_len = num_digits(lines_len);
         let bottom_row = cmp::min(top_row + height, lines_len);
-
-        // let row = cursor.0.clamp(top_row as usize, bottom_row as usize);
-        // let row = cursor.0.clamp(top_row as usize, bottom_row as usize);
-        // let row = cmp::min(row, lines.len() - 1);
-        // log::debug!("br {bottom_row}");
-        // let bottom_row = bottom_row - 3;
-
-        // log::debug!(
-        //     "cursor: ({},{}) height: {height} top_row: {top_row}, bottom_row: {bottom_row} min ({}), len {lines_len}",
-        //     cursor.0,
-        //     cursor.1,
-        //     bottom_row.min(lines_len - 1)
-        // );
-
-        let mut text = Text::from(
-            lines[top_row..bottom_row.min(lines.len())]
-                .iter()
-                .enumerate()
-                .map(|(i, line)| {
-                    if let Some(style) = num_style {
-                        let mut new_line = Line::from(Span::styled(
-                            format!("{:lnum_len$} ", top_row + i + 1),
-                            style,
-                        ));
-                        new_line.extend(line.clone().into_iter());
-                        new_line
-                    } else {
-                        line.clone()
-                    }
-                })
-                .collect::<Vec<_>>(),
-        );
-        // let foo = top_row -
-
-        // txt.lines[cursor.0.min(height - 1)].patch_style(style);
-        let roi = (cursor.0 - top_row).clamp(0, lines_len - 1);
-        let mut i = 0;
-        let mut j = 0;
-        // let mut len = 0;
-        let mut target_span = None;
-        // log::debug!("roi: {}", roi);
-        if text.lines.is_empty() {
-            return text;
-        }
-        if roi == text.lines.len() {
-            return text;
-        }
-        for span in &mut text.lines[roi].spans {
-            i += span.content.len();
-            if i >= cursor.1 {
-                // len = span.content.len();
-                target_span = Some(span);
-                // log::debug!("i: {}, j: {}, {}", i, j, span.content);
+        let top_row = cmp::min(top_row, bottom_row); // Viewport may be past the end of text after lines were removed
+        let bracket = self.0.bracket_to_highlight(top_row..bottom_row);
+        let selection = self.0.selection_range();
+        let wrapper = self.0.wrapper(width);
+        let fill = width as usize + top_col; // Cursor line is filled to the right edge of the viewport
+        let mut lines = Vec::with_capacity(bottom_row - top_row);
+        for (i, line) in self.0.lines()[top_row..bottom_row].iter().enumerate() {
+            let row = top_row + i;
+            let spans = self
+                .0
+                .line_spans(line.as_str(), row, lnum_len, bracket, selection);
+            self.0
+                .push_line(&mut lines, spans, line, row, wrapper.as_ref(), fill);
+            if lines.len() >= height {
+                lines.truncate(height);
                 break;
             }
-            j += 1
-        }
-        // break span j at column i into three. The one before the one, and the
-        // one after. Unless it's just a single character. Then we are luckyy
-        // we'll do that one first
-        if let Some(span) = target_span.as_mut() {
-            // log::warn!("span: {:?}", span);
-            // if len == 1 {
-            span.patch_style(cursor_style);
-            // }
-            // text.lines[row - top_row].patch_style(style);
-        }
-        // text.lines[row - top_row].patch_style(style);
-        // text.patch_style(style);u
-        text
-        // let lines_len = self.0.lines().len();
-        // let lnum_len = num_digits(lines_len);
-        // let bottom_row = cmp::min(top_row + height, lines_len);
-        // let mut lines = Vec::with_capacity(bottom_row - top_row);
-        // for (i, line) in self.0.lines()[top_row..bottom_row].iter().enumerate() {
-        //     lines.push(self.0.line_spans(line.as_str(), top_row + i, lnum_len));
-        // }
-        // Text::from(lines)
+        }
+        Text::from(lines)
     }
 }
 
 impl<'a> Widget for Renderer<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let Rect { width, height, .. } = if let Some(b) = self.0.block() {
-            b.inner(area)
-        } else {
-            area
-        };
-
-        fn next_scroll_top(prev_top: u16, cursor: u16, length: u16) -> u16 {
-            if cursor < prev_top {
-                cursor
-            } else if prev_top + length <= cursor {
-                cursor + 1 - length
-            } else {
-                prev_top
-            }
-        }
+        let inner_area = self.0.inner_size(area);
+        let Rect { width, height, .. } = inner_area;
 
-        let cursor = self.0.cursor();
-        let (top_row, top_col) = self.0.viewport.scroll_top();
-        let top_row = next_scroll_top(top_row, cursor.0 as u16, height);
-        let top_col = next_scroll_top(top_col, cursor.1 as u16, width);
+        let (top_row, top_col) = scroll_top(self.0, width, height);
 
-        let text = self.text(top_row as usize, height as usize);
+        let text = self.text(top_row as usize, top_col as usize, height as usize, width);
         let mut inner = Paragraph::new(text)
             .style(self.0.style())
             .alignment(self.0.alignment());
@@ -315,12 +806,1636 @@ impl<'a> Widget for Renderer<'a> {
             inner = inner.block(b.clone());
         }
         if top_col != 0 {
-            inner = inner.scroll((0, top_col));
+            // Paragraph can scroll horizontally up to u16::MAX columns
+            inner = inner.scroll((0, u16::try_from(top_col).unwrap_or(u16::MAX)));
         }
 
         // Store scroll top position for rendering on the next tick
-        self.0.viewport.store(top_row, top_col, width, height);
+        self.0.viewport.store(top_row, top_col, inner_area);
 
         inner.render(area, buf);
+        render_ruler(self.0, inner_area, top_col, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn syntax_highlight() {
+        let textarea = TextArea::from(["fn main() {}"]);
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 24,
+            height: 8,
+        };
+        let mut b = Buffer::empty(r);
+        textarea
+            .syntax_widget("base16-ocean.dark")
+            .render(r, &mut b);
+
+        // Skip the cursor at (0, 0)
+        let cell = b.get(1, 0);
+        assert_eq!(cell.symbol, "n");
+        assert!(matches!(cell.fg, Color::Rgb(..)), "{:?}", cell.fg);
+    }
+
+    #[test]
+    fn syntax_language() {
+        let textarea = TextArea::default();
+        for (lang, name) in [
+            ("py", "Python"),
+            ("Python", "Python"),
+            ("json", "JSON"),
+            ("this-is-not-a-language", "Plain Text"),
+        ] {
+            let renderer = textarea.syntax_widget("base16-ocean.dark").language(lang);
+            assert_eq!(renderer.syntax().name, name, "{:?}", lang);
+        }
+    }
+
+    #[test]
+    fn share_default_sets() {
+        let textarea = TextArea::default();
+        let r1 = textarea.syntax_widget("base16-ocean.dark");
+        let r2 = textarea.syntax_widget("base16-ocean.dark");
+        assert!(Arc::ptr_eq(&r1.syntax_set, &r2.syntax_set));
+        assert!(Arc::ptr_eq(&r1.theme_set, &r2.theme_set));
+    }
+
+    #[test]
+    fn syntax_cache() {
+        fn highlight_count(t: &TextArea<'_>) -> usize {
+            t.syntax_cache.0.lock().unwrap().highlight_count
+        }
+
+        let mut textarea: TextArea = (0..500).map(|i| format!("let x{} = {};", i, i)).collect();
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 24,
+            height: 8,
+        };
+        let mut b = Buffer::empty(r);
+
+        textarea
+            .syntax_widget("base16-ocean.dark")
+            .render(r, &mut b);
+        assert_eq!(highlight_count(&textarea), 8);
+
+        // Rendering the same content again does not highlight any line
+        for _ in 0..3 {
+            textarea
+                .syntax_widget("base16-ocean.dark")
+                .render(r, &mut b);
+        }
+        assert_eq!(highlight_count(&textarea), 8);
+
+        // Modifying a line invalidates the line and lines after it
+        textarea.move_cursor(crate::CursorMove::Jump(5, 0));
+        textarea.insert_char('a');
+        textarea
+            .syntax_widget("base16-ocean.dark")
+            .render(r, &mut b);
+        assert_eq!(highlight_count(&textarea), 8 + 3);
+
+        // Changing theme highlights lines again
+        textarea.syntax_widget("InspiredGitHub").render(r, &mut b);
+        assert_eq!(highlight_count(&textarea), 8 + 3 + 8);
+    }
+
+    #[test]
+    fn add_theme() {
+        use std::io::Cursor;
+
+        const THEME: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+  <key>name</key>
+  <string>Red</string>
+  <key>settings</key>
+  <array>
+    <dict>
+      <key>settings</key>
+      <dict>
+        <key>foreground</key>
+        <string>#FF0000</string>
+      </dict>
+    </dict>
+  </array>
+</dict>
+</plist>"#;
+
+        let textarea = TextArea::from(["abc"]);
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 8,
+            height: 1,
+        };
+        let mut b = Buffer::empty(r);
+
+        let mut renderer = textarea.syntax_widget("Red").language("txt");
+        renderer
+            .add_theme_from_reader(&mut Cursor::new(THEME))
+            .unwrap();
+        assert!(!Arc::ptr_eq(&renderer.theme_set, &DEFAULT_THEME_SET));
+        renderer.render(r, &mut b);
+        assert_eq!(b.get(1, 0).fg, Color::Rgb(255, 0, 0));
+
+        // Theme without name
+        let invalid = THEME
+            .replace("<key>name</key>", "")
+            .replace("<string>Red</string>", "");
+        let mut renderer = textarea.syntax_widget("Red");
+        let err = renderer
+            .add_theme_from_reader(&mut Cursor::new(invalid))
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn add_syntaxes() {
+        use std::fs;
+
+        const SYNTAX: &str = r#"%YAML 1.2
+---
+name: Foo
+file_extensions: [foo]
+scope: source.foo
+contexts:
+  main:
+    - match: '\bfoo\b'
+      scope: keyword.control.foo
+"#;
+
+        let dir =
+            std::env::temp_dir().join(format!("tui-textarea-syntaxes-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("Foo.sublime-syntax"), SYNTAX).unwrap();
+
+        let textarea = TextArea::from(["foo"]);
+        let mut renderer = textarea.syntax_widget("base16-ocean.dark");
+        renderer.add_syntaxes_from_folder(&dir).unwrap();
+
+        // Previously selected syntax is still available
+        assert_eq!(renderer.syntax().name, "Rust");
+        let renderer = renderer.language("foo");
+        assert_eq!(renderer.syntax().name, "Foo");
+        let renderer = renderer.language("Foo");
+        assert_eq!(renderer.syntax().name, "Foo");
+
+        // Cached lines are highlighted again with each rebuilt syntax set
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 8,
+            height: 1,
+        };
+        let mut b = Buffer::empty(r);
+        renderer.render(r, &mut b);
+        for _ in 0..2 {
+            let mut renderer = textarea.syntax_widget("base16-ocean.dark");
+            renderer.add_syntaxes_from_folder(&dir).unwrap();
+            renderer.language("foo").render(r, &mut b);
+        }
+        assert_eq!(textarea.syntax_cache.0.lock().unwrap().highlight_count, 3);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn quantize_ansi16() {
+        for (rgb, want) in [
+            ((0, 0, 0), Color::Black),
+            ((10, 20, 10), Color::Black),
+            ((140, 10, 10), Color::Red),
+            ((230, 30, 40), Color::LightRed),
+            ((20, 120, 30), Color::Green),
+            ((120, 130, 20), Color::Yellow),
+            ((10, 10, 140), Color::Blue),
+            ((0, 120, 130), Color::Cyan),
+            ((130, 130, 130), Color::DarkGray),
+            ((200, 190, 195), Color::Gray),
+            ((250, 250, 250), Color::White),
+            ((250, 240, 30), Color::LightYellow),
+            ((240, 20, 250), Color::LightMagenta),
+        ] {
+            let (r, g, b) = rgb;
+            let have = ColorDepth::Ansi16.quantize(Color::Rgb(r, g, b));
+            assert_eq!(have, want, "{:?}", rgb);
+        }
+
+        // Non-RGB colors are not converted
+        assert_eq!(ColorDepth::Ansi16.quantize(Color::Reset), Color::Reset);
+    }
+
+    #[test]
+    fn quantize_ansi256() {
+        for (rgb, want) in [
+            ((0, 0, 0), 16),
+            ((255, 255, 255), 231),
+            ((255, 0, 0), 196),
+            ((95, 135, 175), 67),
+            ((128, 128, 128), 244),
+            ((18, 18, 18), 233),
+        ] {
+            let (r, g, b) = rgb;
+            let have = ColorDepth::Ansi256.quantize(Color::Rgb(r, g, b));
+            assert_eq!(have, Color::Indexed(want), "{:?}", rgb);
+        }
+        let c = Color::Rgb(1, 2, 3);
+        assert_eq!(ColorDepth::TrueColor.quantize(c), c);
+    }
+
+    #[test]
+    fn match_bracket() {
+        let mut textarea = TextArea::from(["(a)", "[", "]"]);
+        let style = Style::default().bg(Color::Yellow);
+        textarea.set_match_bracket_style(style);
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 8,
+            height: 3,
+        };
+
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        assert_eq!(b.get(2, 0).symbol, ")");
+        assert_eq!(b.get(2, 0).bg, Color::Yellow);
+
+        textarea.move_cursor(crate::CursorMove::Jump(2, 0));
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        assert_eq!(b.get(0, 1).bg, Color::Yellow);
+        assert_ne!(b.get(2, 0).bg, Color::Yellow);
+
+        // Nothing is highlighted when the cursor is not on a bracket
+        textarea.move_cursor(crate::CursorMove::Jump(0, 1));
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        for (x, y) in [(0, 0), (2, 0), (0, 1), (0, 2)] {
+            assert_ne!(b.get(x, y).bg, Color::Yellow, "{:?}", (x, y));
+        }
+    }
+
+    #[test]
+    fn selection() {
+        let mut textarea = TextArea::from(["abc", "def", "ghi"]);
+        let style = Style::default().bg(Color::LightBlue);
+        textarea.set_selection_style(style);
+        textarea.move_cursor(crate::CursorMove::Forward);
+        textarea.start_selection();
+        textarea.move_cursor(crate::CursorMove::Jump(2, 2));
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 8,
+            height: 3,
+        };
+
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        for (x, y) in [(1, 0), (2, 0), (0, 1), (1, 1), (2, 1), (0, 2), (1, 2)] {
+            assert_eq!(b.get(x, y).bg, Color::LightBlue, "{:?}", (x, y));
+        }
+        for (x, y) in [(0, 0), (3, 0), (3, 1), (2, 2)] {
+            assert_ne!(b.get(x, y).bg, Color::LightBlue, "{:?}", (x, y));
+        }
+
+        // Selection is not rendered after it was cancelled
+        textarea.cancel_selection();
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        assert_ne!(b.get(0, 1).bg, Color::LightBlue);
+    }
+
+    #[test]
+    fn block_selection() {
+        let mut textarea = TextArea::from(["abc", "d", "ghi"]);
+        textarea.set_selection_style(Style::default().bg(Color::LightBlue));
+        textarea.set_selection_mode(crate::SelectionMode::Block);
+        textarea.move_cursor(crate::CursorMove::Forward);
+        textarea.start_selection();
+        textarea.move_cursor(crate::CursorMove::Jump(2, 2));
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 8,
+            height: 3,
+        };
+
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        for (x, y) in [(1, 0), (1, 2)] {
+            assert_eq!(b.get(x, y).bg, Color::LightBlue, "{:?}", (x, y));
+        }
+        for (x, y) in [(0, 0), (2, 0), (0, 1), (1, 1), (0, 2), (2, 2)] {
+            assert_ne!(b.get(x, y).bg, Color::LightBlue, "{:?}", (x, y));
+        }
+    }
+
+    #[cfg(feature = "search")]
+    #[test]
+    fn search_current_match() {
+        let mut textarea = TextArea::from(["abc abc", "abc"]);
+        textarea.set_search_pattern("abc").unwrap();
+        textarea.set_search_style(Style::default().bg(Color::Blue));
+        textarea.set_search_current_style(Style::default().bg(Color::Yellow));
+        textarea.search_forward(false);
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 8,
+            height: 2,
+        };
+
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        // (4, 0) is the cursor
+        for (x, y) in [(5, 0), (6, 0)] {
+            assert_eq!(b.get(x, y).bg, Color::Yellow, "{:?}", (x, y));
+        }
+        for (x, y) in [(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (2, 1)] {
+            assert_eq!(b.get(x, y).bg, Color::Blue, "{:?}", (x, y));
+        }
+
+        textarea.search_forward(false);
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        assert_eq!(b.get(1, 1).bg, Color::Yellow);
+        assert_eq!(b.get(5, 0).bg, Color::Blue);
+    }
+
+    #[test]
+    fn move_cursor_to_line_centers() {
+        let mut textarea: TextArea = (0..100).map(|i| i.to_string()).collect();
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 8,
+            height: 10,
+        };
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+
+        assert_eq!(textarea.move_cursor_to_line(50, false), 50);
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        assert_eq!(textarea.viewport.scroll_top(), (45, 0));
+        assert_eq!(b.get(0, 5).symbol, "5");
+        assert_eq!(b.get(1, 5).symbol, "0");
+
+        // The viewport cannot scroll above the first line
+        textarea.move_cursor_to_line(2, false);
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        assert_eq!(textarea.viewport.scroll_top(), (0, 0));
+        assert_eq!(b.get(0, 2).symbol, "2");
+    }
+
+    #[test]
+    fn text_out_of_bounds() {
+        // `TextArea::new` with no line creates one empty line
+        let textarea = TextArea::new(vec![]);
+        assert_eq!(textarea.lines(), [""]);
+        let text = Renderer::new(&textarea).text(0, 0, 10, 8);
+        assert_eq!(text.lines.len(), 1);
+        assert_eq!(Renderer::new(&textarea).text(0, 0, 0, 8).lines.len(), 0);
+
+        // Top row past the end of lines renders nothing instead of panicking
+        let textarea = TextArea::from(["a", "b"]);
+        for top_row in [2, 3, 100] {
+            let text = Renderer::new(&textarea).text(top_row, 0, 10, 8);
+            assert_eq!(text.lines.len(), 0, "{}", top_row);
+            let text = textarea
+                .syntax_widget("InspiredGitHub")
+                .text(top_row, 0, 10, 8);
+            assert_eq!(text.lines.len(), 0, "{}", top_row);
+        }
+    }
+
+    #[test]
+    fn text_borrows_lines() {
+        use std::borrow::Cow;
+
+        // Spans of lines without tabs are rendered without copying the text of the lines
+        let mut textarea = TextArea::from(["hello world", "foo", ""]);
+        textarea.move_cursor(crate::CursorMove::Forward);
+        let text = Renderer::new(&textarea).text(0, 0, 10, 20);
+        assert_eq!(text.lines.len(), 3);
+        for line in &text.lines {
+            for span in &line.spans {
+                assert!(matches!(span.content, Cow::Borrowed(_)), "{:?}", span);
+            }
+        }
+
+        // Tabs are expanded to spaces so the line needs to be copied
+        let textarea = TextArea::from(["\tfoo"]);
+        let text = Renderer::new(&textarea).text(0, 0, 10, 20);
+        let owned = text.lines[0]
+            .spans
+            .iter()
+            .any(|s| matches!(s.content, Cow::Owned(_)));
+        assert!(owned, "{:?}", text);
+    }
+
+    #[test]
+    fn bracket_in_rendered_rows() {
+        let mut lines = vec!["(".to_string()];
+        lines.extend((0..100).map(|i| i.to_string()));
+        lines.push(")".to_string());
+        let mut textarea = TextArea::new(lines);
+        assert_eq!(textarea.bracket_to_highlight(0..102), None); // Not highlighted by default
+
+        textarea.set_match_bracket_style(Style::default().fg(Color::Red));
+        assert_eq!(textarea.bracket_to_highlight(0..8), None);
+        assert_eq!(textarea.bracket_to_highlight(0..102), Some((101, 0)));
+        assert_eq!(textarea.matching_bracket(), Some((101, 0)));
+
+        textarea.move_cursor(crate::CursorMove::Bottom);
+        assert_eq!(textarea.bracket_to_highlight(94..102), None);
+        assert_eq!(textarea.bracket_to_highlight(0..102), Some((0, 0)));
+    }
+
+    #[test]
+    fn visible_rows_with_wrap() {
+        let mut textarea = TextArea::from(["a".repeat(10).as_str(), "b", "c", "d"]);
+        textarea.set_wrap(true);
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 4,
+            height: 4,
+        };
+        let mut b = Buffer::empty(Rect { height: 8, ..r });
+        textarea.widget().render(r, &mut b);
+        assert_eq!(textarea.viewport_visible_rows(), 0..2);
+
+        // The last line is partially visible
+        let r = Rect { height: 2, ..r };
+        textarea.widget().render(r, &mut b);
+        assert_eq!(textarea.viewport_visible_rows(), 0..1);
+        let r = Rect { height: 5, ..r };
+        textarea.widget().render(r, &mut b);
+        assert_eq!(textarea.viewport_visible_rows(), 0..3);
+    }
+
+    #[test]
+    fn cursor_screen_position() {
+        let mut textarea: TextArea = (0..30).map(|i| format!("line {}", i)).collect();
+        textarea.set_line_number_style(Style::default());
+        let r = Rect {
+            x: 5,
+            y: 3,
+            width: 24,
+            height: 8,
+        };
+        let mut b = Buffer::empty(Rect {
+            x: 0,
+            y: 0,
+            width: 40,
+            height: 20,
+        });
+        textarea.widget().render(r, &mut b);
+        textarea.scroll((10, 0));
+        textarea.widget().render(r, &mut b);
+        assert_eq!(textarea.viewport_visible_rows(), 10..18);
+
+        // The cursor is rendered at the position
+        textarea.move_cursor(crate::CursorMove::Jump(12, 3));
+        textarea.widget().render(r, &mut b);
+        assert_eq!(textarea.cursor_screen_position(r), Some((12, 5)));
+        assert!(b.get(12, 5).modifier.contains(Modifier::REVERSED));
+
+        textarea.move_cursor(crate::CursorMove::Jump(17, 0));
+        assert_eq!(textarea.cursor_screen_position(r), Some((9, 10)));
+        textarea.move_cursor(crate::CursorMove::Jump(18, 0));
+        assert_eq!(textarea.cursor_screen_position(r), None);
+        textarea.move_cursor(crate::CursorMove::Jump(9, 0));
+        assert_eq!(textarea.cursor_screen_position(r), None);
+
+        // Wrapped lines above the cursor are counted
+        let mut textarea = TextArea::from(["a".repeat(10).as_str(), "b", "c"]);
+        textarea.set_wrap(true);
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 4,
+            height: 4,
+        };
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        textarea.move_cursor(crate::CursorMove::Jump(0, 5));
+        assert_eq!(textarea.cursor_screen_position(r), Some((1, 1)));
+        textarea.move_cursor(crate::CursorMove::Jump(1, 0));
+        assert_eq!(textarea.cursor_screen_position(r), Some((0, 3)));
+        textarea.move_cursor(crate::CursorMove::Jump(2, 0));
+        assert_eq!(textarea.cursor_screen_position(r), None);
+
+        // Each wrapped row is aligned
+        let mut textarea = TextArea::from(["abcdef"]);
+        textarea.set_wrap(true);
+        textarea.set_alignment(Alignment::Right);
+        for (col, want) in [(1, (1, 0)), (5, (3, 1)), (6, (3, 1))] {
+            textarea.move_cursor(crate::CursorMove::Jump(0, col));
+            let mut b = Buffer::empty(r);
+            textarea.widget().render(r, &mut b);
+            assert_eq!(textarea.cursor_screen_position(r), Some(want), "{}", col);
+            let (x, y) = want;
+            assert!(b.get(x, y).modifier.contains(Modifier::REVERSED), "{}", col);
+        }
+    }
+
+    #[test]
+    fn line_signs() {
+        let mut textarea: TextArea = (0..20).map(|i| i.to_string()).collect();
+        textarea.set_line_number_style(Style::default());
+        textarea.set_line_sign(10, Some(('●', Style::default().fg(Color::Red))));
+        textarea.move_cursor(crate::CursorMove::Jump(15, 0));
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 10,
+            height: 8,
+        };
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        assert_eq!(textarea.scrollbar_state().0, 8);
+
+        let cell = |x, y| {
+            let c = b.get(x, y);
+            (c.symbol.as_str(), c.fg)
+        };
+        // The sign scrolls with its line. Signs, line numbers and text are rendered in this order
+        assert_eq!(cell(0, 2), ("●", Color::Red));
+        assert_eq!(cell(0, 1), (" ", Color::Reset));
+        assert_eq!(cell(0, 3), (" ", Color::Reset));
+        assert_eq!(cell(3, 2), ("1", Color::Reset));
+        assert_eq!(cell(4, 2), ("1", Color::Reset));
+        assert_eq!(cell(6, 2), ("1", Color::Reset));
+        assert_eq!(cell(7, 2), ("0", Color::Reset));
+
+        // The sign is rendered only on the first visual row of the wrapped line
+        let mut textarea = TextArea::from(["abcdefghij"]);
+        textarea.set_wrap(true);
+        textarea.set_line_sign(0, Some(('+', Style::default())));
+        let r = Rect { height: 2, ..r };
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        assert_eq!(b.get(0, 0).symbol, "+");
+        assert_eq!(b.get(2, 0).symbol, "a");
+        assert_eq!(b.get(0, 1).symbol, " ");
+        assert_eq!(b.get(2, 1).symbol, "i");
+    }
+
+    #[test]
+    fn line_signs_follow_lines() {
+        use crate::CursorMove;
+
+        let sign = ('●', Style::default().fg(Color::Red));
+        let signs = |textarea: &TextArea<'_>| {
+            (0..textarea.lines().len())
+                .filter(|r| textarea.line_sign(*r).is_some())
+                .collect::<Vec<_>>()
+        };
+
+        let mut textarea = TextArea::from(["a", "b", "c", "d"]);
+        textarea.set_line_sign(2, Some(sign));
+        textarea.set_line_sign(3, Some(sign));
+
+        // Inserting lines above the sign
+        textarea.move_cursor(CursorMove::Jump(0, 1));
+        textarea.insert_newline();
+        assert_eq!(signs(&textarea), [3, 4]);
+        textarea.insert_str("x\ny");
+        assert_eq!(signs(&textarea), [4, 5]);
+
+        // Splitting the line at its head moves the sign with the text
+        textarea.move_cursor(CursorMove::Jump(4, 0));
+        textarea.insert_newline();
+        assert_eq!(textarea.lines()[5], "c");
+        assert_eq!(signs(&textarea), [5, 6]);
+
+        // Undo and redo move the signs back and forth
+        assert!(textarea.undo());
+        assert_eq!(signs(&textarea), [4, 5]);
+        assert!(textarea.redo());
+        assert_eq!(signs(&textarea), [5, 6]);
+
+        // Deleting lines above the sign
+        let mut textarea = TextArea::from(["a", "b", "c", "d"]);
+        textarea.set_line_sign(2, Some(sign));
+        textarea.move_cursor(CursorMove::Jump(1, 0));
+        textarea.delete_char();
+        assert_eq!(textarea.lines(), ["ab", "c", "d"]);
+        assert_eq!(signs(&textarea), [1]);
+        assert!(textarea.undo());
+        assert_eq!(signs(&textarea), [2]);
+
+        // Signs of deleted lines are removed
+        textarea.set_line_sign(3, Some(sign));
+        textarea.move_cursor(CursorMove::Jump(1, 1));
+        textarea.start_selection();
+        textarea.move_cursor(CursorMove::Jump(3, 0));
+        textarea.cut();
+        assert_eq!(textarea.lines(), ["a", "bd"]);
+        assert!(signs(&textarea).is_empty());
+        assert_eq!(textarea.line_sign(2), None);
+        assert_eq!(textarea.line_sign(3), None);
+    }
+
+    #[test]
+    fn extra_highlights() {
+        let mut textarea = TextArea::from(["let x = y;"]);
+        textarea.set_selection_style(Style::default().bg(Color::Blue));
+        textarea.set_cursor_line_style(Style::default());
+        textarea.add_highlight(0, 0..5, Style::default().fg(Color::Red));
+        textarea.add_highlight(0, 4..6, Style::default().add_modifier(Modifier::UNDERLINED));
+        textarea.add_highlight(0, 8..100, Style::default().fg(Color::Red));
+        textarea.move_cursor(crate::CursorMove::Jump(0, 7));
+        textarea.start_selection();
+        textarea.move_cursor(crate::CursorMove::End);
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 12,
+            height: 1,
+        };
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+
+        let cell = |x| {
+            let c = b.get(x, 0);
+            let underlined = c.modifier.contains(Modifier::UNDERLINED);
+            (c.symbol.as_str(), c.fg, c.bg, underlined)
+        };
+        assert_eq!(cell(3), (" ", Color::Red, Color::Reset, false));
+        // Overlapping highlights are layered
+        assert_eq!(cell(4), ("x", Color::Red, Color::Reset, true));
+        assert_eq!(cell(5), (" ", Color::Reset, Color::Reset, true));
+        assert_eq!(cell(6), ("=", Color::Reset, Color::Reset, false));
+        // Highlights are patched on top of the selection
+        assert_eq!(cell(7), (" ", Color::Reset, Color::Blue, false));
+        assert_eq!(cell(8), ("y", Color::Red, Color::Blue, false));
+        assert_eq!(cell(9), (";", Color::Red, Color::Blue, false));
+
+        textarea.clear_highlights();
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        assert_eq!(b.get(8, 0).fg, Color::Reset);
+    }
+
+    #[test]
+    fn extra_highlights_partially_overlapping() {
+        let mut textarea = TextArea::from(["abcdefg", "xyz"]);
+        textarea.set_selection_style(Style::default().bg(Color::Blue));
+        textarea.set_cursor_line_style(Style::default());
+        textarea.add_highlight(0, 3..6, Style::default().fg(Color::Red));
+        textarea.add_highlight(1, 0..1, Style::default().fg(Color::Red));
+        textarea.move_cursor(crate::CursorMove::Jump(0, 4));
+        textarea.start_selection();
+        textarea.move_cursor(crate::CursorMove::Jump(0, 1));
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 8,
+            height: 1,
+        };
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+
+        let cell = |x| {
+            let c = b.get(x, 0);
+            (c.symbol.as_str(), c.fg, c.bg)
+        };
+        assert_eq!(cell(2), ("c", Color::Reset, Color::Blue));
+        assert_eq!(cell(3), ("d", Color::Red, Color::Blue));
+        // The highlight continues after the selection ends
+        assert_eq!(cell(4), ("e", Color::Red, Color::Reset));
+        assert_eq!(cell(5), ("f", Color::Red, Color::Reset));
+        assert_eq!(cell(6), ("g", Color::Reset, Color::Reset));
+
+        // Highlights move along with their lines
+        textarea.move_cursor(crate::CursorMove::Jump(0, 0));
+        textarea.insert_newline();
+        let r = Rect { height: 3, ..r };
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        assert_eq!(b.get(3, 0).fg, Color::Reset);
+        assert_eq!(b.get(3, 1).fg, Color::Red);
+        assert_eq!(b.get(0, 2).fg, Color::Red);
+
+        // Highlights of deleted lines are removed
+        textarea.move_cursor(crate::CursorMove::Jump(1, 7));
+        textarea.delete_next_char();
+        assert_eq!(textarea.lines(), ["", "abcdefgxyz"]);
+        assert!(textarea.undo());
+        assert_eq!(textarea.lines(), ["", "abcdefg", "xyz"]);
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        assert_eq!(b.get(3, 1).fg, Color::Red);
+        assert_eq!(b.get(0, 2).fg, Color::Reset);
+    }
+
+    #[test]
+    fn render_cursor_beyond_viewport() {
+        let mut textarea: TextArea = (0..20).map(|i| i.to_string()).collect();
+        textarea.move_cursor(crate::CursorMove::Bottom);
+        for (width, height) in [(0, 0), (8, 0), (0, 3), (8, 3)] {
+            let r = Rect {
+                x: 0,
+                y: 0,
+                width,
+                height,
+            };
+            let mut b = Buffer::empty(r);
+            textarea.widget().render(r, &mut b);
+            let mut b = Buffer::empty(r);
+            textarea.syntax_widget("InspiredGitHub").render(r, &mut b);
+        }
+
+        // Lines were removed while the viewport is scrolled to the bottom
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 8,
+            height: 3,
+        };
+        let mut textarea = TextArea::from(["a", "b", "c", "d", "e"]);
+        textarea.move_cursor(crate::CursorMove::Bottom);
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        assert_eq!(textarea.viewport.scroll_top(), (2, 0));
+        textarea.move_cursor(crate::CursorMove::Top);
+        while textarea.lines().len() > 1 {
+            textarea.delete_line_by_end();
+            textarea.delete_next_char();
+        }
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        assert_eq!(textarea.lines(), ["e"]);
+        assert_eq!(b.get(0, 0).symbol, "e");
+    }
+
+    #[test]
+    fn scroll_beyond_u16() {
+        let mut textarea: TextArea = (0..70010).map(|i| i.to_string()).collect();
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 8,
+            height: 10,
+        };
+
+        textarea.move_cursor_to(70000, 0);
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        assert_eq!(textarea.viewport.scroll_top(), (69991, 0));
+        assert_eq!(textarea.viewport.position(), (69991, 0, 70000, 7));
+        for (x, c) in "70000".chars().enumerate() {
+            assert_eq!(b.get(x as u16, 9).symbol, c.to_string());
+        }
+
+        textarea.scroll((-1000, 0));
+        assert_eq!(textarea.viewport.scroll_top(), (68991, 0));
+        assert_eq!(textarea.cursor(), (69000, 0));
+
+        assert_eq!(textarea.move_cursor_to_line(70000, false), 70000);
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        assert_eq!(textarea.viewport.scroll_top(), (69995, 0));
+    }
+
+    #[test]
+    fn wrap_lines() {
+        fn rows(b: &Buffer) -> Vec<String> {
+            let area = b.area();
+            (0..area.height)
+                .map(|y| {
+                    let row: String = (0..area.width)
+                        .map(|x| b.get(x, y).symbol.as_str())
+                        .collect();
+                    row.trim_end().to_string()
+                })
+                .collect()
+        }
+
+        let mut textarea = TextArea::from(["abcdefghij", "abcdefghij", "z"]);
+        textarea.set_wrap(true);
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 4,
+            height: 4,
+        };
+
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        assert_eq!(rows(&b), ["abcd", "efgh", "ij", "abcd"]);
+
+        // Scroll so that the visual row of the cursor is in the viewport
+        textarea.move_cursor(crate::CursorMove::Jump(1, 9));
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        assert_eq!(textarea.viewport.scroll_top(), (1, 0));
+        assert_eq!(rows(&b), ["abcd", "efgh", "ij", "z"]);
+        assert!(b.get(1, 2).modifier.contains(Modifier::REVERSED)); // Cursor
+
+        let mut b = Buffer::empty(r);
+        textarea.syntax_widget("InspiredGitHub").render(r, &mut b);
+        assert_eq!(rows(&b), ["abcd", "efgh", "ij", "z"]);
+
+        // Line numbers are shown only on the first visual row of each line
+        textarea.set_line_number_style(Style::default());
+        textarea.move_cursor(crate::CursorMove::Top);
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 7,
+            height: 4,
+        };
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        assert_eq!(rows(&b), [" 1 abcd", "   efgh", "   ij", " 2 abcd"]);
+
+        // Lines are not wrapped when disabled. Move the cursor to the head so that the viewport is not scrolled
+        // horizontally
+        textarea.move_cursor(crate::CursorMove::Head);
+        textarea.set_wrap(false);
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        assert_eq!(rows(&b), [" 1 abcd", " 2 abcd", " 3 z", ""]);
+
+        // Lines are broken at whitespaces in word wrap mode
+        let mut textarea = TextArea::from(["foo bar baz"]);
+        textarea.set_wrap(true);
+        textarea.set_wrap_mode(crate::WrapMode::Word);
+        textarea.move_cursor(crate::CursorMove::Jump(0, 9));
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 6,
+            height: 3,
+        };
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        assert_eq!(rows(&b), ["foo", "bar", "baz"]);
+        assert!(b.get(1, 2).modifier.contains(Modifier::REVERSED)); // Cursor
+    }
+
+    #[test]
+    fn wide_char_cursor() {
+        let mut textarea = TextArea::from(["日本語test"]);
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 6,
+            height: 1,
+        };
+
+        // The cursor covers both cells of the wide character
+        textarea.move_cursor(crate::CursorMove::Forward);
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        assert_eq!(b.get(2, 0).symbol, "本");
+        assert!(b.get(2, 0).modifier.contains(Modifier::REVERSED));
+        assert!(!b.get(0, 0).modifier.contains(Modifier::REVERSED));
+
+        // Horizontal scroll counts display width. The wide character at the left edge is hidden entirely
+        textarea.move_cursor(crate::CursorMove::End);
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        assert_eq!(textarea.viewport.scroll_top(), (0, 6));
+        let row: String = (0..5).map(|x| b.get(x, 0).symbol.as_str()).collect();
+        assert_eq!(row, "test ");
+        assert!(b.get(4, 0).modifier.contains(Modifier::REVERSED));
+
+        // Moving the cursor into the viewport lands on the leftmost visible character
+        textarea.move_cursor(crate::CursorMove::Head);
+        textarea.move_cursor(crate::CursorMove::InViewport);
+        assert_eq!(textarea.cursor(), (0, 3));
+    }
+
+    #[test]
+    fn line_display_width() {
+        let mut textarea = TextArea::from(["\tab\t", "日本語test", "ｆｕｌｌ\tx", ""]);
+        let want = [8, 10, 13, 0];
+        for (row, want) in want.into_iter().enumerate() {
+            assert_eq!(textarea.line_display_width(row), want, "row {}", row);
+        }
+
+        textarea.set_tab_length(2);
+        assert_eq!(textarea.line_display_width(0), 6);
+        assert_eq!(textarea.line_display_width(2), 11);
+
+        // Line numbers are not included. Masked line has the width of mask characters
+        textarea.set_line_number_style(Style::default());
+        textarea.set_mask_char(Some('●'));
+        assert_eq!(textarea.line_display_width(1), 7);
+
+        // The width matches the rendered line
+        let textarea = TextArea::from(["日本\t語"]);
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 12,
+            height: 1,
+        };
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        assert_eq!(textarea.line_display_width(0), 10);
+        assert_eq!(b.get(8, 0).symbol, "語");
+    }
+
+    #[test]
+    fn ruler() {
+        let mut textarea = TextArea::from(["abcdef", ""]);
+        textarea.set_ruler_column(Some(4));
+        let style = Style::default().bg(Color::Red);
+        textarea.set_ruler_style(style);
+        let r = Rect {
+            x: 1,
+            y: 1,
+            width: 8,
+            height: 3,
+        };
+
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        for y in 1..4 {
+            assert_eq!(b.get(5, y).bg, Color::Red, "y={}", y); // On text, on empty line, and below the last line
+            assert_ne!(b.get(4, y).bg, Color::Red, "y={}", y);
+        }
+        assert_eq!(b.get(5, 1).symbol, "e");
+
+        // The ruler is shifted by line numbers and follows horizontal scroll
+        textarea.set_line_number_style(Style::default());
+        textarea.set_ruler_column(Some(6));
+        textarea.move_cursor(crate::CursorMove::End);
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        assert_eq!(textarea.viewport.scroll_top(), (0, 2));
+        assert_eq!(b.get(1 + 3 + 6 - 2, 2).bg, Color::Red);
+
+        textarea.set_ruler_column(None);
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        assert!((1..9).all(|x| b.get(x, 2).bg != Color::Red));
+    }
+
+    #[test]
+    fn aligned_cursor() {
+        use crate::tui::layout::Alignment;
+        use crate::{CursorMove, Input, Key};
+
+        let mut textarea = TextArea::from(["abc", "de"]);
+        textarea.set_alignment(Alignment::Right);
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 10,
+            height: 2,
+        };
+        let render = |textarea: &TextArea<'_>| {
+            let mut b = Buffer::empty(r);
+            textarea.widget().render(r, &mut b);
+            let cursor = (0..r.height)
+                .flat_map(|y| (0..r.width).map(move |x| (x, y)))
+                .filter(|&(x, y)| b.get(x, y).modifier.contains(Modifier::REVERSED))
+                .collect::<Vec<_>>();
+            (b, cursor)
+        };
+
+        textarea.move_cursor(CursorMove::Forward);
+        let (b, cursor) = render(&textarea);
+        assert_eq!(b.get(7, 0).symbol, "a");
+        assert_eq!(cursor, [(8, 0)]);
+
+        // The cursor at end of line is rendered at the right edge
+        textarea.move_cursor(CursorMove::End);
+        let (b, cursor) = render(&textarea);
+        assert_eq!(b.get(6, 0).symbol, "a");
+        assert_eq!(cursor, [(9, 0)]);
+
+        textarea.set_alignment(Alignment::Center);
+        textarea.move_cursor(CursorMove::Down);
+        let (b, cursor) = render(&textarea);
+        assert_eq!(b.get(4, 0).symbol, "a");
+        assert_eq!(b.get(4, 1).symbol, "d");
+        assert_eq!(cursor, [(6, 1)]);
+
+        // Mouse click is mapped to the aligned text
+        textarea.input(Input {
+            key: Key::MouseClick(5, 0),
+            ..Default::default()
+        });
+        assert_eq!(textarea.cursor(), (0, 1));
+    }
+
+    #[test]
+    fn styles() {
+        let mut textarea = TextArea::from(["abc", "def"]);
+        textarea.set_style(Style::default().fg(Color::Red));
+        textarea.set_cursor_style(Style::default().bg(Color::Green));
+        textarea.set_cursor_line_style(Style::default().bg(Color::Blue));
+        textarea.set_line_number_style(Style::default().fg(Color::Yellow));
+        textarea.set_selection_style(Style::default().bg(Color::Cyan));
+        textarea.move_cursor(crate::CursorMove::Forward);
+        textarea.start_selection();
+        textarea.move_cursor(crate::CursorMove::Forward);
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 10,
+            height: 2,
+        };
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+
+        let style = |x, y| {
+            let c = b.get(x, y);
+            (c.symbol.as_str(), c.fg, c.bg)
+        };
+        assert_eq!(style(1, 0), ("1", Color::Yellow, Color::Reset));
+        assert_eq!(style(1, 1), ("2", Color::Yellow, Color::Reset));
+        // Selection and cursor replace the cursor line style. All are patched on top of the base style
+        assert_eq!(style(3, 0), ("a", Color::Red, Color::Blue));
+        assert_eq!(style(4, 0), ("b", Color::Red, Color::Cyan));
+        assert_eq!(style(5, 0), ("c", Color::Red, Color::Green));
+        assert_eq!(style(9, 0), (" ", Color::Red, Color::Blue));
+        assert_eq!(style(3, 1), ("d", Color::Red, Color::Reset));
+    }
+
+    #[test]
+    fn mouse_click() {
+        use crate::tui::widgets::{Block, Borders};
+        use crate::{Input, Key};
+
+        fn click(textarea: &mut TextArea<'_>, x: u16, y: u16) -> (usize, usize) {
+            textarea.input(Input {
+                key: Key::MouseClick(x, y),
+                ..Default::default()
+            });
+            textarea.cursor()
+        }
+
+        let mut textarea = TextArea::from(["abc", "\tdef", "あいう"]);
+        textarea.set_block(Block::default().borders(Borders::ALL));
+        textarea.set_line_number_style(Style::default());
+        let r = Rect {
+            x: 2,
+            y: 1,
+            width: 16,
+            height: 6,
+        };
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+
+        // Text starts at (6, 2): the area offset, the border, and the line number " 1 "
+        assert_eq!(click(&mut textarea, 7, 2), (0, 1));
+        assert_eq!(click(&mut textarea, 15, 2), (0, 3)); // Past the end of line
+        assert_eq!(click(&mut textarea, 3, 2), (0, 0)); // On line number
+        assert_eq!(click(&mut textarea, 9, 3), (1, 0)); // On tab
+        assert_eq!(click(&mut textarea, 10, 3), (1, 1));
+        assert_eq!(click(&mut textarea, 9, 4), (2, 1)); // On the second cell of a wide character
+        assert_eq!(click(&mut textarea, 9, 5), (2, 1)); // Below the last line
+        assert_eq!(click(&mut textarea, 7, 2), (0, 1));
+
+        // Clicks outside the textarea are ignored, including on the border
+        for (x, y) in [(7, 6), (7, 1), (2, 3), (17, 3), (30, 30)] {
+            assert_eq!(click(&mut textarea, x, y), (0, 1), "{:?}", (x, y));
+        }
+        textarea.input(Input {
+            key: Key::MouseDrag(9, 30),
+            ..Default::default()
+        });
+        assert_eq!(textarea.cursor(), (0, 1));
+        assert_eq!(textarea.selection_range(), None);
+        click(&mut textarea, 9, 4);
+
+        // Clicks with Shift extend the selection
+        textarea.input(Input {
+            key: Key::MouseClick(6, 2),
+            shift: true,
+            ..Default::default()
+        });
+        assert_eq!(textarea.selection_range(), Some(((0, 0), (2, 1))));
+        click(&mut textarea, 6, 2);
+        assert_eq!(textarea.selection_range(), None);
+
+        // Wrapped lines
+        let mut textarea = TextArea::from(["abcdefghij", "z"]);
+        textarea.set_wrap(true);
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 4,
+            height: 4,
+        };
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        assert_eq!(click(&mut textarea, 1, 1), (0, 5));
+        assert_eq!(click(&mut textarea, 3, 2), (0, 10));
+        assert_eq!(click(&mut textarea, 3, 3), (1, 1));
+    }
+
+    #[test]
+    fn mouse_drag() {
+        use crate::{Input, Key};
+
+        fn mouse(textarea: &mut TextArea<'_>, key: Key) {
+            textarea.input(Input {
+                key,
+                ..Default::default()
+            });
+        }
+
+        let mut textarea: TextArea = (0..20).map(|i| format!("line{}", i)).collect();
+        let r = Rect {
+            x: 0,
+            y: 2,
+            width: 8,
+            height: 4,
+        };
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+
+        // Pressing the button starts a selection and dragging extends it
+        mouse(&mut textarea, Key::MouseClick(1, 2));
+        assert_eq!(textarea.selection_range(), None);
+        mouse(&mut textarea, Key::MouseDrag(3, 3));
+        assert_eq!(textarea.selection_range(), Some(((0, 1), (1, 3))));
+        mouse(&mut textarea, Key::MouseRelease(4, 4));
+        assert_eq!(textarea.selection_range(), Some(((0, 1), (2, 4))));
+        assert_eq!(textarea.cursor(), (2, 4));
+
+        // Moving the cursor cancels the selection
+        mouse(&mut textarea, Key::Right);
+        assert_eq!(textarea.selection_range(), None);
+
+        // Releasing the button without dragging selects nothing
+        mouse(&mut textarea, Key::MouseClick(0, 3));
+        mouse(&mut textarea, Key::MouseRelease(0, 3));
+        assert_eq!(textarea.selection_range(), None);
+        assert_eq!(textarea.cursor(), (1, 0));
+
+        // Dragging below the viewport scrolls down
+        mouse(&mut textarea, Key::MouseClick(0, 4));
+        mouse(&mut textarea, Key::MouseDrag(0, 7));
+        assert_eq!(textarea.cursor(), (4, 0));
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        assert_eq!(textarea.viewport.scroll_top(), (1, 0));
+
+        // Dragging above the viewport scrolls up
+        mouse(&mut textarea, Key::MouseDrag(0, 1));
+        assert_eq!(textarea.cursor(), (0, 0));
+        assert_eq!(textarea.selection_range(), Some(((0, 0), (2, 0))));
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        assert_eq!(textarea.viewport.scroll_top(), (0, 0));
+    }
+
+    #[test]
+    fn mouse_read_only() {
+        use crate::{Input, Key};
+
+        let mut textarea: TextArea = (0..20).map(|i| format!("line{}", i)).collect();
+        textarea.set_read_only(true);
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 8,
+            height: 4,
+        };
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+
+        for (key, cursor) in [
+            (Key::MouseClick(2, 1), (1, 2)),
+            (Key::MouseDrag(4, 2), (2, 4)),
+            (Key::MouseRelease(4, 2), (2, 4)),
+        ] {
+            let input = Input {
+                key,
+                ..Default::default()
+            };
+            assert!(!textarea.input(input.clone()));
+            assert_eq!(textarea.cursor(), cursor, "{:?}", key);
+            textarea.move_cursor(crate::CursorMove::Jump(0, 0));
+            assert!(!textarea.input_without_shortcuts(input));
+            assert_eq!(textarea.cursor(), cursor, "{:?}", key);
+        }
+        assert_eq!(textarea.selection_range(), Some(((1, 2), (2, 4))));
+
+        for input in [
+            |textarea: &mut TextArea<'_>, key| textarea.input(key),
+            |textarea: &mut TextArea<'_>, key| textarea.input_without_shortcuts(key),
+        ] {
+            textarea.viewport.scroll(-20, 0);
+            input(
+                &mut textarea,
+                Input {
+                    key: Key::MouseScrollDown,
+                    ..Default::default()
+                },
+            );
+            assert_eq!(textarea.viewport.scroll_top(), (3, 0));
+        }
+        assert_eq!(textarea.lines().len(), 20);
+    }
+
+    #[test]
+    fn mouse_wheel() {
+        use crate::{Input, Key};
+
+        fn wheel(textarea: &mut TextArea<'_>, key: Key) -> (u32, u32) {
+            textarea.input(Input {
+                key,
+                ..Default::default()
+            });
+            let r = Rect {
+                x: 0,
+                y: 0,
+                width: 8,
+                height: 4,
+            };
+            let mut b = Buffer::empty(r);
+            textarea.widget().render(r, &mut b);
+            textarea.viewport.scroll_top()
+        }
+
+        let mut textarea: TextArea = (0..20).map(|i| i.to_string()).collect();
+        assert_eq!(wheel(&mut textarea, Key::Null), (0, 0));
+
+        // The viewport scrolls without moving the cursor
+        assert_eq!(wheel(&mut textarea, Key::MouseScrollDown), (3, 0));
+        assert_eq!(textarea.cursor(), (0, 0));
+        for _ in 0..4 {
+            wheel(&mut textarea, Key::MouseScrollDown);
+        }
+        assert_eq!(textarea.viewport.scroll_top(), (15, 0));
+        // The viewport cannot scroll past the last line
+        assert_eq!(wheel(&mut textarea, Key::MouseScrollDown), (16, 0));
+        assert_eq!(wheel(&mut textarea, Key::MouseScrollUp), (13, 0));
+        assert_eq!(textarea.cursor(), (0, 0));
+
+        // The cursor out of the viewport is not rendered
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 8,
+            height: 4,
+        };
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        for y in 0..4 {
+            for x in 0..8 {
+                assert!(!b.get(x, y).modifier.contains(Modifier::REVERSED));
+            }
+        }
+
+        // The viewport follows the cursor again once it moves
+        assert_eq!(wheel(&mut textarea, Key::Down), (1, 0));
+
+        textarea.set_scroll_lines_per_wheel(1);
+        assert_eq!(wheel(&mut textarea, Key::MouseScrollDown), (2, 0));
+        assert_eq!(wheel(&mut textarea, Key::MouseScrollUp), (1, 0));
+    }
+
+    #[test]
+    fn mouse_wheel_then_cursor_moved_back() {
+        use crate::{CursorMove, Input, Key};
+
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 8,
+            height: 4,
+        };
+        let scroll_down = |textarea: &mut TextArea<'_>| {
+            for _ in 0..4 {
+                textarea.input(Input {
+                    key: Key::MouseScrollDown,
+                    ..Default::default()
+                });
+            }
+            let mut b = Buffer::empty(r);
+            textarea.widget().render(r, &mut b);
+            assert_eq!(textarea.viewport.scroll_top(), (12, 0));
+        };
+        let render = |textarea: &TextArea<'_>| {
+            let mut b = Buffer::empty(r);
+            textarea.widget().render(r, &mut b);
+            textarea.viewport.scroll_top()
+        };
+
+        let mut textarea: TextArea = (0..20).map(|i| i.to_string()).collect();
+
+        // The cursor comes back to the position where the viewport was scrolled by mouse wheel
+        scroll_down(&mut textarea);
+        textarea.move_cursor(CursorMove::Down);
+        textarea.move_cursor(CursorMove::Up);
+        assert_eq!(render(&textarea), (0, 0));
+
+        scroll_down(&mut textarea);
+        textarea.move_cursor(CursorMove::Jump(0, 0));
+        assert_eq!(render(&textarea), (0, 0));
+
+        scroll_down(&mut textarea);
+        textarea.set_text("a\nb");
+        assert_eq!(render(&textarea), (0, 0));
+
+        let mut textarea: TextArea = (0..20).map(|i| i.to_string()).collect();
+        scroll_down(&mut textarea);
+        textarea.insert_char('a');
+        textarea.delete_char();
+        assert_eq!(textarea.cursor(), (0, 0));
+        assert_eq!(render(&textarea), (0, 0));
+    }
+
+    #[test]
+    fn show_whitespace() {
+        let mut textarea = TextArea::from(["a\tb  ", " c"]);
+        textarea.set_tab_length(4);
+        textarea.move_cursor(crate::CursorMove::Bottom);
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 10,
+            height: 2,
+        };
+
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        let row: String = (0..7).map(|x| b.get(x, 0).symbol.as_str()).collect();
+        assert_eq!(row, "a   b  ");
+
+        textarea.set_show_whitespace(true);
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        let row: String = (0..7).map(|x| b.get(x, 0).symbol.as_str()).collect();
+        assert_eq!(row, "a\u{2192}  b\u{b7}\u{b7}");
+        for x in [1, 5, 6] {
+            assert!(b.get(x, 0).modifier.contains(Modifier::DIM), "{}", x);
+        }
+        for x in [0, 4] {
+            assert!(!b.get(x, 0).modifier.contains(Modifier::DIM), "{}", x);
+        }
+
+        // Leading spaces are not trailing spaces
+        assert_eq!(b.get(0, 1).symbol, " ");
+        assert_eq!(b.get(1, 1).symbol, "c");
+    }
+
+    #[test]
+    fn tab_stops() {
+        let mut textarea = TextArea::from(["a\tb", "abc\td", "\t\te", "abcd\tf"]);
+        textarea.set_tab_length(4);
+        textarea.move_cursor(crate::CursorMove::Jump(3, 0));
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 12,
+            height: 4,
+        };
+
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        let rows: Vec<String> = (0..4)
+            .map(|y| (0..9).map(|x| b.get(x, y).symbol.as_str()).collect())
+            .collect();
+        assert_eq!(rows, ["a   b    ", "abc d    ", "        e", "abcd    f"]);
+
+        // Horizontal scroll is computed by the visual column of the cursor
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 6,
+            height: 4,
+        };
+        textarea.move_cursor(crate::CursorMove::End);
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        assert_eq!(textarea.viewport.scroll_top(), (0, 4));
+        assert_eq!(b.get(4, 3).symbol, "f");
+    }
+
+    #[test]
+    fn relative_line_number() {
+        let mut textarea: TextArea = (0..12).map(|i| i.to_string()).collect();
+        textarea.set_line_number_style(Style::default());
+        textarea.set_line_number_relative(true);
+        textarea.move_cursor(crate::CursorMove::Jump(9, 0));
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 8,
+            height: 12,
+        };
+
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        let rows: Vec<String> = (0..12)
+            .map(|y| (0..4).map(|x| b.get(x, y).symbol.as_str()).collect())
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                "  9 ", "  8 ", "  7 ", "  6 ", "  5 ", "  4 ", "  3 ", "  2 ", "  1 ", " 10 ",
+                "  1 ", "  2 ",
+            ],
+        );
+
+        // The cursor line number is aligned to left
+        textarea.move_cursor(crate::CursorMove::Top);
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        let row: String = (0..5).map(|x| b.get(x, 0).symbol.as_str()).collect();
+        assert_eq!(row, " 1  0");
+        let row: String = (0..5).map(|x| b.get(x, 11).symbol.as_str()).collect();
+        assert_eq!(row, " 11 1");
+    }
+
+    #[test]
+    fn cursor_line_number_style() {
+        let mut textarea = TextArea::from(["a", "b", "c"]);
+        textarea.set_line_number_style(Style::default().fg(Color::DarkGray));
+        textarea.move_cursor(crate::CursorMove::Down);
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 8,
+            height: 3,
+        };
+
+        // Falls back to the line number style
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        for y in 0..3 {
+            assert_eq!(b.get(1, y).fg, Color::DarkGray, "{}", y);
+        }
+
+        textarea.set_cursor_line_number_style(Style::default().fg(Color::Yellow));
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        assert_eq!(b.get(1, 0).fg, Color::DarkGray);
+        assert_eq!(b.get(1, 1).fg, Color::Yellow);
+        assert_eq!(b.get(1, 2).fg, Color::DarkGray);
+    }
+
+    #[test]
+    fn cursor_line_background() {
+        let mut textarea = TextArea::from(["ab", "c"]);
+        textarea.set_cursor_line_style(Style::default().bg(Color::Blue));
+
+        let text = Renderer::new(&textarea).text(0, 0, 2, 6);
+        let spans = &text.lines[0].spans;
+        assert_eq!(text.lines[0].width(), 6);
+        assert_eq!(spans[0].content, "a"); // Cursor
+        for span in &spans[1..] {
+            assert_eq!(span.style.bg, Some(Color::Blue), "{:?}", span);
+        }
+        assert_eq!(text.lines[1].width(), 1);
+
+        // Background is extended to the right edge with line numbers and horizontal scroll
+        let mut textarea = TextArea::from(["abcdefghij", "x"]);
+        textarea.set_cursor_line_style(Style::default().bg(Color::Blue));
+        textarea.set_line_number_style(Style::default());
+        textarea.move_cursor(crate::CursorMove::End);
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 8,
+            height: 2,
+        };
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        assert_eq!(textarea.viewport.scroll_top(), (0, 6));
+        assert_eq!(b.get(0, 0).symbol, "d");
+        for x in 0..7 {
+            assert_eq!(b.get(x, 0).bg, Color::Blue, "{}", x);
+        }
+        for x in 0..8 {
+            assert_ne!(b.get(x, 1).bg, Color::Blue, "{}", x);
+        }
+
+        textarea.move_cursor(crate::CursorMove::Down);
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        assert_eq!(textarea.viewport.scroll_top(), (0, 4));
+        assert!(b.get(0, 1).modifier.contains(Modifier::REVERSED)); // Cursor at end of line
+        for x in 1..8 {
+            assert_eq!(b.get(x, 1).bg, Color::Blue, "{}", x);
+        }
+    }
+
+    #[test]
+    fn horizontal_scroll_with_line_numbers() {
+        let mut textarea = TextArea::from(["abcdefghij"]);
+        textarea.set_line_number_style(Style::default());
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 8,
+            height: 1,
+        };
+        let render = |textarea: &TextArea<'_>| {
+            let mut b = Buffer::empty(r);
+            textarea.widget().render(r, &mut b);
+            let cursor = (0..8).find(|&x| b.get(x, 0).modifier.contains(Modifier::REVERSED));
+            let row: String = (0..8).map(|x| b.get(x, 0).symbol.as_str()).collect();
+            (row, cursor)
+        };
+
+        // Line numbers occupy the left of the viewport so the text scrolls before the cursor reaches the right edge
+        textarea.move_cursor(crate::CursorMove::Jump(0, 4));
+        assert_eq!(render(&textarea), (" 1 abcde".to_string(), Some(7)));
+        assert_eq!(textarea.viewport.scroll_top(), (0, 0));
+        textarea.move_cursor(crate::CursorMove::Jump(0, 5));
+        assert_eq!(render(&textarea), ("1 abcdef".to_string(), Some(7)));
+        assert_eq!(textarea.viewport.scroll_top(), (0, 1));
+        textarea.move_cursor(crate::CursorMove::End);
+        assert_eq!(render(&textarea), ("defghij ".to_string(), Some(7)));
+        assert_eq!(textarea.viewport.scroll_top(), (0, 6));
+
+        // Line numbers are scrolled out with the text while the cursor is at the left edge
+        textarea.move_cursor(crate::CursorMove::Head);
+        assert_eq!(render(&textarea), ("abcdefgh".to_string(), Some(0)));
+        assert_eq!(textarea.viewport.scroll_top(), (0, 3));
+    }
+
+    #[test]
+    fn placeholder() {
+        let mut textarea = TextArea::default();
+        textarea.set_placeholder_text("Type here");
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 12,
+            height: 1,
+        };
+
+        for syntax in [false, true] {
+            let mut b = Buffer::empty(r);
+            if syntax {
+                textarea.syntax_widget("InspiredGitHub").render(r, &mut b);
+            } else {
+                textarea.widget().render(r, &mut b);
+            }
+            let row: String = (0..9).map(|x| b.get(x, 0).symbol.as_str()).collect();
+            assert_eq!(row, "Type here", "syntax={}", syntax);
+            // Cursor is rendered at the head of the placeholder
+            assert!(b.get(0, 0).modifier.contains(Modifier::REVERSED));
+            assert_eq!(b.get(1, 0).fg, Color::DarkGray);
+        }
+        assert_eq!(textarea.lines(), [""]);
+
+        // Placeholder disappears once some text is input
+        textarea.insert_char('a');
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        let row: String = (0..9).map(|x| b.get(x, 0).symbol.as_str()).collect();
+        assert_eq!(row.trim_end(), "a");
+
+        textarea.delete_char();
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        assert_eq!(b.get(1, 0).symbol, "y");
+    }
+
+    #[test]
+    fn mask_char() {
+        let mut textarea = TextArea::from(["p\tあ", "abc"]);
+        textarea.set_mask_char(Some('\u{2022}'));
+        textarea.move_cursor(crate::CursorMove::End);
+        let r = Rect {
+            x: 0,
+            y: 0,
+            width: 6,
+            height: 2,
+        };
+
+        let mut b = Buffer::empty(r);
+        textarea.widget().render(r, &mut b);
+        let rows: Vec<String> = (0..2)
+            .map(|y| (0..4).map(|x| b.get(x, y).symbol.as_str()).collect())
+            .collect();
+        // One mask character per character. The cursor is rendered after them
+        assert_eq!(
+            rows,
+            ["\u{2022}\u{2022}\u{2022} ", "\u{2022}\u{2022}\u{2022} "]
+        );
+        assert!(b.get(3, 0).modifier.contains(Modifier::REVERSED));
+
+        // The actual text is not affected
+        assert_eq!(textarea.lines(), ["p\tあ", "abc"]);
+        textarea.start_selection();
+        textarea.move_cursor(crate::CursorMove::Head);
+        assert_eq!(textarea.copy().as_deref(), Some("p\tあ"));
     }
 }
@@ -9,7 +9,7 @@ impl CharKind {
     fn new(c: char) -> Self {
         if c.is_whitespace() {
             Self::Space
-        } else if c.is_ascii_punctuation() {
+        } else if c.is_ascii_punctuation() && c != '_' {
             Self::Punct
         } else {
             Self::Other
@@ -17,6 +17,30 @@ impl CharKind {
     }
 }
 
+// Characters which make words. Words are split by whitespaces and punctuations
+pub fn is_word_char(c: char) -> bool {
+    CharKind::new(c) == CharKind::Other
+}
+
+// Range of the run of characters of the same kind at the column, as (start, end) columns. The end is exclusive
+pub fn find_word_range(line: &str, col: usize) -> Option<(usize, usize)> {
+    let kind = CharKind::new(line.chars().nth(col)?);
+    let mut start = 0;
+    let mut end = 0;
+    for (i, c) in line.chars().enumerate() {
+        let same = CharKind::new(c) == kind;
+        if i < col {
+            if !same {
+                start = i + 1;
+            }
+        } else if !same {
+            break;
+        }
+        end = i + 1;
+    }
+    Some((start, end))
+}
+
 pub fn find_word_start_forward(line: &str, start_col: usize) -> Option<usize> {
     let mut it = line.chars().enumerate().skip(start_col);
     let mut prev = CharKind::new(it.next()?.1);
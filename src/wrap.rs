@@ -0,0 +1,392 @@
+use crate::tui::text::{Line, Span};
+use crate::util::tab_width;
+use unicode_width::UnicodeWidthChar;
+
+/// How lines are broken when soft wrapping is enabled by [`crate::TextArea::set_wrap`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WrapMode {
+    /// Break lines at the character which doesn't fit in the width. This is the default.
+    Char,
+    /// Break lines after the last whitespace before the edge of the width so that words are not split. Words longer
+    /// than the width are broken at the character which doesn't fit.
+    Word,
+}
+
+impl Default for WrapMode {
+    fn default() -> Self {
+        Self::Char
+    }
+}
+
+// Lines are wrapped by cells. A cell is a character on terminal and each tab is rendered as cells of spaces to the next
+// tab stop.
+// Since rendered spans have the same cells as their line, break points computed from the line can be used for
+// splitting the spans.
+pub struct Wrapper {
+    width: usize,
+    tab_len: u8,
+    mode: WrapMode,
+    mask: Option<char>,
+}
+
+impl Wrapper {
+    pub fn new(width: usize, tab_len: u8, mode: WrapMode) -> Self {
+        Self {
+            width,
+            tab_len,
+            mode,
+            mask: None,
+        }
+    }
+
+    // All characters are rendered as the mask character
+    pub fn mask(mut self, mask: Option<char>) -> Self {
+        self.mask = mask;
+        self
+    }
+
+    // (width, is_whitespace) of each cell in the line. When `cursor_at_end` is true, the cursor rendered after the last
+    // character is added as a cell.
+    fn cells(&self, line: &str, cursor_at_end: bool) -> Vec<(usize, bool)> {
+        let mut cells = Vec::with_capacity(line.len() + 1);
+        let mut col = 0;
+        for c in line.chars() {
+            if let Some(m) = self.mask {
+                cells.push((m.width().unwrap_or(0), false));
+            } else if c == '\t' {
+                let w = tab_width(col, self.tab_len);
+                cells.extend((0..w).map(|_| (1, true)));
+                col += w;
+            } else {
+                let w = c.width().unwrap_or(0);
+                cells.push((w, c.is_whitespace()));
+                col += w;
+            }
+        }
+        if cursor_at_end {
+            cells.push((1, true)); // Cursor is not a part of word
+        }
+        cells
+    }
+
+    // Indices of cells which start new visual rows
+    fn breaks(&self, cells: &[(usize, bool)]) -> Vec<usize> {
+        let mut breaks = vec![];
+        let mut row_start = 0;
+        let mut col = 0;
+        for (i, &(w, is_space)) in cells.iter().enumerate() {
+            if col == 0 || col + w <= self.width {
+                col += w;
+                continue;
+            }
+
+            let mut start = i;
+            if self.mode == WrapMode::Word && !is_space {
+                // Move the word at the end of row to the next row
+                if let Some(j) = cells[row_start..i].iter().rposition(|&(_, s)| s) {
+                    let word_start = row_start + j + 1;
+                    let word_width: usize = cells[word_start..=i].iter().map(|&(w, _)| w).sum();
+                    if word_start < i && word_width <= self.width {
+                        start = word_start;
+                    }
+                }
+            }
+
+            breaks.push(start);
+            row_start = start;
+            col = cells[start..=i].iter().map(|&(w, _)| w).sum();
+        }
+        breaks
+    }
+
+    // Map the character-wise column in the line to the visual (row, col) position relative to the head of the line.
+    // The column at the end of line is where the cursor is rendered.
+    pub fn position(&self, line: &str, col: usize) -> (usize, usize) {
+        let cursor_at_end = line.chars().count() <= col;
+        let cells = self.cells(line, cursor_at_end);
+        let breaks = self.breaks(&cells);
+
+        // Cells of the characters before the column
+        let prefix = line.char_indices().nth(col).map(|(i, _)| &line[..i]);
+        let cell = prefix.map_or(cells.len() - 1, |p| self.cells(p, false).len());
+        let row = breaks.iter().take_while(|&&b| b <= cell).count();
+        let row_start = if row == 0 { 0 } else { breaks[row - 1] };
+        let col = cells[row_start..cell].iter().map(|&(w, _)| w).sum();
+        (row, col)
+    }
+
+    // Map the visual (row, col) position relative to the head of the line to the character-wise column in the line. It
+    // is the inverse of `position`. Positions past the end of a visual row are clamped to the last character in the
+    // row, or to the end of line on the last visual row. Positions below the last visual row are mapped to the end of
+    // line.
+    pub fn column(&self, line: &str, row: usize, col: usize) -> usize {
+        let cells = self.cells(line, false);
+        let breaks = self.breaks(&cells);
+        if row > breaks.len() {
+            return line.chars().count(); // The cursor rendered after the last character may be put on the next row
+        }
+        let row_start = if row == 0 { 0 } else { breaks[row - 1] };
+        let row_end = breaks.get(row).copied();
+
+        let mut cell = row_start;
+        let mut w = 0;
+        while cell < row_end.unwrap_or(cells.len()) {
+            w += cells[cell].0;
+            if col < w {
+                break;
+            }
+            cell += 1;
+        }
+        if cell == cells.len() {
+            return line.chars().count();
+        }
+        if Some(cell) == row_end {
+            cell -= 1; // The character at the break is rendered on the next visual row
+        }
+
+        // Find the character of the cell. Tabs occupy multiple cells
+        let mut cells_end = 0;
+        let mut width = 0;
+        for (i, c) in line.chars().enumerate() {
+            if self.mask.is_none() && c == '\t' {
+                let w = tab_width(width, self.tab_len);
+                cells_end += w;
+                width += w;
+            } else {
+                cells_end += 1;
+                width += c.width().unwrap_or(0);
+            }
+            if cell < cells_end {
+                return i;
+            }
+        }
+        line.chars().count()
+    }
+
+    // Width of the visual row of the line in cells
+    pub fn row_width(&self, line: &str, row: usize, cursor_at_end: bool) -> usize {
+        let cells = self.cells(line, cursor_at_end);
+        let breaks = self.breaks(&cells);
+        let start = if row == 0 { 0 } else { breaks[row - 1] };
+        let end = breaks.get(row).copied().unwrap_or(cells.len());
+        cells[start..end].iter().map(|&(w, _)| w).sum()
+    }
+
+    // Number of visual rows of the line
+    pub fn rows(&self, line: &str, cursor_at_end: bool) -> usize {
+        self.breaks(&self.cells(line, cursor_at_end)).len() + 1
+    }
+
+    // Split the rendered spans of the line into visual rows. The first `gutter` spans are a sign and a line number and
+    // they are put only on the first visual row. Other visual rows are indented with spaces in the same styles.
+    pub fn wrap_line<'a>(
+        &self,
+        spans: Line<'a>,
+        line: &str,
+        gutter: usize,
+        cursor_at_end: bool,
+    ) -> Vec<Line<'a>> {
+        let breaks = self.breaks(&self.cells(line, cursor_at_end));
+        let mut breaks = breaks.into_iter().peekable();
+
+        let mut spans = spans.spans.into_iter();
+        let gutter: Vec<_> = spans.by_ref().take(gutter).collect();
+
+        let mut rows = vec![vec![]];
+        let mut cell = 0;
+        for span in spans {
+            let content = span.content.as_ref();
+            let mut start = 0;
+            for (i, _) in content.char_indices() {
+                if breaks.peek() == Some(&cell) {
+                    breaks.next();
+                    if start < i {
+                        let s = content[start..i].to_string();
+                        rows.last_mut().unwrap().push(Span::styled(s, span.style));
+                    }
+                    rows.push(vec![]);
+                    start = i;
+                }
+                cell += 1;
+            }
+            if start == 0 {
+                rows.last_mut().unwrap().push(span);
+            } else if start < content.len() {
+                let s = content[start..].to_string();
+                rows.last_mut().unwrap().push(Span::styled(s, span.style));
+            }
+        }
+
+        rows.into_iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let head = gutter.iter().map(|span| {
+                    if i == 0 {
+                        span.clone()
+                    } else {
+                        Span::styled(" ".repeat(span.width()), span.style)
+                    }
+                });
+                Line::from(head.chain(row).collect::<Vec<_>>())
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Lines in tests must not contain tabs which are not at tab stops
+    fn wrap(line: &str, width: usize, mode: WrapMode) -> Vec<String> {
+        let spans = Line::from(vec![Span::raw(line.replace('\t', "  "))]);
+        Wrapper::new(width, 2, mode)
+            .wrap_line(spans, line, 0, false)
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn position() {
+        for (line, col, width, want) in [
+            ("abcdef", 0, 3, (0, 0)),
+            ("abcdef", 2, 3, (0, 2)),
+            ("abcdef", 3, 3, (1, 0)),
+            ("abcdef", 5, 3, (1, 2)),
+            ("abcdef", 6, 3, (2, 0)), // Cursor at end of line
+            ("abcde", 5, 3, (1, 2)),
+            ("あいう", 1, 3, (1, 0)),
+            ("あいう", 2, 5, (1, 0)),
+            ("aあい", 2, 4, (1, 0)),
+            ("\tab", 1, 3, (0, 2)),
+            ("\tab", 2, 3, (1, 0)),
+            ("a\tb", 2, 10, (0, 2)), // Tab advances to the next tab stop
+            ("abc\td", 4, 10, (0, 4)),
+            ("", 0, 3, (0, 0)),
+        ] {
+            let w = Wrapper::new(width, 2, WrapMode::Char);
+            assert_eq!(w.position(line, col), want, "{:?}", (line, col, width));
+        }
+    }
+
+    #[test]
+    fn column() {
+        for (line, row, col, width, want) in [
+            ("abcdef", 0, 0, 3, 0),
+            ("abcdef", 0, 2, 3, 2),
+            ("abcdef", 0, 5, 3, 2), // Past the end of non-last visual row
+            ("abcdef", 1, 0, 3, 3),
+            ("abcdef", 1, 5, 3, 6), // Past the end of line
+            ("abcdef", 5, 0, 3, 6), // Below the last visual row
+            ("あいう", 1, 1, 5, 2),
+            ("\tab", 0, 1, 3, 0),
+            ("\tab", 0, 2, 3, 1),
+            ("", 0, 3, 3, 0),
+        ] {
+            let w = Wrapper::new(width, 2, WrapMode::Char);
+            assert_eq!(
+                w.column(line, row, col),
+                want,
+                "{:?}",
+                (line, row, col, width)
+            );
+        }
+
+        // `column` is the inverse of `position`
+        let w = Wrapper::new(4, 2, WrapMode::Word);
+        let line = "foo bar\tbazあい";
+        for col in 0..=line.chars().count() {
+            let (r, c) = w.position(line, col);
+            assert_eq!(w.column(line, r, c), col, "{}", col);
+        }
+    }
+
+    #[test]
+    fn rows() {
+        for (line, width, cursor_at_end, want) in [
+            ("", 3, false, 1),
+            ("", 3, true, 1),
+            ("abc", 3, false, 1),
+            ("abc", 3, true, 2),
+            ("abcdefg", 3, false, 3),
+            ("あいう", 5, false, 2),
+            ("\t\t", 3, false, 2),
+            ("abc", 0, false, 3),
+        ] {
+            let w = Wrapper::new(width, 2, WrapMode::Char);
+            assert_eq!(
+                w.rows(line, cursor_at_end),
+                want,
+                "{:?}",
+                (line, width, cursor_at_end)
+            );
+        }
+    }
+
+    #[test]
+    fn row_width() {
+        let w = Wrapper::new(4, 2, WrapMode::Word);
+        assert_eq!(w.row_width("ab cdef", 0, false), 3);
+        assert_eq!(w.row_width("ab cdef", 1, false), 4);
+        assert_eq!(w.row_width("ab cdef", 1, true), 4);
+        assert_eq!(w.row_width("ab cdef", 2, true), 1);
+        assert_eq!(w.row_width("", 0, true), 1);
+    }
+
+    #[test]
+    fn split_spans() {
+        let spans = Line::from(vec![Span::raw(" 1 "), Span::raw("abc"), Span::raw("defあ")]);
+        let rows: Vec<String> = Wrapper::new(3, 4, WrapMode::Char)
+            .wrap_line(spans, "abcdefあ", 1, false)
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert_eq!(rows, [" 1 abc", "   def", "   あ"]);
+
+        assert_eq!(wrap("abcdef", 3, WrapMode::Char), ["abc", "def"]);
+    }
+
+    #[test]
+    fn word_wrap() {
+        for (line, width, want) in [
+            ("foo bar baz", 8, &["foo bar ", "baz"][..]),
+            ("foo bar baz", 7, &["foo bar", " baz"][..]),
+            ("foo bar baz", 5, &["foo ", "bar ", "baz"][..]),
+            ("foobarbaz qux", 4, &["foob", "arba", "z ", "qux"][..]),
+            ("ab\tc", 3, &["ab ", " c"][..]),
+            ("あい うえお", 6, &["あい ", "うえお"][..]),
+        ] {
+            assert_eq!(
+                wrap(line, width, WrapMode::Word),
+                want,
+                "{:?}",
+                (line, width)
+            );
+        }
+    }
+
+    #[test]
+    fn word_wrap_long_url() {
+        let url = format!("https://example.com/{}", "a".repeat(180));
+        assert_eq!(url.len(), 200);
+        let line = format!("see {} for details", url);
+        let w = Wrapper::new(40, 4, WrapMode::Word);
+
+        let rows = wrap(&line, 40, WrapMode::Word);
+        assert_eq!(rows.len(), 7);
+        assert_eq!(rows[0], "see ");
+        assert_eq!(rows[1..6].concat(), url); // The URL is broken at the width
+        assert_eq!(rows[6], " for details");
+        assert_eq!(w.rows(&line, false), rows.len());
+
+        // Positions are consistent with the wrapped rows
+        assert_eq!(w.position(&line, 3), (0, 3));
+        assert_eq!(w.position(&line, 4), (1, 0));
+        assert_eq!(w.position(&line, 4 + 39), (1, 39));
+        assert_eq!(w.position(&line, 4 + 40), (2, 0));
+        assert_eq!(w.position(&line, 4 + 199), (5, 39));
+        assert_eq!(w.position(&line, 4 + 200), (6, 0));
+        assert_eq!(w.position(&line, line.len()), (6, 12));
+    }
+}
@@ -1,4 +1,4 @@
-use tui_textarea::{CursorMove, TextArea};
+use tui_textarea::{CursorMove, Input, Key, TextArea};
 
 const BOTTOM_RIGHT: CursorMove = CursorMove::Jump(u16::MAX, u16::MAX);
 
@@ -279,3 +279,177 @@ fn bottom_trim() {
         assert_eq!(t.cursor(), (t.lines().len() - 1, col), "{:?}", t.lines());
     }
 }
+
+#[test]
+fn move_cursor_to() {
+    let mut t = TextArea::from(["abc", "あいうえお", ""]);
+    for (pos, want) in [
+        ((0, 0), (0, 0)),
+        ((0, 3), (0, 3)),
+        ((0, 4), (0, 3)),
+        ((1, 4), (1, 4)),
+        ((1, usize::MAX), (1, 5)),
+        ((2, 3), (2, 0)),
+        ((usize::MAX, usize::MAX), (2, 0)),
+    ] {
+        assert_eq!(t.move_cursor_to(pos.0, pos.1), want, "{:?}", pos);
+        assert_eq!(t.cursor(), want, "{:?}", pos);
+    }
+
+    let mut t = TextArea::default();
+    assert_eq!(t.move_cursor_to(3, 3), (0, 0));
+}
+
+#[test]
+fn move_word() {
+    for (lines, forward, backward) in [
+        (
+            &["aaa   bbb", "  ccc"][..],
+            &[(0, 6), (1, 0), (1, 2), (1, 5)][..],
+            &[(1, 2), (0, 9), (0, 6), (0, 0)][..],
+        ),
+        (
+            &["日本語 テキスト\u{3000}です"][..],
+            &[(0, 4), (0, 9), (0, 11)][..],
+            &[(0, 9), (0, 4), (0, 0)][..],
+        ),
+        (
+            &["foo_bar, baz_qux"][..],
+            &[(0, 7), (0, 9), (0, 16)][..],
+            &[(0, 9), (0, 7), (0, 0)][..],
+        ),
+    ] {
+        let mut t = TextArea::from(lines.iter().copied());
+        for &pos in forward {
+            t.move_word_forward();
+            assert_eq!(t.cursor(), pos, "{:?}", lines);
+        }
+        for &pos in backward {
+            t.move_word_backward();
+            assert_eq!(t.cursor(), pos, "{:?}", lines);
+        }
+    }
+}
+
+#[test]
+fn move_cursor_top_bottom() {
+    let mut t = TextArea::from(["abc", "de", "fghi"]);
+    t.move_cursor(CursorMove::Jump(1, 1));
+    t.move_cursor_bottom();
+    assert_eq!(t.cursor(), (2, 4));
+    t.move_cursor_top();
+    assert_eq!(t.cursor(), (0, 0));
+
+    let mut t = TextArea::default();
+    t.move_cursor_bottom();
+    assert_eq!(t.cursor(), (0, 0));
+
+    // Ctrl+Home and Ctrl+End are mapped by default
+    let mut t = TextArea::from(["abc", "de"]);
+    for (key, want) in [(Key::End, (1, 2)), (Key::Home, (0, 0))] {
+        t.input(Input {
+            key,
+            ctrl: true,
+            ..Default::default()
+        });
+        assert_eq!(t.cursor(), want, "{:?}", key);
+    }
+}
+
+#[test]
+fn move_cursor_line_start_end() {
+    let mut t = TextArea::from(["  \tabc", "   ", "あいう"]);
+    t.move_cursor_line_end();
+    assert_eq!(t.cursor(), (0, 6));
+    for want in [3, 0, 3] {
+        t.move_cursor_line_start(true);
+        assert_eq!(t.cursor(), (0, want));
+    }
+    t.move_cursor_line_start(false);
+    assert_eq!(t.cursor(), (0, 0));
+
+    // Lines which contain only whitespaces
+    t.move_cursor(CursorMove::Down);
+    t.move_cursor_line_start(true);
+    assert_eq!(t.cursor(), (1, 3));
+
+    t.move_cursor(CursorMove::Down);
+    t.move_cursor_line_end();
+    assert_eq!(t.cursor(), (2, 3));
+
+    // Home key toggles the head and the first non-blank of the line
+    let mut t = TextArea::from(["  abc"]);
+    t.move_cursor_line_end();
+    for want in [2, 0, 2] {
+        t.input(Input {
+            key: Key::Home,
+            ..Default::default()
+        });
+        assert_eq!(t.cursor(), (0, want));
+    }
+}
+
+#[test]
+fn move_cursor_by_grapheme_cluster() {
+    // Family emoji is a sequence of 3 emojis joined with ZWJ (5 characters). "é" is "e" and a combining accent
+    let mut t = TextArea::from(["a👨\u{200d}👩\u{200d}👧e\u{301}b", "c"]);
+    for want in [(0, 1), (0, 6), (0, 8), (0, 9), (1, 0)] {
+        t.move_cursor(CursorMove::Forward);
+        assert_eq!(t.cursor(), want);
+    }
+    for want in [(0, 9), (0, 8), (0, 6), (0, 1), (0, 0)] {
+        t.move_cursor(CursorMove::Back);
+        assert_eq!(t.cursor(), want);
+    }
+}
+
+#[test]
+fn grapheme_under_cursor() {
+    let mut t = TextArea::from(["a👨\u{200d}👩\u{200d}👧b", ""]);
+    for want in [
+        Some("a"),
+        Some("👨\u{200d}👩\u{200d}👧"),
+        Some("b"),
+        None,
+        None,
+    ] {
+        assert_eq!(t.grapheme_under_cursor(), want, "{:?}", t.cursor());
+        assert_eq!(
+            t.char_under_cursor(),
+            want.and_then(|g| g.chars().next()),
+            "{:?}",
+            t.cursor(),
+        );
+        t.move_cursor(CursorMove::Forward);
+    }
+}
+
+#[test]
+fn keep_desired_col_on_vertical_move() {
+    let mut t = TextArea::from(["abcdef", "ab", "", "abcdefgh"]);
+    t.move_cursor(CursorMove::Jump(0, 5));
+    for want in [(1, 2), (2, 0), (3, 5)] {
+        t.move_cursor(CursorMove::Down);
+        assert_eq!(t.cursor(), want);
+    }
+    for want in [(2, 0), (1, 2), (0, 5)] {
+        t.move_cursor(CursorMove::Up);
+        assert_eq!(t.cursor(), want);
+    }
+
+    // Moving horizontally resets the column
+    t.move_cursor(CursorMove::Back);
+    for want in [(1, 2), (2, 0), (3, 4)] {
+        t.move_cursor(CursorMove::Down);
+        assert_eq!(t.cursor(), want);
+    }
+
+    // Editing text resets the column
+    t.move_cursor(CursorMove::Jump(0, 5));
+    t.move_cursor(CursorMove::Down);
+    t.insert_char('x');
+    assert_eq!(t.cursor(), (1, 3));
+    t.move_cursor(CursorMove::Down);
+    t.move_cursor(CursorMove::Down);
+    assert_eq!(t.cursor(), (3, 3));
+}
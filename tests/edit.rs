@@ -0,0 +1,966 @@
+use tui_textarea::{CursorMove, LineEnding, TextArea, TextEdit, TextEditError};
+
+#[test]
+fn delete_word() {
+    for (line, col, want, yank) in [
+        ("aaa bbb", 7, "aaa ", "bbb"),
+        // Trailing whitespaces are deleted with the word before them
+        ("aaa bbb   ", 10, "aaa ", "bbb   "),
+        ("aaa  ", 5, "", "aaa  "),
+        ("foo(bar)", 8, "foo(bar", ")"),
+        ("foo(bar", 7, "foo(", "bar"),
+        ("あいう えお", 6, "あいう ", "えお"),
+        ("   ", 3, "", "   "),
+    ] {
+        let mut t = TextArea::from([line]);
+        t.move_cursor(CursorMove::Jump(0, col));
+        assert!(t.delete_word(), "{:?}", line);
+        assert_eq!(t.lines(), [want], "{:?}", line);
+        assert_eq!(t.yank_text(), yank, "{:?}", line);
+
+        // One deletion is one undo unit
+        assert!(t.undo(), "{:?}", line);
+        assert_eq!(t.lines(), [line], "{:?}", line);
+    }
+}
+
+#[test]
+fn delete_word_at_line_head() {
+    let mut t = TextArea::from(["abc", "def"]);
+    t.move_cursor(CursorMove::Jump(1, 0));
+    assert!(t.delete_word());
+    assert_eq!(t.lines(), ["abcdef"]);
+    assert_eq!(t.cursor(), (0, 3));
+
+    let mut t = TextArea::from(["abc"]);
+    assert!(!t.delete_word());
+    assert_eq!(t.lines(), ["abc"]);
+}
+
+#[test]
+fn delete_line_by_end() {
+    let mut t = TextArea::from(["abc", "def"]);
+    t.move_cursor(CursorMove::Jump(0, 1));
+    assert!(t.delete_line_by_end());
+    assert_eq!(t.lines(), ["a", "def"]);
+    assert_eq!(t.cursor(), (0, 1));
+    assert_eq!(t.yank_text(), "bc");
+
+    // At the end of line, the next line is joined
+    assert!(t.delete_line_by_end());
+    assert_eq!(t.lines(), ["adef"]);
+    assert_eq!(t.cursor(), (0, 1));
+
+    assert!(t.delete_line_by_end());
+    assert_eq!(t.lines(), ["a"]);
+    assert!(!t.delete_line_by_end());
+    assert_eq!(t.lines(), ["a"]);
+
+    // Each deletion is one undo unit
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["adef"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["a", "def"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["abc", "def"]);
+}
+
+#[test]
+fn delete_line_by_head() {
+    let mut t = TextArea::from(["abc", "def"]);
+    t.move_cursor(CursorMove::Jump(1, 2));
+    assert!(t.delete_line_by_head());
+    assert_eq!(t.lines(), ["abc", "f"]);
+    assert_eq!(t.cursor(), (1, 0));
+    assert_eq!(t.yank_text(), "de");
+
+    // At the head of line, the line is joined to the previous line
+    assert!(t.delete_line_by_head());
+    assert_eq!(t.lines(), ["abcf"]);
+    assert_eq!(t.cursor(), (0, 3));
+
+    assert!(t.delete_line_by_head());
+    assert_eq!(t.lines(), ["f"]);
+    assert!(!t.delete_line_by_head());
+
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["abcf"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["abc", "f"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["abc", "def"]);
+}
+
+#[test]
+fn paste_multiple_lines() {
+    let mut t = TextArea::from(["abc", "def"]);
+    t.move_cursor(CursorMove::Jump(0, 1));
+    t.set_yank_text("x\nyy\nzzz");
+    assert!(t.paste());
+    assert_eq!(t.lines(), ["ax", "yy", "zzzbc", "def"]);
+    assert_eq!(t.cursor(), (2, 3));
+
+    // Pasting is one undo unit
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["abc", "def"]);
+    assert_eq!(t.cursor(), (0, 1));
+    assert!(t.redo());
+    assert_eq!(t.lines(), ["ax", "yy", "zzzbc", "def"]);
+    assert_eq!(t.cursor(), (2, 3));
+
+    let mut t = TextArea::from(["あい"]);
+    t.move_cursor(CursorMove::End);
+    t.set_yank_text("う\n");
+    assert!(t.paste());
+    assert_eq!(t.lines(), ["あいう", ""]);
+    assert_eq!(t.cursor(), (1, 0));
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["あい"]);
+
+    let mut t = TextArea::default();
+    assert!(!t.paste());
+}
+
+#[test]
+fn insert_str_multiple_lines() {
+    let mut t = TextArea::from(["xyz", "w"]);
+    t.move_cursor(CursorMove::Jump(0, 1));
+    assert!(t.insert_str("a\nbb\nccc"));
+    assert_eq!(t.lines(), ["xa", "bb", "cccyz", "w"]);
+    assert_eq!(t.cursor(), (2, 3));
+
+    // Inserting is one undo unit
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["xyz", "w"]);
+    assert_eq!(t.cursor(), (0, 1));
+    assert!(t.redo());
+    assert_eq!(t.lines(), ["xa", "bb", "cccyz", "w"]);
+
+    // CRLF is also a newline
+    let mut t = TextArea::default();
+    assert!(t.insert_str("a\r\nb\r\n"));
+    assert_eq!(t.lines(), ["a", "b", ""]);
+    assert_eq!(t.cursor(), (2, 0));
+
+    assert!(!t.insert_str(""));
+}
+
+#[test]
+fn read_only_ignores_edit_inputs() {
+    use tui_textarea::{Input, Key};
+
+    let input = |key, ctrl, alt| Input {
+        key,
+        ctrl,
+        alt,
+        shift: false,
+    };
+
+    let mut t = TextArea::from(["abc", "def"]);
+    t.set_read_only(true);
+    t.move_cursor(CursorMove::Jump(0, 1));
+    t.set_yank_text("xyz");
+
+    for i in [
+        input(Key::Char('x'), false, false),
+        input(Key::Enter, false, false),
+        input(Key::Tab, false, false),
+        input(Key::Backspace, false, false),
+        input(Key::Delete, false, false),
+        input(Key::Char('k'), true, false),
+        input(Key::Char('w'), true, false),
+        input(Key::Char('d'), false, true),
+        input(Key::Char('y'), true, false),
+    ] {
+        assert!(!t.input(i.clone()), "{:?}", i);
+        assert!(!t.input_without_shortcuts(i.clone()), "{:?}", i);
+    }
+    assert_eq!(t.lines(), ["abc", "def"]);
+
+    // Navigation still works
+    t.input(input(Key::Down, false, false));
+    t.input(input(Key::Right, false, false));
+    assert_eq!(t.cursor(), (1, 2));
+
+    // Programmatic edit still works
+    assert!(t.insert_str("!"));
+    assert_eq!(t.lines(), ["abc", "de!f"]);
+
+    t.set_read_only(false);
+    assert!(t.input(input(Key::Char('x'), false, false)));
+    assert_eq!(t.lines(), ["abc", "de!xf"]);
+}
+
+#[test]
+fn max_chars_on_input_and_paste() {
+    use tui_textarea::{Input, Key};
+
+    let input = |key| Input {
+        key,
+        ctrl: false,
+        alt: false,
+        shift: false,
+    };
+
+    let mut t = TextArea::default();
+    t.set_max_chars(Some(5));
+    for c in "abcdefg".chars() {
+        t.input(input(Key::Char(c)));
+    }
+    assert_eq!(t.lines(), ["abcde"]);
+    assert_eq!(t.char_count(), 5);
+    assert!(!t.input(input(Key::Tab)));
+    assert!(!t.input_without_shortcuts(input(Key::Char('x'))));
+
+    // Newlines are not counted as characters
+    assert!(t.input(input(Key::Enter)));
+    assert_eq!(t.lines(), ["abcde", ""]);
+
+    // Deleting makes room again
+    assert!(t.input(input(Key::Backspace)));
+    assert!(t.input(input(Key::Backspace)));
+    assert_eq!(t.char_count(), 4);
+
+    // Paste inserts only up to the remaining budget
+    t.set_yank_text("xyz");
+    assert!(t.paste());
+    assert_eq!(t.lines(), ["abcdx"]);
+    assert!(!t.paste());
+
+    t.set_max_chars(None);
+    assert!(t.paste());
+    assert_eq!(t.lines(), ["abcdxxyz"]);
+
+    // insert_str is limited as well. Newlines are still inserted
+    t.set_max_chars(Some(10));
+    assert!(t.insert_str("12\n345"));
+    assert_eq!(t.lines(), ["abcdxxyz12", ""]);
+    assert!(!t.insert_str("6"));
+    assert_eq!(t.char_count(), 10);
+}
+
+#[test]
+fn max_lines_on_input_and_paste() {
+    use tui_textarea::{Input, Key};
+
+    let mut t = TextArea::from(["a"]);
+    t.move_cursor(CursorMove::End);
+    t.set_max_lines(Some(3));
+
+    let enter = Input {
+        key: Key::Enter,
+        ctrl: false,
+        alt: false,
+        shift: false,
+    };
+    assert!(t.input(enter.clone()));
+    assert!(t.input(enter.clone()));
+    assert!(!t.input(enter.clone()));
+    assert!(!t.input_without_shortcuts(enter));
+    assert_eq!(t.lines(), ["a", "", ""]);
+
+    let mut t = TextArea::from(["a"]);
+    t.move_cursor(CursorMove::End);
+    t.set_max_lines(Some(2));
+    t.set_yank_text("b\nc\nd");
+    assert!(t.paste());
+    assert_eq!(t.lines(), ["ab", "c"]);
+    assert_eq!(t.cursor(), (1, 1));
+
+    // Both limits are applied to paste
+    let mut t = TextArea::default();
+    t.set_max_lines(Some(3));
+    t.set_max_chars(Some(4));
+    t.set_yank_text("ab\ncde\nf");
+    assert!(t.paste());
+    assert_eq!(t.lines(), ["ab", "cd"]);
+
+    let mut t = TextArea::from(["a"]);
+    t.set_max_lines(Some(2));
+    assert!(t.insert_str("b\nc\nd"));
+    assert_eq!(t.lines(), ["b", "ca"]);
+}
+
+#[test]
+fn set_text_and_content() {
+    for text in [
+        "",
+        "abc",
+        "abc\n",
+        "abc\n\n",
+        "abc\n\ndef",
+        "\n",
+        "\n\n",
+        "あいう\nえお",
+    ] {
+        let mut t = TextArea::from(["xyz", "123"]);
+        t.move_cursor(CursorMove::Bottom);
+        t.insert_char('!');
+        t.set_line_ending(LineEnding::Lf);
+        t.set_text(text);
+        assert_eq!(t.content(), text);
+        assert_eq!(t.cursor(), (0, 0), "{:?}", text);
+        assert!(!t.undo(), "{:?}", text);
+    }
+
+    let mut t = TextArea::default();
+    t.set_text("abc\ndef\n");
+    assert_eq!(t.lines(), ["abc", "def"]);
+    assert!(t.trailing_newline());
+    t.set_text("abc\ndef");
+    assert_eq!(t.lines(), ["abc", "def"]);
+    assert!(!t.trailing_newline());
+
+    // `\r` before `\n` is stripped
+    t.set_text("abc\r\ndef\r\n");
+    assert_eq!(t.lines(), ["abc", "def"]);
+    t.set_line_ending(LineEnding::CrLf);
+    assert_eq!(t.content(), "abc\r\ndef\r\n");
+}
+
+#[test]
+fn auto_indent() {
+    use tui_textarea::{Input, Key};
+
+    for (line, col, want, cursor) in [
+        ("    foo", 7, &["    foo", "    "][..], (1, 4)),
+        ("  \tfoo bar", 6, &["  \tfoo", "  \t bar"][..], (1, 3)),
+        // Only whitespaces before the cursor are copied
+        ("    foo", 2, &["  ", "    foo"][..], (1, 2)),
+        ("    ", 4, &["    ", "    "][..], (1, 4)),
+        ("foo", 3, &["foo", ""][..], (1, 0)),
+    ] {
+        let mut t = TextArea::from([line]);
+        t.set_auto_indent(true);
+        t.move_cursor(CursorMove::Jump(0, col));
+        assert!(t.input(Input {
+            key: Key::Enter,
+            ..Default::default()
+        }));
+        assert_eq!(t.lines(), want, "{:?}", line);
+        assert_eq!(t.cursor(), cursor, "{:?}", line);
+
+        // The newline and the indent are one undo unit
+        assert!(t.undo(), "{:?}", line);
+        assert_eq!(t.lines(), [line], "{:?}", line);
+        assert_eq!(t.cursor(), (0, col as usize), "{:?}", line);
+        assert!(t.redo(), "{:?}", line);
+        assert_eq!(t.lines(), want, "{:?}", line);
+    }
+
+    // Auto-indent is disabled by default
+    let mut t = TextArea::from(["    foo"]);
+    t.move_cursor(CursorMove::End);
+    t.insert_newline();
+    assert_eq!(t.lines(), ["    foo", ""]);
+
+    // Enter is limited only by the line limit. The indent is omitted when it doesn't fit in the character limit
+    let mut t = TextArea::from(["    foo"]);
+    t.set_auto_indent(true);
+    t.set_max_chars(Some(7));
+    t.move_cursor(CursorMove::End);
+    assert!(t.input(Input {
+        key: Key::Enter,
+        ..Default::default()
+    }));
+    assert_eq!(t.lines(), ["    foo", ""]);
+
+    // Methods to edit the text programmatically are not limited
+    t.move_cursor(CursorMove::Top);
+    t.move_cursor(CursorMove::End);
+    t.insert_newline();
+    assert_eq!(t.lines(), ["    foo", "    ", ""]);
+}
+
+#[test]
+fn insert_newline_below_and_above() {
+    let mut t = TextArea::from(["  foo bar"]);
+    t.move_cursor(CursorMove::Jump(0, 5));
+    t.insert_newline_below();
+    assert_eq!(t.lines(), ["  foo bar", ""]);
+    assert_eq!(t.cursor(), (1, 0));
+    t.insert_char('x');
+    t.move_cursor(CursorMove::Up);
+    t.insert_newline_above();
+    assert_eq!(t.lines(), ["", "  foo bar", "x"]);
+    assert_eq!(t.cursor(), (0, 0));
+
+    // Each is one undo unit
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["  foo bar", "x"]);
+    assert_eq!(t.cursor(), (0, 1));
+    assert!(t.undo());
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["  foo bar"]);
+    assert_eq!(t.cursor(), (0, 5));
+
+    // Auto-indent copies the leading whitespaces of the current line
+    t.set_auto_indent(true);
+    t.insert_newline_below();
+    assert_eq!(t.lines(), ["  foo bar", "  "]);
+    assert_eq!(t.cursor(), (1, 2));
+    t.move_cursor(CursorMove::Up);
+    t.insert_newline_above();
+    assert_eq!(t.lines(), ["  ", "  foo bar", "  "]);
+    assert_eq!(t.cursor(), (0, 2));
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["  foo bar", "  "]);
+    assert!(t.redo());
+    assert_eq!(t.lines(), ["  ", "  foo bar", "  "]);
+}
+
+#[test]
+fn indent_and_dedent_lines() {
+    let mut t = TextArea::from(["foo", "", "  bar", "baz"]);
+    t.move_cursor(CursorMove::Jump(0, 1));
+    assert!(t.indent_lines());
+    assert_eq!(t.lines(), ["    foo", "", "  bar", "baz"]);
+    assert_eq!(t.cursor(), (0, 5));
+
+    // Lines covered by the selection except for empty lines. The line where the selection ends at its head is not
+    // covered
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(3, 0));
+    assert!(t.indent_lines());
+    assert_eq!(t.lines(), ["        foo", "", "      bar", "baz"]);
+    assert_eq!(t.selection_range(), Some(((0, 9), (3, 0))));
+
+    // The selection is kept so lines can be dedented repeatedly
+    assert!(t.dedent_lines());
+    assert_eq!(t.lines(), ["    foo", "", "  bar", "baz"]);
+    assert!(t.dedent_lines());
+    assert_eq!(t.lines(), ["foo", "", "bar", "baz"]);
+    assert_eq!(t.selection_range(), Some(((0, 1), (3, 0))));
+    assert!(!t.dedent_lines());
+
+    // Each operation is one undo unit
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["    foo", "", "  bar", "baz"]);
+    assert!(t.undo());
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["    foo", "", "  bar", "baz"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["foo", "", "  bar", "baz"]);
+
+    // Hard tab
+    let mut t = TextArea::from(["foo", "\t\tbar"]);
+    t.set_hard_tab_indent(true);
+    assert!(t.indent_lines());
+    assert_eq!(t.lines(), ["\tfoo", "\t\tbar"]);
+    t.move_cursor(CursorMove::Down);
+    assert!(t.dedent_lines());
+    assert_eq!(t.lines(), ["\tfoo", "\tbar"]);
+}
+
+#[test]
+fn dedent_by_shift_tab() {
+    use tui_textarea::{Input, Key};
+
+    let mut t = TextArea::from(["      foo"]);
+    let shift_tab = Input {
+        key: Key::Tab,
+        shift: true,
+        ..Default::default()
+    };
+    assert!(t.input(shift_tab.clone()));
+    assert_eq!(t.lines(), ["  foo"]);
+    assert!(t.input(shift_tab.clone()));
+    assert_eq!(t.lines(), ["foo"]);
+    assert!(!t.input(shift_tab));
+}
+
+#[test]
+fn toggle_comment() {
+    let mut t = TextArea::from(["fn f() {", "", "    // foo();", "    bar();", "}"]);
+    t.move_cursor(CursorMove::Jump(3, 6));
+    assert!(t.toggle_comment("// "));
+    assert_eq!(t.lines()[3], "    // bar();");
+    assert_eq!(t.cursor(), (3, 9));
+    assert!(t.toggle_comment("// "));
+    assert_eq!(t.lines()[3], "    bar();");
+    assert_eq!(t.cursor(), (3, 6));
+
+    // Mixed lines are all commented. Blank lines in the selection are ignored
+    t.move_cursor(CursorMove::Jump(1, 0));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(4, 0));
+    assert!(t.toggle_comment("// "));
+    assert_eq!(
+        t.lines(),
+        ["fn f() {", "", "    // // foo();", "    // bar();", "}"],
+    );
+    assert!(t.toggle_comment("// "));
+    assert_eq!(
+        t.lines(),
+        ["fn f() {", "", "    // foo();", "    bar();", "}"],
+    );
+
+    // Token without trailing space is also removed
+    let mut t = TextArea::from(["  //foo"]);
+    assert!(t.toggle_comment("// "));
+    assert_eq!(t.lines(), ["  foo"]);
+
+    // One invocation is one undo unit
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["  //foo"]);
+
+    assert!(!t.toggle_comment(""));
+}
+
+#[test]
+fn delete_range() {
+    let mut t = TextArea::from(["abc", "def", "ghi", "jkl"]);
+    t.move_cursor(CursorMove::Jump(3, 1));
+    assert_eq!(t.delete_range((0, 1), (2, 2)), "bc\ndef\ngh");
+    assert_eq!(t.lines(), ["ai", "jkl"]);
+    assert_eq!(t.cursor(), (0, 1));
+
+    // Deleting is one undo unit
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["abc", "def", "ghi", "jkl"]);
+    assert_eq!(t.cursor(), (3, 1));
+
+    // Positions are normalized and clamped
+    assert_eq!(t.delete_range((9, 9), (2, 10)), "\njkl");
+    assert_eq!(t.lines(), ["abc", "def", "ghi"]);
+    assert_eq!(t.cursor(), (2, 3));
+
+    assert_eq!(t.delete_range((1, 1), (1, 1)), "");
+    assert_eq!(t.lines(), ["abc", "def", "ghi"]);
+    assert_eq!(t.cursor(), (1, 1));
+}
+
+#[test]
+fn duplicate_line() {
+    let mut t = TextArea::from(["abc", "def", "ghi"]);
+    t.move_cursor(CursorMove::Jump(1, 2));
+    assert!(t.duplicate_line());
+    assert_eq!(t.lines(), ["abc", "def", "def", "ghi"]);
+    assert_eq!(t.cursor(), (2, 2));
+
+    // Block covered by the selection
+    t.move_cursor(CursorMove::Jump(0, 1));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(1, 1));
+    assert!(t.duplicate_line());
+    assert_eq!(t.lines(), ["abc", "def", "abc", "def", "def", "ghi"]);
+    assert_eq!(t.selection_range(), Some(((2, 1), (3, 1))));
+
+    // Duplicating is one undo unit
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["abc", "def", "def", "ghi"]);
+    assert_eq!(t.cursor(), (1, 1));
+    assert!(t.redo());
+    assert_eq!(t.lines(), ["abc", "def", "abc", "def", "def", "ghi"]);
+    assert_eq!(t.cursor(), (3, 1));
+}
+
+#[test]
+fn move_line_up_and_down() {
+    let mut t = TextArea::from(["a", "b", "c", "d"]);
+    assert!(!t.move_line_up());
+    assert!(t.move_line_down());
+    assert_eq!(t.lines(), ["b", "a", "c", "d"]);
+    assert_eq!(t.cursor(), (1, 0));
+    assert!(t.move_line_up());
+    assert_eq!(t.lines(), ["a", "b", "c", "d"]);
+    assert_eq!(t.cursor(), (0, 0));
+
+    // Block covered by the selection. The selection ending at the head of the next line is kept
+    t.move_cursor(CursorMove::Jump(1, 0));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(3, 0));
+    assert!(t.move_line_down());
+    assert_eq!(t.lines(), ["a", "d", "b", "c"]);
+    assert_eq!(t.selection_range(), Some(((2, 0), (3, 1))));
+    assert!(!t.move_line_down());
+    assert!(t.move_line_up());
+    assert!(t.move_line_up());
+    assert_eq!(t.lines(), ["b", "c", "a", "d"]);
+    assert_eq!(t.selection_range(), Some(((0, 0), (1, 1))));
+
+    // Each move is one undo unit
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["a", "b", "c", "d"]);
+    assert_eq!(t.cursor(), (2, 1));
+}
+
+#[test]
+fn move_line_by_alt_arrow() {
+    use tui_textarea::{Input, Key};
+
+    let mut t = TextArea::from(["a", "b", "c"]);
+    let alt = |key| Input {
+        key,
+        alt: true,
+        ..Default::default()
+    };
+    assert!(t.input(alt(Key::Down)));
+    assert!(t.input(alt(Key::Down)));
+    assert_eq!(t.lines(), ["b", "c", "a"]);
+    assert!(!t.input(alt(Key::Down)));
+    assert!(t.input(alt(Key::Up)));
+    assert_eq!(t.lines(), ["b", "a", "c"]);
+    assert_eq!(t.cursor(), (1, 0));
+
+    t.set_read_only(true);
+    assert!(!t.input(alt(Key::Up)));
+}
+
+#[test]
+fn transpose_chars() {
+    use tui_textarea::{Input, Key};
+
+    let mut t = TextArea::from(["🐱あb", "c"]);
+    assert!(!t.transpose_chars());
+    t.move_cursor(CursorMove::Forward);
+    assert!(t.transpose_chars());
+    assert_eq!(t.lines(), ["あ🐱b", "c"]);
+    assert_eq!(t.cursor(), (0, 2));
+    assert!(t.transpose_chars());
+    assert_eq!(t.lines(), ["あb🐱", "c"]);
+    assert_eq!(t.cursor(), (0, 3));
+
+    // At end of line, the last two characters are swapped
+    assert!(t.transpose_chars());
+    assert_eq!(t.lines(), ["あ🐱b", "c"]);
+    assert_eq!(t.cursor(), (0, 3));
+
+    // One character is not transposed
+    t.move_cursor(CursorMove::Jump(1, 1));
+    assert!(!t.transpose_chars());
+
+    // Each transposition is one undo unit
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["あb🐱", "c"]);
+    assert_eq!(t.cursor(), (0, 3));
+
+    let ctrl_t = Input {
+        key: Key::Char('t'),
+        ctrl: true,
+        ..Default::default()
+    };
+    assert!(t.input(ctrl_t));
+    assert_eq!(t.lines(), ["あ🐱b", "c"]);
+}
+
+#[test]
+fn join_next_line() {
+    let mut t = TextArea::from(["a ", "  b  ", "", "\tc ", "d"]);
+    assert!(t.join_next_line());
+    assert_eq!(t.lines(), ["a b  ", "", "\tc ", "d"]);
+    assert_eq!(t.cursor(), (0, 1));
+
+    // No space is inserted for blank lines
+    assert!(t.join_next_line());
+    assert_eq!(t.lines(), ["a b", "\tc ", "d"]);
+    assert_eq!(t.cursor(), (0, 3));
+
+    // Lines covered by the selection
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(2, 1));
+    assert!(t.join_next_line());
+    assert_eq!(t.lines(), ["a b c d"]);
+    assert_eq!(t.cursor(), (0, 5));
+    assert!(!t.join_next_line());
+
+    // Joining lines is one undo unit
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["a b", "\tc ", "d"]);
+    assert_eq!(t.cursor(), (2, 1));
+    assert!(t.redo());
+    assert_eq!(t.lines(), ["a b c d"]);
+}
+
+#[test]
+fn delete_grapheme_cluster() {
+    let mut t = TextArea::from(["a👨\u{200d}👩\u{200d}👧e\u{301}b"]);
+    t.move_cursor(CursorMove::Jump(0, 8));
+    assert!(t.delete_char());
+    assert_eq!(t.lines(), ["a👨\u{200d}👩\u{200d}👧b"]);
+    assert_eq!(t.cursor(), (0, 6));
+    assert!(t.delete_char());
+    assert_eq!(t.lines(), ["ab"]);
+    assert_eq!(t.cursor(), (0, 1));
+
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["a👨\u{200d}👩\u{200d}👧b"]);
+    assert_eq!(t.cursor(), (0, 6));
+
+    t.move_cursor(CursorMove::Head);
+    t.move_cursor(CursorMove::Forward);
+    assert!(t.delete_next_char());
+    assert_eq!(t.lines(), ["ab"]);
+    assert_eq!(t.cursor(), (0, 1));
+}
+
+#[test]
+fn tab_to_next_tab_stop() {
+    use tui_textarea::{Input, Key};
+
+    let tab = Input {
+        key: Key::Tab,
+        ..Default::default()
+    };
+    let mut t = TextArea::from(["abcd"]);
+    t.move_cursor(CursorMove::Jump(0, 2));
+    assert!(t.input(tab.clone()));
+    assert_eq!(t.lines(), ["ab  cd"]);
+    assert_eq!(t.cursor(), (0, 4));
+
+    // Tabs and wide characters before the cursor occupy multiple columns
+    let mut t = TextArea::from(["\tあx"]);
+    t.move_cursor(CursorMove::End);
+    assert!(t.input(tab.clone()));
+    assert_eq!(t.lines(), ["\tあx "]);
+
+    t.set_hard_tab_indent(true);
+    assert!(t.input(tab));
+    assert_eq!(t.lines(), ["\tあx \t"]);
+}
+
+#[test]
+fn tab_length_is_at_least_one() {
+    let mut t = TextArea::from(["a"]);
+    t.set_tab_length(0);
+    assert_eq!(t.tab_length(), 1);
+    assert!(t.insert_tab());
+    assert_eq!(t.lines(), [" a"]);
+    assert_eq!(t.line_display_width(0), 2);
+}
+
+#[test]
+fn apply_edits() {
+    let mut t = TextArea::from(["aaa", "bbb", "ccc", "ddd"]);
+    t.move_cursor(CursorMove::Jump(3, 1));
+    let edits = [
+        TextEdit::new((2, 3), (2, 3), "\nyyy\r\nzzz"),
+        TextEdit::new((1, 2), (0, 1), "X"),
+    ];
+    assert_eq!(t.apply_edits(&edits), Ok(true));
+    assert_eq!(t.lines(), ["aXb", "ccc", "yyy", "zzz", "ddd"]);
+    assert_eq!(t.cursor(), (4, 1));
+
+    // Edits are undone at once
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["aaa", "bbb", "ccc", "ddd"]);
+    assert_eq!(t.cursor(), (3, 1));
+    assert!(!t.undo());
+    assert!(t.redo());
+    assert_eq!(t.lines(), ["aXb", "ccc", "yyy", "zzz", "ddd"]);
+
+    // The cursor keeps its position relative to the text around it
+    for (col, edits, want) in [
+        (3, vec![TextEdit::new((0, 0), (0, 5), "goodbye")], (0, 7)),
+        (8, vec![TextEdit::new((0, 0), (0, 5), "goodbye")], (0, 10)),
+        (0, vec![TextEdit::new((0, 6), (0, 11), "\n")], (0, 0)),
+        (6, vec![TextEdit::new((0, 0), (0, 0), "\n")], (1, 6)),
+        (6, vec![TextEdit::new((0, 6), (0, 6), "big ")], (0, 6)),
+    ] {
+        let mut t = TextArea::from(["hello world"]);
+        t.move_cursor(CursorMove::Jump(0, col));
+        assert_eq!(t.apply_edits(&edits), Ok(true), "{:?}", edits);
+        assert_eq!(t.cursor(), want, "{:?}", edits);
+    }
+
+    // Insertions at the same position are applied in the given order
+    let mut t = TextArea::from(["abc"]);
+    let edits = [
+        TextEdit::new((0, 1), (0, 1), "x"),
+        TextEdit::new((0, 1), (0, 2), "z"),
+        TextEdit::new((0, 1), (0, 1), "y"),
+    ];
+    assert_eq!(t.apply_edits(&edits), Ok(true));
+    assert_eq!(t.lines(), ["axyzc"]);
+
+    // Overlapping edits are not applied
+    let edits = [
+        TextEdit::new((0, 0), (0, 1), "x"),
+        TextEdit::new((0, 3), (0, 4), "y"),
+        TextEdit::new((0, 2), (0, 4), "z"),
+    ];
+    assert_eq!(t.apply_edits(&edits), Err(TextEditError::Overlap(1, 2)));
+    assert_eq!(t.lines(), ["axyzc"]);
+
+    // Nothing is modified
+    assert_eq!(t.apply_edits(&[]), Ok(false));
+    let edits = [TextEdit::new((0, 1), (0, 2), "x")];
+    assert_eq!(t.apply_edits(&edits), Ok(false));
+    assert_eq!(t.lines(), ["axyzc"]);
+}
+
+#[test]
+fn replace_all_text() {
+    let mut t = TextArea::from(["fn f(){", "if x {", "foo(bar);", "}", "}"]);
+    t.move_cursor(CursorMove::Jump(2, 4)); // On "bar"
+    let formatted = "fn f() {\n    if x {\n        foo(bar);\n    }\n}\n";
+    assert!(t.replace_all_text(formatted));
+    assert_eq!(
+        t.lines(),
+        ["fn f() {", "    if x {", "        foo(bar);", "    }", "}"],
+    );
+    // The cursor stays on the same token
+    assert_eq!(t.cursor(), (2, 12));
+    assert_eq!(&t.lines()[2][12..], "bar);");
+
+    // Formatting the formatted text again does nothing
+    assert!(!t.replace_all_text(formatted));
+
+    // Replaced at once
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["fn f(){", "if x {", "foo(bar);", "}", "}"]);
+    assert_eq!(t.cursor(), (2, 4));
+    assert!(!t.undo());
+
+    // Lines after the replaced lines are shifted
+    let mut t = TextArea::from(["a", "b", "c"]);
+    t.move_cursor(CursorMove::Jump(2, 1));
+    assert!(t.replace_all_text("a b\r\nc"));
+    assert_eq!(t.lines(), ["a b", "c"]);
+    assert_eq!(t.cursor(), (1, 1));
+
+    // The cursor in the indentation stays in the indentation
+    let mut t = TextArea::from(["        x", "y"]);
+    t.move_cursor(CursorMove::Jump(0, 6));
+    assert!(t.replace_all_text("    x\ny"));
+    assert_eq!(t.cursor(), (0, 4));
+}
+
+#[test]
+fn auto_pairs() {
+    use tui_textarea::{Input, Key};
+
+    fn type_str(t: &mut TextArea<'_>, s: &str) {
+        for c in s.chars() {
+            t.input(Input {
+                key: Key::Char(c),
+                ..Default::default()
+            });
+        }
+    }
+    fn backspace(t: &mut TextArea<'_>) -> bool {
+        t.input(Input {
+            key: Key::Backspace,
+            ..Default::default()
+        })
+    }
+
+    for (open, close) in [('(', ')'), ('[', ']'), ('{', '}'), ('"', '"'), ('\'', '\'')] {
+        let mut t = TextArea::default();
+        t.set_auto_pairs(true);
+        type_str(&mut t, &open.to_string());
+        let pair = format!("{}{}", open, close);
+        assert_eq!(t.lines(), [pair.as_str()], "{:?}", open);
+        assert_eq!(t.cursor(), (0, 1), "{:?}", open);
+
+        // The pair is one undo unit
+        assert!(t.undo(), "{:?}", open);
+        assert_eq!(t.lines(), [""], "{:?}", open);
+        assert!(t.redo(), "{:?}", open);
+        assert_eq!(t.lines(), [pair.as_str()], "{:?}", open);
+
+        // Typing the closing character moves the cursor over it
+        t.move_cursor(CursorMove::Jump(0, 1));
+        assert!(!t.input(Input {
+            key: Key::Char(close),
+            ..Default::default()
+        }));
+        assert_eq!(t.lines(), [pair.as_str()], "{:?}", open);
+        assert_eq!(t.cursor(), (0, 2), "{:?}", open);
+
+        // Backspace between an empty pair deletes both
+        t.move_cursor(CursorMove::Jump(0, 1));
+        assert!(backspace(&mut t), "{:?}", open);
+        assert_eq!(t.lines(), [""], "{:?}", open);
+        assert_eq!(t.cursor(), (0, 0), "{:?}", open);
+        assert!(t.undo(), "{:?}", open);
+        assert_eq!(t.lines(), [pair.as_str()], "{:?}", open);
+        assert_eq!(t.cursor(), (0, 1), "{:?}", open);
+    }
+
+    let mut t = TextArea::default();
+    t.set_auto_pairs(true);
+    type_str(&mut t, "f(a[0], \"b\")");
+    assert_eq!(t.lines(), ["f(a[0], \"b\")"]);
+    assert_eq!(t.cursor(), (0, 12));
+
+    // Backspace after a non-empty pair deletes one character
+    t.move_cursor(CursorMove::Jump(0, 6));
+    assert!(backspace(&mut t));
+    assert_eq!(t.lines(), ["f(a[0, \"b\")"]);
+
+    // Pair is not inserted before a word, and a quote after a word is an apostrophe
+    for (line, col, c, want) in [
+        ("foo", 0, '(', "(foo"),
+        ("foo", 3, '\'', "foo'"),
+        ("foo)", 3, '(', "foo())"),
+        ("foo bar", 3, '"', "foo\" bar"),
+        ("a ", 2, '"', "a \"\""),
+    ] {
+        let mut t = TextArea::from([line]);
+        t.set_auto_pairs(true);
+        t.move_cursor(CursorMove::Jump(0, col));
+        type_str(&mut t, &c.to_string());
+        assert_eq!(t.lines(), [want], "{:?} at {}", line, col);
+    }
+
+    // Auto-pairs is disabled by default
+    let mut t = TextArea::default();
+    type_str(&mut t, "(");
+    assert_eq!(t.lines(), ["("]);
+    assert!(backspace(&mut t));
+    type_str(&mut t, "()");
+    t.move_cursor(CursorMove::Back);
+    assert!(backspace(&mut t));
+    assert_eq!(t.lines(), [")"]);
+}
+
+#[test]
+fn auto_pairs_wrap_selection() {
+    use tui_textarea::{Input, Key};
+
+    let brace = Input {
+        key: Key::Char('{'),
+        ..Default::default()
+    };
+
+    let mut t = TextArea::from(["foo", "bar", "baz"]);
+    t.set_auto_pairs(true);
+    t.move_cursor(CursorMove::Jump(0, 1));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(2, 2));
+    assert!(t.input(brace.clone()));
+    assert_eq!(t.lines(), ["f{oo", "bar", "ba}z"]);
+    assert_eq!(t.selection_range(), Some(((0, 2), (2, 2))));
+    assert_eq!(t.cursor(), (2, 2));
+
+    // Wrapping is one undo unit
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["foo", "bar", "baz"]);
+    assert!(t.redo());
+    assert_eq!(t.lines(), ["f{oo", "bar", "ba}z"]);
+
+    // The selection is kept when it was made backward and on a single line
+    for c in ['(', '"'] {
+        let mut t = TextArea::from(["foo bar"]);
+        t.set_auto_pairs(true);
+        t.move_cursor(CursorMove::Jump(0, 7));
+        t.start_selection();
+        t.move_cursor(CursorMove::Jump(0, 4));
+        assert!(t.input(Input {
+            key: Key::Char(c),
+            ..Default::default()
+        }));
+        let want = format!("foo {}bar{}", c, if c == '(' { ')' } else { '"' });
+        assert_eq!(t.lines(), [want.as_str()], "{:?}", c);
+        assert_eq!(t.selection_range(), Some(((0, 5), (0, 8))), "{:?}", c);
+        assert_eq!(t.cursor(), (0, 5), "{:?}", c);
+    }
+
+    // Selected text is not wrapped when auto-pairs is disabled
+    let mut t = TextArea::from(["foo"]);
+    t.start_selection();
+    t.move_cursor(CursorMove::End);
+    assert!(t.input(brace));
+    assert_eq!(t.lines(), ["foo{"]);
+}
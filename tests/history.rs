@@ -1,4 +1,4 @@
-use tui_textarea::TextArea;
+use tui_textarea::{CursorMove, TextArea};
 
 // Regression test for #4
 #[test]
@@ -8,3 +8,116 @@ fn disable_history() {
     assert!(t.insert_str("hello"));
     assert_eq!(t.lines(), ["hello"]);
 }
+
+#[test]
+fn coalesce_insertions() {
+    let mut t = TextArea::default();
+    for c in "abc".chars() {
+        t.insert_char(c);
+    }
+    assert_eq!(t.lines(), ["abc"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), [""]);
+    assert_eq!(t.cursor(), (0, 0));
+    assert!(!t.undo());
+    assert!(t.redo());
+    assert_eq!(t.lines(), ["abc"]);
+    assert_eq!(t.cursor(), (0, 3));
+}
+
+#[test]
+fn no_coalesce_pasted_text() {
+    let mut t = TextArea::default();
+    t.insert_char('a');
+    t.insert_char('b');
+    t.insert_str("cd");
+    t.insert_char('e');
+    t.insert_char('f');
+    assert_eq!(t.lines(), ["abcdef"]);
+
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["abcd"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["ab"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), [""]);
+    assert!(!t.undo());
+}
+
+#[test]
+fn coalesce_stops_at_other_edits() {
+    let mut t = TextArea::default();
+    t.insert_char('a');
+    t.insert_char('b');
+    t.insert_newline();
+    t.insert_char('c');
+    t.insert_char('d');
+    t.move_cursor(CursorMove::Head);
+    t.insert_char('e');
+    assert_eq!(t.lines(), ["ab", "ecd"]);
+
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["ab", "cd"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["ab", ""]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["ab"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), [""]);
+}
+
+#[test]
+fn no_coalesce_after_undo() {
+    let mut t = TextArea::default();
+    t.insert_str("ab");
+    t.delete_char();
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["ab"]);
+    t.insert_char('c');
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["ab"]);
+}
+
+#[test]
+fn version_bumps_on_modification() {
+    let mut t = TextArea::from(["abc"]);
+    let v = t.version();
+    t.move_cursor(CursorMove::End);
+    assert!(!t.delete_next_char());
+    assert_eq!(t.version(), v);
+
+    t.insert_char('d');
+    let v1 = t.version();
+    assert_ne!(v1, v);
+    assert!(t.undo());
+    let v2 = t.version();
+    assert_ne!(v2, v1);
+    assert!(t.redo());
+    assert_ne!(t.version(), v2);
+
+    let v = t.version();
+    t.set_text("foo");
+    assert_ne!(t.version(), v);
+}
+
+#[test]
+fn modified_flag() {
+    let mut t = TextArea::from(["abc"]);
+    assert!(!t.is_modified());
+    t.move_cursor(CursorMove::Forward);
+    assert!(!t.is_modified());
+
+    t.insert_char('x');
+    assert!(t.is_modified());
+    t.reset_modified();
+    assert!(!t.is_modified());
+
+    // Undo back to the saved text still counts as a modification
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["abc"]);
+    assert!(t.is_modified());
+    t.reset_modified();
+    assert!(!t.is_modified());
+    assert!(t.redo());
+    assert!(t.is_modified());
+}
@@ -0,0 +1,361 @@
+use tui_textarea::{emacs_input, Action, CursorMove, Input, Key, KeyMap, TextArea};
+
+fn key(key: Key) -> Input {
+    Input {
+        key,
+        ctrl: false,
+        alt: false,
+        shift: false,
+    }
+}
+
+#[test]
+fn edit_keys() {
+    let mut t = TextArea::default();
+    assert!(t.input(key(Key::Char('a'))));
+    assert!(t.input(key(Key::Char('b'))));
+    assert!(t.input(key(Key::Enter)));
+    assert!(t.input(key(Key::Tab)));
+    assert!(t.input(key(Key::Char('c'))));
+    assert_eq!(t.lines(), ["ab", "    c"]);
+
+    assert!(t.input(key(Key::Backspace)));
+    assert_eq!(t.lines(), ["ab", "    "]);
+
+    assert!(!t.input(key(Key::Left)));
+    assert!(!t.input(key(Key::Up)));
+    assert!(!t.input(key(Key::Left)));
+    assert_eq!(t.cursor(), (0, 1));
+    assert!(t.input(key(Key::Delete)));
+    assert_eq!(t.lines(), ["a", "    "]);
+}
+
+#[test]
+fn modified_result_matches_version() {
+    let mut t = TextArea::from(["ab"]);
+    let ctrl = |c| Input {
+        key: Key::Char(c),
+        ctrl: true,
+        ..Default::default()
+    };
+    for (input, modified) in [
+        (key(Key::Backspace), false),
+        (ctrl('u'), false),
+        (ctrl('y'), false),
+        (key(Key::End), false),
+        (key(Key::Delete), false),
+        (ctrl('k'), false),
+        (key(Key::Char('c')), true),
+        (ctrl('w'), true),
+        (ctrl('y'), true),
+        (ctrl('u'), true),
+    ] {
+        let version = t.version();
+        assert_eq!(t.input(input.clone()), modified, "{:?}", input);
+        assert_eq!(t.version() != version, modified, "{:?}", input);
+    }
+}
+
+#[test]
+fn navigation_keys() {
+    let mut t = TextArea::from(["abc", "de", "fghi"]);
+    for (k, want) in [
+        (Key::Right, (0, 1)),
+        (Key::End, (0, 3)),
+        (Key::Down, (1, 2)),
+        (Key::Left, (1, 1)),
+        (Key::Down, (2, 1)),
+        (Key::Home, (2, 0)),
+        (Key::Up, (1, 0)),
+        (Key::Left, (0, 3)),
+    ] {
+        assert!(!t.input(key(k)), "{:?}", k);
+        assert_eq!(t.cursor(), want, "{:?}", k);
+    }
+    assert_eq!(t.lines(), ["abc", "de", "fghi"]);
+}
+
+#[test]
+fn ignored_keys() {
+    let mut t = TextArea::from(["abc"]);
+    for k in [Key::Esc, Key::Null, Key::F(1)] {
+        assert!(!t.input(key(k)), "{:?}", k);
+        assert!(!t.input_without_shortcuts(key(k)), "{:?}", k);
+    }
+    assert_eq!(t.lines(), ["abc"]);
+    assert_eq!(t.cursor(), (0, 0));
+}
+
+#[test]
+fn shift_selection() {
+    let mut t = TextArea::from(["abc def", "ghi"]);
+    let shift = |key| Input {
+        key,
+        shift: true,
+        ..Default::default()
+    };
+
+    t.input(shift(Key::Right));
+    t.input(shift(Key::Right));
+    assert_eq!(t.selection_range(), Some(((0, 0), (0, 2))));
+
+    // Ctrl+Shift+Right extends the selection by word
+    t.input(Input {
+        key: Key::Right,
+        ctrl: true,
+        alt: false,
+        shift: true,
+    });
+    t.input(shift(Key::Down));
+    assert_eq!(t.selection_range(), Some(((0, 0), (1, 3))));
+    assert_eq!(t.copy().as_deref(), Some("abc def\nghi"));
+
+    // Moving without Shift cancels the selection started with Shift
+    t.input(shift(Key::Home));
+    assert_eq!(t.selection_range(), Some(((1, 0), (1, 3))));
+    t.input(key(Key::Right));
+    assert_eq!(t.selection_range(), None);
+
+    // Selection started explicitly is not cancelled
+    t.start_selection();
+    t.input(key(Key::Right));
+    assert_eq!(t.selection_range(), Some(((1, 1), (1, 2))));
+
+    // Shift is ignored on typing
+    t.input(shift(Key::Char('X')));
+    assert_eq!(t.lines(), ["abc def", "ghXi"]);
+}
+
+#[test]
+fn input_with_key_map() {
+    let ctrl = |c| Input {
+        key: Key::Char(c),
+        ctrl: true,
+        ..Default::default()
+    };
+
+    let mut map = KeyMap::default();
+    map.bind(ctrl('d'), Action::DeleteLine);
+
+    let mut t = TextArea::from(["abc", "def"]);
+    assert!(t.input_with(ctrl('d'), &map));
+    assert_eq!(t.lines(), ["def"]);
+    assert!(t.input_with(key(Key::Char('x')), &map));
+    assert!(!t.input_with(ctrl('e'), &map));
+    assert!(t.input_with(key(Key::Enter), &map));
+    assert_eq!(t.lines(), ["xdef", ""]);
+    assert!(t.input_with(ctrl('u'), &map));
+    assert_eq!(t.lines(), ["xdef"]);
+
+    // Shift+arrow extends the selection with the action bound to the arrow
+    t.move_cursor(CursorMove::Head);
+    let shift_right = Input {
+        key: Key::Right,
+        shift: true,
+        ..Default::default()
+    };
+    assert!(!t.input_with(shift_right, &map));
+    assert_eq!(t.selection_range(), Some(((0, 0), (0, 1))));
+
+    // Unbound keys are ignored
+    let mut map = KeyMap::new();
+    assert!(!t.input_with(ctrl('h'), &map));
+    assert!(!t.input_with(key(Key::Enter), &map));
+    assert_eq!(t.lines(), ["xdef"]);
+    map.bind(key(Key::Enter), Action::InsertNewline);
+    assert!(t.input_with(key(Key::Enter), &map));
+    assert_eq!(t.lines(), ["x", "def"]);
+    map.bind(ctrl('j'), Action::DuplicateLine);
+    assert!(t.input_with(ctrl('j'), &map));
+    assert_eq!(t.lines(), ["x", "def", "def"]);
+
+    // Read-only textarea ignores edits
+    t.set_read_only(true);
+    assert!(!t.input_with(key(Key::Enter), &map));
+    assert!(!t.input_with(key(Key::Char('a')), &map));
+    assert!(!t.input_with(ctrl('j'), &map));
+    assert_eq!(t.lines(), ["x", "def", "def"]);
+}
+
+#[test]
+fn emacs_keys() {
+    let mut t = TextArea::from(["abc def"]);
+    let ctrl = |c| Input {
+        key: Key::Char(c),
+        ctrl: true,
+        ..Default::default()
+    };
+    let alt = |c| Input {
+        key: Key::Char(c),
+        alt: true,
+        ..Default::default()
+    };
+
+    // Mark and region
+    assert!(!emacs_input(&mut t, ctrl(' ')));
+    assert!(!emacs_input(&mut t, ctrl('f')));
+    assert!(!emacs_input(&mut t, ctrl('f')));
+    assert_eq!(t.selection_range(), Some(((0, 0), (0, 2))));
+    assert!(!emacs_input(&mut t, alt('w')));
+    assert_eq!(t.yank_text(), "ab");
+    assert_eq!(t.selection_range(), None);
+
+    assert!(!emacs_input(&mut t, ctrl('@')));
+    assert!(!emacs_input(&mut t, ctrl('e')));
+    assert!(!emacs_input(&mut t, ctrl('g')));
+    assert_eq!(t.selection_range(), None);
+
+    assert!(!emacs_input(&mut t, ctrl(' ')));
+    assert!(!emacs_input(&mut t, ctrl('b')));
+    assert!(emacs_input(&mut t, ctrl('w')));
+    assert_eq!(t.lines(), ["abc de"]);
+    assert_eq!(t.yank_text(), "f");
+
+    // Ctrl+W without region deletes a word as default key mappings
+    assert!(emacs_input(&mut t, ctrl('w')));
+    assert_eq!(t.lines(), ["abc "]);
+
+    assert!(emacs_input(&mut t, ctrl('/')));
+    assert_eq!(t.lines(), ["abc de"]);
+    assert!(emacs_input(&mut t, ctrl('_')));
+    assert_eq!(t.lines(), ["abc def"]);
+    assert!(emacs_input(&mut t, ctrl('y')));
+}
+
+#[cfg(feature = "crossterm")]
+#[test]
+fn crossterm_key_event() {
+    use crossterm::event::{
+        Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent,
+        MouseEventKind,
+    };
+
+    let i = Input::from(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::CONTROL));
+    assert!(matches!(i.key, Key::Char('a')), "{:?}", i);
+    assert!(i.ctrl && !i.alt && !i.shift, "{:?}", i);
+
+    let i = Input::from(KeyEvent::new(KeyCode::BackTab, KeyModifiers::NONE));
+    assert!(matches!(i.key, Key::Tab), "{:?}", i);
+    assert!(i.shift, "{:?}", i);
+
+    let i = Input::from(KeyEvent::new(KeyCode::F(3), KeyModifiers::ALT));
+    assert!(matches!(i.key, Key::F(3)), "{:?}", i);
+    assert!(i.alt, "{:?}", i);
+
+    let i = Input::from(Event::Key(KeyEvent::new(
+        KeyCode::Enter,
+        KeyModifiers::NONE,
+    )));
+    assert!(matches!(i.key, Key::Enter), "{:?}", i);
+
+    // Key release events are ignored
+    let mut release = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+    release.kind = KeyEventKind::Release;
+    let i = Input::from(Event::Key(release));
+    assert!(matches!(i.key, Key::Null), "{:?}", i);
+
+    let i = Input::from(Event::FocusGained);
+    assert!(matches!(i.key, Key::Null), "{:?}", i);
+
+    let i = Input::from(Event::Mouse(MouseEvent {
+        kind: MouseEventKind::Down(MouseButton::Left),
+        column: 3,
+        row: 5,
+        modifiers: KeyModifiers::SHIFT,
+    }));
+    assert!(matches!(i.key, Key::MouseClick(3, 5)), "{:?}", i);
+    assert!(i.shift, "{:?}", i);
+}
+
+#[cfg(feature = "ratatui-termwiz")]
+#[test]
+fn termwiz_mouse_event() {
+    use ratatui::buffer::Buffer;
+    use ratatui::layout::Rect;
+    use ratatui::widgets::Widget;
+    use termwiz::input::{InputEvent, Modifiers, MouseButtons, MouseEvent};
+
+    let mouse = |x, mouse_buttons| MouseEvent {
+        x,
+        y: 2,
+        mouse_buttons,
+        modifiers: Modifiers::NONE,
+    };
+
+    // Pressing, dragging and releasing the left button
+    for (event, want) in [
+        (mouse(1, MouseButtons::NONE), Key::Null),
+        (mouse(1, MouseButtons::LEFT), Key::MouseClick(1, 2)),
+        (mouse(2, MouseButtons::LEFT), Key::MouseDrag(2, 2)),
+        (mouse(3, MouseButtons::LEFT), Key::MouseDrag(3, 2)),
+        (mouse(3, MouseButtons::NONE), Key::MouseRelease(3, 2)),
+        (mouse(4, MouseButtons::NONE), Key::Null),
+    ] {
+        let i = Input::from(InputEvent::Mouse(event));
+        assert_eq!(i.key, want);
+    }
+
+    let i = Input::from(MouseEvent {
+        modifiers: Modifiers::SHIFT,
+        ..mouse(5, MouseButtons::LEFT)
+    });
+    assert!(matches!(i.key, Key::MouseClick(5, 2)), "{:?}", i);
+    assert!(i.shift, "{:?}", i);
+    let i = Input::from(mouse(5, MouseButtons::NONE));
+    assert!(matches!(i.key, Key::MouseRelease(5, 2)), "{:?}", i);
+
+    let wheel = MouseButtons::VERT_WHEEL | MouseButtons::WHEEL_POSITIVE;
+    let i = Input::from(mouse(0, wheel));
+    assert!(matches!(i.key, Key::MouseScrollUp), "{:?}", i);
+
+    // Dragging selects text
+    let mut t = TextArea::from(["hello world"]);
+    let r = Rect {
+        x: 0,
+        y: 0,
+        width: 20,
+        height: 3,
+    };
+    t.widget().render(r, &mut Buffer::empty(r));
+    for event in [
+        mouse(1, MouseButtons::LEFT),
+        mouse(3, MouseButtons::LEFT),
+        mouse(5, MouseButtons::LEFT),
+        mouse(5, MouseButtons::NONE),
+    ] {
+        t.input(event);
+    }
+    assert_eq!(t.selection_range(), Some(((0, 1), (0, 5))));
+}
+
+#[cfg(feature = "termion")]
+#[test]
+fn termion_key() {
+    use termion::event::{Event, Key as TermionKey};
+
+    let i = Input::from(TermionKey::Ctrl('a'));
+    assert!(matches!(i.key, Key::Char('a')), "{:?}", i);
+    assert!(i.ctrl && !i.alt && !i.shift, "{:?}", i);
+
+    let i = Input::from(TermionKey::Alt('b'));
+    assert!(matches!(i.key, Key::Char('b')), "{:?}", i);
+    assert!(!i.ctrl && i.alt && !i.shift, "{:?}", i);
+
+    for (k, want) in [
+        (TermionKey::Char('\n'), Key::Enter),
+        (TermionKey::Char('\t'), Key::Tab),
+        (TermionKey::Left, Key::Left),
+        (TermionKey::PageDown, Key::PageDown),
+        (TermionKey::F(2), Key::F(2)),
+    ] {
+        let i = Input::from(k);
+        assert_eq!(format!("{:?}", i.key), format!("{:?}", want));
+    }
+
+    let i = Input::from(TermionKey::BackTab);
+    assert!(matches!(i.key, Key::Tab), "{:?}", i);
+    assert!(i.shift, "{:?}", i);
+
+    let i = Input::from(Event::Key(TermionKey::Esc));
+    assert!(matches!(i.key, Key::Esc), "{:?}", i);
+}
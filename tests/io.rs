@@ -0,0 +1,121 @@
+use tui_textarea::{LineEnding, TextArea};
+
+#[test]
+fn from_reader() {
+    for (text, want) in [
+        ("", &[""][..]),
+        ("\n", &[""][..]),
+        ("abc", &["abc"][..]),
+        ("abc\n", &["abc"][..]),
+        ("abc\ndef", &["abc", "def"][..]),
+        ("abc\r\ndef\r\n", &["abc", "def"][..]),
+        ("abc\n\n", &["abc", ""][..]),
+        ("あいう\r\n\r\nえお", &["あいう", "", "えお"][..]),
+    ] {
+        let t = TextArea::from_reader(text.as_bytes()).unwrap();
+        assert_eq!(t.lines(), want, "{:?}", text);
+        assert_eq!(t.cursor(), (0, 0), "{:?}", text);
+    }
+}
+
+#[test]
+fn from_reader_invalid_utf8() {
+    let bytes: &[u8] = &[b'a', 0xff, b'b'];
+    assert!(TextArea::from_reader(bytes).is_err());
+}
+
+#[test]
+fn write_to_round_trip() {
+    for text in [
+        "",
+        "\n",
+        "abc",
+        "abc\n",
+        "abc\ndef",
+        "abc\r\ndef\r\n",
+        "abc\n\n",
+        "あいう\r\n\r\nえお",
+    ] {
+        let t = TextArea::from_reader(text.as_bytes()).unwrap();
+        let mut buf = vec![];
+        t.write_to(&mut buf).unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), text);
+    }
+}
+
+#[test]
+fn write_to_after_edit() {
+    let mut t = TextArea::from_reader("abc\r\ndef\r\n".as_bytes()).unwrap();
+    t.insert_newline();
+    t.insert_str("xyz");
+
+    let mut buf = vec![];
+    t.write_to(&mut buf).unwrap();
+    assert_eq!(buf, b"\r\nxyzabc\r\ndef\r\n");
+
+    t.set_trailing_newline(false);
+    let mut buf = vec![];
+    t.write_to(&mut buf).unwrap();
+    assert_eq!(buf, b"\r\nxyzabc\r\ndef");
+}
+
+#[test]
+fn trailing_newline_round_trip() {
+    for (lines, enabled, text) in [
+        (&[""][..], true, "\n"),
+        (&[""][..], false, ""),
+        (&["abc"][..], true, "abc\n"),
+        (&["abc"][..], false, "abc"),
+        (&["abc", ""][..], true, "abc\n\n"),
+        (&["", ""][..], true, "\n\n"),
+    ] {
+        let mut t = TextArea::from(lines.iter().copied());
+        t.set_line_ending(LineEnding::Lf);
+        t.set_trailing_newline(enabled);
+
+        let mut buf = vec![];
+        t.write_to(&mut buf).unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), text, "{:?}", lines);
+        assert_eq!(t.content(), text, "{:?}", lines);
+
+        // A trailing newline is not an empty line which the cursor can move to
+        let t = TextArea::from_reader(text.as_bytes()).unwrap();
+        assert_eq!(t.lines(), lines, "{:?}", text);
+        assert_eq!(t.trailing_newline(), enabled, "{:?}", text);
+    }
+
+    // An empty last line is followed by the trailing newline even if it is disabled so that the line is not lost
+    let mut t = TextArea::from(["abc", ""]);
+    t.set_line_ending(LineEnding::Lf);
+    t.set_trailing_newline(false);
+    let mut buf = vec![];
+    t.write_to(&mut buf).unwrap();
+    assert_eq!(buf, b"abc\n\n");
+    assert_eq!(t.content(), "abc\n\n");
+    let t = TextArea::from_reader(&buf[..]).unwrap();
+    assert_eq!(t.lines(), ["abc", ""]);
+    assert!(t.trailing_newline());
+}
+
+#[test]
+fn mixed_line_endings() {
+    let text = "abc\r\ndef\nghi\r\n\njkl\n";
+    let mut t = TextArea::from_reader(text.as_bytes()).unwrap();
+    assert_eq!(t.lines(), ["abc", "def", "ghi", "", "jkl"]);
+    // The line ending of the first line is detected
+    assert_eq!(t.line_ending(), LineEnding::CrLf);
+
+    let mut buf = vec![];
+    t.write_to(&mut buf).unwrap();
+    assert_eq!(buf, b"abc\r\ndef\r\nghi\r\n\r\njkl\r\n");
+
+    t.set_line_ending(LineEnding::Lf);
+    let mut buf = vec![];
+    t.write_to(&mut buf).unwrap();
+    assert_eq!(buf, b"abc\ndef\nghi\n\njkl\n");
+    assert_eq!(t.content(), "abc\ndef\nghi\n\njkl\n");
+
+    let t = TextArea::from_reader("abc\ndef\r\n".as_bytes()).unwrap();
+    assert_eq!(t.line_ending(), LineEnding::Lf);
+    assert_eq!(t.lines(), ["abc", "def"]);
+}
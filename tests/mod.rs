@@ -1,2 +1,7 @@
 mod cursor;
+mod edit;
 mod history;
+mod input;
+mod io;
+mod search;
+mod selection;
@@ -0,0 +1,220 @@
+#![cfg(feature = "search")]
+
+use regex::{Regex, RegexBuilder};
+use tui_textarea::{SearchOptions, TextArea};
+
+#[test]
+fn search_pattern_regex() {
+    let mut t = TextArea::from(["foo Foo", "FOO"]);
+
+    t.set_search_pattern_regex(Regex::new("Foo").unwrap());
+    assert!(t.search_forward(false));
+    assert_eq!(t.cursor(), (0, 4));
+    assert!(t.search_forward(false));
+    assert_eq!(t.cursor(), (0, 4)); // Only one match
+
+    let re = RegexBuilder::new("foo")
+        .case_insensitive(true)
+        .build()
+        .unwrap();
+    t.set_search_pattern_regex(re);
+    assert!(t.search_forward(false));
+    assert_eq!(t.cursor(), (1, 0));
+    assert!(t.search_forward(false));
+    assert_eq!(t.cursor(), (0, 0));
+
+    // `(?i)` flag works with string patterns
+    t.set_search_pattern("(?i)fOo").unwrap();
+    assert!(t.search_back(false));
+    assert_eq!(t.cursor(), (1, 0));
+
+    // Empty pattern stops the search
+    t.set_search_pattern_regex(Regex::new("").unwrap());
+    assert!(t.search_pattern().is_none());
+    assert!(!t.search_forward(false));
+}
+
+#[test]
+fn invalid_search_pattern() {
+    let mut t = TextArea::from(["abc"]);
+    t.set_search_pattern("b").unwrap();
+    assert!(t.set_search_pattern("(b").is_err());
+    // The previous pattern is kept
+    assert_eq!(t.search_pattern().unwrap().as_str(), "b");
+}
+
+#[test]
+fn search_across_lines() {
+    let mut t = TextArea::from(["ab ab", "", "xyz", "ab"]);
+    t.set_search_pattern("ab").unwrap();
+
+    let mut forward = vec![];
+    for _ in 0..4 {
+        assert!(t.search_forward(false));
+        forward.push(t.cursor());
+    }
+    assert_eq!(forward, [(0, 3), (3, 0), (0, 0), (0, 3)]);
+
+    let mut back = vec![];
+    for _ in 0..4 {
+        assert!(t.search_back(false));
+        back.push(t.cursor());
+    }
+    assert_eq!(back, [(0, 0), (3, 0), (0, 3), (0, 0)]);
+
+    // Without wrapping, search stops at both ends of the text
+    t.set_search_wrap(false);
+    assert!(!t.search_back(false));
+    assert_eq!(t.cursor(), (0, 0));
+    assert!(t.search_forward(false));
+    assert!(t.search_forward(false));
+    assert_eq!(t.cursor(), (3, 0));
+    assert!(!t.search_forward(false));
+    assert_eq!(t.cursor(), (3, 0));
+    assert!(t.search_back(false));
+    assert_eq!(t.cursor(), (0, 3));
+}
+
+#[test]
+fn replace_next() {
+    let mut t = TextArea::from(["ab ab", "xyz", "ab"]);
+    t.set_search_pattern("ab").unwrap();
+    t.move_cursor(tui_textarea::CursorMove::Jump(0, 1));
+
+    assert!(t.replace_next("c"));
+    assert_eq!(t.lines(), ["ab c", "xyz", "ab"]);
+    assert_eq!(t.cursor(), (0, 4));
+    assert!(t.replace_next("c"));
+    assert_eq!(t.lines(), ["ab c", "xyz", "c"]);
+    assert_eq!(t.cursor(), (2, 1));
+
+    // Wraps around the text
+    assert!(t.replace_next("あい"));
+    assert_eq!(t.lines(), ["あい c", "xyz", "c"]);
+    assert_eq!(t.cursor(), (0, 2));
+    assert!(!t.replace_next("c"));
+
+    // Each replacement is one undo unit
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["ab c", "xyz", "c"]);
+    assert_eq!(t.cursor(), (2, 1));
+    assert!(t.undo());
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["ab ab", "xyz", "ab"]);
+    assert_eq!(t.cursor(), (0, 1));
+    assert!(t.redo());
+    assert_eq!(t.lines(), ["ab c", "xyz", "ab"]);
+}
+
+#[test]
+fn replace_all() {
+    let mut t = TextArea::from(["aaa", "bab", "", "aa"]);
+    t.move_cursor(tui_textarea::CursorMove::Bottom);
+    t.move_cursor(tui_textarea::CursorMove::End);
+    t.set_search_pattern("a+").unwrap();
+
+    assert_eq!(t.replace_all("x"), 3);
+    assert_eq!(t.lines(), ["x", "bxb", "", "x"]);
+    assert_eq!(t.cursor(), (3, 1));
+
+    // The whole batch is one undo unit
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["aaa", "bab", "", "aa"]);
+    assert_eq!(t.cursor(), (3, 2));
+    assert!(t.redo());
+    assert_eq!(t.lines(), ["x", "bxb", "", "x"]);
+
+    // Empty matches are not replaced
+    t.set_search_pattern("b*").unwrap();
+    assert_eq!(t.replace_all("-"), 2);
+    assert_eq!(t.lines(), ["x", "-x-", "", "x"]);
+    assert!(!t.replace_next("y"));
+
+    assert_eq!(t.replace_all("-"), 0);
+}
+
+#[test]
+fn search_match_count() {
+    let mut t = TextArea::from(["ab ab", "xyz", "ab"]);
+    t.set_search_pattern("ab").unwrap();
+    assert_eq!(t.search_match_count(), 3);
+
+    let mut indices = vec![t.search_current_index()];
+    for _ in 0..3 {
+        t.search_forward(false);
+        indices.push(t.search_current_index());
+    }
+    assert_eq!(indices, [Some(0), Some(1), Some(2), Some(0)]);
+
+    // Cursor in the middle of a match
+    t.move_cursor(tui_textarea::CursorMove::Forward);
+    assert_eq!(t.search_current_index(), Some(0));
+    t.move_cursor(tui_textarea::CursorMove::Forward);
+    assert_eq!(t.search_current_index(), None);
+
+    // The count follows edits of the text
+    t.insert_str("ab");
+    assert_eq!(t.search_match_count(), 4);
+    assert_eq!(t.search_current_index(), None);
+    assert!(t.undo());
+    assert_eq!(t.search_match_count(), 3);
+    assert!(t.redo());
+    assert_eq!(t.search_match_count(), 4);
+    t.set_text("ab");
+    assert_eq!(t.search_match_count(), 1);
+
+    // The count follows changes of the pattern
+    t.set_search_pattern("b").unwrap();
+    assert_eq!(t.search_match_count(), 1);
+    assert_eq!(t.search_current_index(), None);
+    t.set_search_pattern("x*").unwrap();
+    assert_eq!(t.search_match_count(), 0); // Empty matches are not counted
+    t.set_search_pattern("").unwrap();
+    assert_eq!(t.search_match_count(), 0);
+}
+
+#[test]
+fn search_options() {
+    let mut t = TextArea::from(["foo Foo", "FOO"]);
+    t.set_search_pattern("Foo").unwrap();
+    assert_eq!(t.search_match_count(), 1);
+
+    let options = SearchOptions::default().case_insensitive(true);
+    t.set_search_options(options);
+    assert_eq!(t.search_options(), options);
+    assert_eq!(t.search_match_count(), 3);
+    assert!(t.search_forward(false));
+    assert_eq!(t.cursor(), (0, 4));
+    assert!(t.search_forward(false));
+    assert_eq!(t.cursor(), (1, 0));
+
+    // The option is kept when the pattern is changed
+    t.set_search_pattern("fOO").unwrap();
+    assert_eq!(t.search_match_count(), 3);
+
+    t.set_search_options(SearchOptions::default());
+    assert_eq!(t.search_match_count(), 0);
+}
+
+#[test]
+fn search_whole_word() {
+    let mut t = TextArea::from(["ab abc ab_ ab", "(ab)", "xab"]);
+    t.set_search_pattern("ab").unwrap();
+    assert_eq!(t.search_match_count(), 6);
+
+    t.set_search_options(SearchOptions::default().whole_word(true));
+    // `_` is a word character
+    assert_eq!(t.search_match_count(), 3);
+
+    let mut forward = vec![];
+    for _ in 0..3 {
+        assert!(t.search_forward(false));
+        forward.push(t.cursor());
+    }
+    assert_eq!(forward, [(0, 11), (1, 1), (0, 0)]);
+    assert!(t.search_back(false));
+    assert_eq!(t.cursor(), (1, 1));
+
+    assert_eq!(t.replace_all("x"), 3);
+    assert_eq!(t.lines(), ["x abc ab_ x", "(x)", "xab"]);
+}
@@ -0,0 +1,231 @@
+use tui_textarea::{CursorMove, SelectionMode, TextArea};
+
+#[test]
+fn selection_range() {
+    let mut t = TextArea::from(["abc", "def", "ghi"]);
+    assert_eq!(t.selection_range(), None);
+
+    t.move_cursor(CursorMove::Jump(1, 1));
+    t.start_selection();
+    assert_eq!(t.selection_range(), Some(((1, 1), (1, 1))));
+
+    // Selection extends as the cursor moves
+    t.move_cursor(CursorMove::Forward);
+    assert_eq!(t.selection_range(), Some(((1, 1), (1, 2))));
+    t.move_cursor(CursorMove::Down);
+    assert_eq!(t.selection_range(), Some(((1, 1), (2, 2))));
+
+    // Range is normalized when the cursor moves before the start position
+    t.move_cursor(CursorMove::Top);
+    assert_eq!(t.selection_range(), Some(((0, 2), (1, 1))));
+    t.move_cursor(CursorMove::Head);
+    assert_eq!(t.selection_range(), Some(((0, 0), (1, 1))));
+    t.move_cursor_to(1, 0);
+    assert_eq!(t.selection_range(), Some(((1, 0), (1, 1))));
+
+    t.cancel_selection();
+    assert_eq!(t.selection_range(), None);
+    assert_eq!(t.cursor(), (1, 0));
+
+    // Restart selection at the current cursor
+    t.start_selection();
+    t.move_word_forward();
+    t.start_selection();
+    assert_eq!(t.selection_range(), Some(((2, 0), (2, 0))));
+}
+
+#[test]
+fn edit_cancels_selection() {
+    let mut t = TextArea::from(["abc"]);
+    t.start_selection();
+    t.move_cursor(CursorMove::End);
+    t.insert_char('d');
+    assert_eq!(t.selection_range(), None);
+
+    t.start_selection();
+    t.move_cursor(CursorMove::Head);
+    assert!(t.undo());
+    assert_eq!(t.selection_range(), None);
+}
+
+#[test]
+fn copy_and_cut() {
+    let mut t = TextArea::from(["abc"]);
+    assert_eq!(t.copy(), None);
+    assert_eq!(t.cut(), None);
+    assert_eq!(t.yank_text(), "");
+
+    // Single line
+    t.move_cursor(CursorMove::End);
+    t.start_selection();
+    t.move_cursor(CursorMove::Back);
+    t.move_cursor(CursorMove::Back);
+    assert_eq!(t.copy().as_deref(), Some("bc"));
+    assert_eq!(t.selection_range(), None);
+    t.start_selection();
+    t.move_cursor(CursorMove::Forward);
+    assert_eq!(t.cut().as_deref(), Some("b"));
+    assert_eq!(t.lines(), ["ac"]);
+    assert_eq!(t.cursor(), (0, 1));
+    assert_eq!(t.yank_text(), "b");
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["abc"]);
+
+    // Empty selection
+    t.start_selection();
+    assert_eq!(t.cut().as_deref(), Some(""));
+    assert_eq!(t.lines(), ["abc"]);
+}
+
+#[test]
+fn cut_three_lines_with_wide_chars() {
+    let mut t = TextArea::from(["あいう", "🐶🐱", "漢字です", "end"]);
+    t.move_cursor(CursorMove::Jump(2, 2));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(0, 1));
+    assert_eq!(t.selection_range(), Some(((0, 1), (2, 2))));
+
+    assert_eq!(t.copy().as_deref(), Some("いう\n🐶🐱\n漢字"));
+    assert_eq!(t.lines(), ["あいう", "🐶🐱", "漢字です", "end"]);
+
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(2, 2));
+    assert_eq!(t.cut().as_deref(), Some("いう\n🐶🐱\n漢字"));
+    assert_eq!(t.lines(), ["あです", "end"]);
+    assert_eq!(t.cursor(), (0, 1));
+    assert_eq!(t.selection_range(), None);
+
+    // Cut is one undo unit
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["あいう", "🐶🐱", "漢字です", "end"]);
+    assert_eq!(t.cursor(), (2, 2));
+    assert!(t.redo());
+    assert_eq!(t.lines(), ["あです", "end"]);
+    assert_eq!(t.cursor(), (0, 1));
+
+    assert!(t.paste());
+    assert_eq!(t.lines(), ["あいう", "🐶🐱", "漢字です", "end"]);
+    assert_eq!(t.cursor(), (2, 2));
+}
+
+#[test]
+fn select_all() {
+    let mut t = TextArea::from(["abc", "", "de"]);
+    t.move_cursor(CursorMove::Jump(1, 0));
+    t.select_all();
+    assert_eq!(t.selection_range(), Some(((0, 0), (2, 2))));
+    assert_eq!(t.cursor(), (2, 2));
+    assert_eq!(t.copy().as_deref(), Some("abc\n\nde"));
+
+    t.select_all();
+    assert_eq!(t.cut().as_deref(), Some("abc\n\nde"));
+    assert_eq!(t.lines(), [""]);
+
+    t.select_all();
+    assert_eq!(t.selection_range(), Some(((0, 0), (0, 0))));
+}
+
+#[test]
+fn select_word() {
+    let mut t = TextArea::from(["foo_bar, baz", ""]);
+    t.move_cursor(CursorMove::Jump(0, 4));
+    t.select_word();
+    assert_eq!(t.selection_range(), Some(((0, 0), (0, 7))));
+    assert_eq!(t.cursor(), (0, 7));
+
+    // Punctuation
+    t.move_cursor(CursorMove::Jump(0, 7));
+    t.select_word();
+    assert_eq!(t.selection_range(), Some(((0, 7), (0, 8))));
+
+    // Whitespace
+    t.move_cursor(CursorMove::Jump(0, 8));
+    t.select_word();
+    assert_eq!(t.selection_range(), Some(((0, 8), (0, 9))));
+
+    // End of line
+    t.move_cursor(CursorMove::End);
+    t.select_word();
+    assert_eq!(t.selection_range(), Some(((0, 9), (0, 12))));
+
+    // Empty line
+    t.move_cursor(CursorMove::Down);
+    t.select_word();
+    assert_eq!(t.selection_range(), Some(((1, 0), (1, 0))));
+}
+
+#[test]
+fn word_under_cursor() {
+    let mut t = TextArea::from(["foo_bar, bäz", ""]);
+    for (col, want) in [
+        (0, Some(("foo_bar", 0..7))), // Start of word
+        (4, Some(("foo_bar", 0..7))), // Middle of word
+        (6, Some(("foo_bar", 0..7))), // End of word
+        (7, None),                    // Punctuation
+        (8, None),                    // Whitespace
+        (10, Some(("bäz", 9..12))),
+        (12, Some(("bäz", 9..12))), // End of line
+    ] {
+        t.move_cursor(CursorMove::Jump(0, col));
+        assert_eq!(t.word_under_cursor(), want, "col={}", col);
+    }
+    // The cursor and the selection are not changed
+    assert_eq!(t.cursor(), (0, 12));
+    assert_eq!(t.selection_range(), None);
+
+    // Empty line
+    t.move_cursor(CursorMove::Down);
+    assert_eq!(t.word_under_cursor(), None);
+
+    // Whitespace directly after a word
+    let mut t = TextArea::from(["foo bar"]);
+    t.move_cursor(CursorMove::Jump(0, 3));
+    assert_eq!(t.word_under_cursor(), None);
+}
+
+#[test]
+fn select_line() {
+    let mut t = TextArea::from(["abc", "de"]);
+    t.move_cursor(CursorMove::Forward);
+    t.select_line();
+    assert_eq!(t.selection_range(), Some(((0, 0), (1, 0))));
+    assert_eq!(t.cut().as_deref(), Some("abc\n"));
+    assert_eq!(t.lines(), ["de"]);
+
+    // Last line has no trailing newline
+    t.select_line();
+    assert_eq!(t.selection_range(), Some(((0, 0), (0, 2))));
+}
+
+#[test]
+fn block_selection() {
+    let mut t = TextArea::from(["abcde", "f", "ghijk"]);
+    t.set_selection_mode(SelectionMode::Block);
+
+    // Cursor at the top-left corner
+    t.move_cursor(CursorMove::Jump(2, 4));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(0, 1));
+    assert_eq!(t.selection_range(), Some(((0, 1), (2, 4))));
+    assert_eq!(t.copy().as_deref(), Some("bcd\n\nhij"));
+    assert_eq!(t.lines(), ["abcde", "f", "ghijk"]);
+
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(2, 4));
+    assert_eq!(t.cut().as_deref(), Some("bcd\n\nhij"));
+    assert_eq!(t.lines(), ["ae", "f", "gk"]);
+    assert_eq!(t.cursor(), (2, 1));
+    assert_eq!(t.selection_range(), None);
+    assert_eq!(t.yank_text(), "bcd\n\nhij");
+
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["abcde", "f", "ghijk"]);
+
+    // Empty rectangle
+    t.start_selection();
+    t.move_cursor(CursorMove::Up);
+    t.move_cursor(CursorMove::Up);
+    assert_eq!(t.selection_range(), Some(((0, 4), (2, 4))));
+    assert_eq!(t.cut().as_deref(), Some("\n\n"));
+    assert_eq!(t.lines(), ["abcde", "f", "ghijk"]);
+}
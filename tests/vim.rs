@@ -0,0 +1,221 @@
+#![cfg(feature = "vim")]
+
+use tui_textarea::{CursorMove, Input, Key, TextArea, Vim, VimMode};
+
+fn keys(vim: &mut Vim, t: &mut TextArea<'_>, s: &str) {
+    for c in s.chars() {
+        let key = if c == '\x1b' { Key::Esc } else { Key::Char(c) };
+        vim.input(
+            t,
+            Input {
+                key,
+                ctrl: false,
+                alt: false,
+                shift: false,
+            },
+        );
+    }
+}
+
+#[test]
+fn motions() {
+    let mut t = TextArea::from(["foo bar", "baz"]);
+    let mut v = Vim::new();
+    keys(&mut v, &mut t, "w");
+    assert_eq!(t.cursor(), (0, 4));
+    keys(&mut v, &mut t, "jk$");
+    assert_eq!(t.cursor(), (0, 6)); // On the last character
+    keys(&mut v, &mut t, "G0");
+    assert_eq!(t.cursor(), (1, 0));
+    keys(&mut v, &mut t, "ggl");
+    assert_eq!(t.cursor(), (0, 1));
+    assert_eq!(v.mode(), VimMode::Normal);
+}
+
+#[test]
+fn delete_and_yank_lines() {
+    let mut t = TextArea::from(["a", "b", "c"]);
+    let mut v = Vim::new();
+    keys(&mut v, &mut t, "jdd");
+    assert_eq!(t.lines(), ["a", "c"]);
+    assert_eq!(t.cursor(), (1, 0));
+    assert_eq!(t.yank_text(), "b\n");
+
+    // Deleting the last line removes the newline before it
+    keys(&mut v, &mut t, "dd");
+    assert_eq!(t.lines(), ["a"]);
+    assert_eq!(t.cursor(), (0, 0));
+
+    keys(&mut v, &mut t, "p");
+    assert_eq!(t.lines(), ["a", "c"]);
+    assert_eq!(t.cursor(), (1, 0));
+    keys(&mut v, &mut t, "yyP");
+    assert_eq!(t.lines(), ["a", "c", "c"]);
+    assert_eq!(t.cursor(), (1, 0));
+
+    keys(&mut v, &mut t, "u");
+    assert_eq!(t.lines(), ["a", "c"]);
+}
+
+#[test]
+fn operators_with_motion() {
+    let mut t = TextArea::from(["foo bar baz"]);
+    let mut v = Vim::new();
+    keys(&mut v, &mut t, "dw");
+    assert_eq!(t.lines(), ["bar baz"]);
+    keys(&mut v, &mut t, "yw$P");
+    assert_eq!(t.lines(), ["bar babar z"]);
+
+    keys(&mut v, &mut t, "0cwqux\x1b");
+    assert_eq!(t.lines(), ["quxbabar z"]);
+    assert_eq!(t.cursor(), (0, 2));
+    assert_eq!(v.mode(), VimMode::Normal);
+}
+
+#[test]
+fn edits_never_join_lines() {
+    let mut t = TextArea::from(["foo bar", "baz"]);
+    let mut v = Vim::new();
+    keys(&mut v, &mut t, "$x");
+    assert_eq!(t.lines(), ["foo ba", "baz"]);
+    assert_eq!(t.cursor(), (0, 5));
+
+    // `D` at the last character deletes only the character
+    keys(&mut v, &mut t, "D");
+    assert_eq!(t.lines(), ["foo b", "baz"]);
+    assert_eq!(t.cursor(), (0, 4));
+    keys(&mut v, &mut t, "0D");
+    assert_eq!(t.lines(), ["", "baz"]);
+    keys(&mut v, &mut t, "xDX");
+    assert_eq!(t.lines(), ["", "baz"]);
+
+    // `cc` on an empty line
+    keys(&mut v, &mut t, "ccq\x1b");
+    assert_eq!(t.lines(), ["q", "baz"]);
+    assert_eq!(t.cursor(), (0, 0));
+
+    // `X` at head of line
+    keys(&mut v, &mut t, "jX");
+    assert_eq!(t.lines(), ["q", "baz"]);
+
+    // `C` on an empty line
+    keys(&mut v, &mut t, "ddO\x1bC\x1b");
+    assert_eq!(t.lines(), ["", "q"]);
+}
+
+#[test]
+fn paste_within_limits() {
+    let mut t = TextArea::from(["abc", "de"]);
+    t.set_max_chars(Some(7));
+    let mut v = Vim::new();
+
+    // Only the whole lines fitting in the limit are pasted
+    t.set_yank_text("xy\nz\n");
+    keys(&mut v, &mut t, "p");
+    assert_eq!(t.lines(), ["abc", "xy", "de"]);
+    assert_eq!(t.cursor(), (1, 0));
+    keys(&mut v, &mut t, "P");
+    assert_eq!(t.lines(), ["abc", "xy", "de"]);
+
+    t.set_max_chars(Some(10));
+    keys(&mut v, &mut t, "P");
+    assert_eq!(t.lines(), ["abc", "xy", "z", "xy", "de"]);
+    assert_eq!(t.cursor(), (1, 0));
+
+    // Characters are truncated to the limit
+    t.set_max_chars(Some(11));
+    t.set_yank_text("uvw");
+    keys(&mut v, &mut t, "p");
+    assert_eq!(t.lines(), ["abc", "xuy", "z", "xy", "de"]);
+
+    let mut t = TextArea::from(["abc"]);
+    t.set_max_lines(Some(2));
+    t.set_yank_text("x\ny\n");
+    keys(&mut v, &mut t, "P");
+    assert_eq!(t.lines(), ["x", "abc"]);
+}
+
+#[test]
+fn insert_mode() {
+    let mut t = TextArea::from(["bc"]);
+    let mut v = Vim::new();
+    keys(&mut v, &mut t, "ia");
+    assert_eq!(v.mode(), VimMode::Insert);
+    assert_eq!(t.lines(), ["abc"]);
+    keys(&mut v, &mut t, "\x1b");
+    assert_eq!(v.mode(), VimMode::Normal);
+    assert_eq!(t.cursor(), (0, 0));
+
+    keys(&mut v, &mut t, "Ad\x1boe\x1bOf\x1b");
+    assert_eq!(t.lines(), ["abcd", "f", "e"]);
+}
+
+#[test]
+fn visual_mode() {
+    let mut t = TextArea::from(["hello world"]);
+    let mut v = Vim::new();
+    keys(&mut v, &mut t, "v");
+    assert_eq!(v.mode(), VimMode::Visual);
+    keys(&mut v, &mut t, "w");
+    assert_eq!(t.selection_range(), Some(((0, 0), (0, 6))));
+    keys(&mut v, &mut t, "d");
+    assert_eq!(v.mode(), VimMode::Normal);
+    assert_eq!(t.lines(), ["world"]);
+
+    keys(&mut v, &mut t, "vl\x1b");
+    assert_eq!(t.selection_range(), None);
+    assert_eq!(v.mode(), VimMode::Normal);
+}
+
+#[test]
+fn scroll_cursor() {
+    let mut t: TextArea = (0..20).map(|i| i.to_string()).collect();
+    let mut v = Vim::new();
+    // The viewport height is unknown before the first render so the cursor line is put at the top
+    for k in ["zz", "zt", "zb"] {
+        t.move_cursor(CursorMove::Top);
+        t.scroll_cursor_top();
+        assert_eq!(t.scrollbar_state().0, 0, "{}", k);
+        t.move_cursor(CursorMove::Jump(5, 0));
+        keys(&mut v, &mut t, k);
+        assert_eq!(t.scrollbar_state().0, 5, "{}", k);
+        assert_eq!(t.cursor(), (5, 0), "{}", k);
+    }
+}
+
+#[test]
+fn read_only() {
+    let mut t = TextArea::from(["abc"]);
+    t.set_read_only(true);
+    let mut v = Vim::new();
+    keys(&mut v, &mut t, "xddia");
+    assert_eq!(t.lines(), ["abc"]);
+    assert_eq!(v.mode(), VimMode::Normal);
+    t.move_cursor(CursorMove::End);
+    keys(&mut v, &mut t, "yy");
+    assert_eq!(t.yank_text(), "abc\n");
+}
+
+#[cfg(feature = "search")]
+#[test]
+fn search() {
+    let mut t = TextArea::from(["foo bar", "bar"]);
+    let mut v = Vim::new();
+    keys(&mut v, &mut t, "/ba");
+    assert_eq!(v.mode(), VimMode::Search);
+    assert_eq!(v.search_input(), Some("ba"));
+    let enter = Input {
+        key: Key::Enter,
+        ctrl: false,
+        alt: false,
+        shift: false,
+    };
+    assert!(!v.input(&mut t, enter));
+    assert_eq!(v.mode(), VimMode::Normal);
+    assert_eq!(v.search_input(), None);
+    assert_eq!(t.cursor(), (0, 4));
+    keys(&mut v, &mut t, "n");
+    assert_eq!(t.cursor(), (1, 0));
+    keys(&mut v, &mut t, "N");
+    assert_eq!(t.cursor(), (0, 4));
+}